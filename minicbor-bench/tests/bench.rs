@@ -101,6 +101,37 @@ fn serde_decode() {
     bench("decode/minicbor-serde", || minicbor_serde::from_slice::<AddressBook>(&sample).is_ok());
 }
 
+#[test]
+fn primitives() {
+    let mut g = StdRng::seed_from_u64(0);
+    let words: Vec<String> = iter::repeat_with(|| gen_word(&mut g)).take(10_000).collect();
+    let items: Vec<(u32, i64, &str, f64)> = words.iter()
+        .map(|w| (g.gen(), g.gen(), w.as_str(), g.gen()))
+        .collect();
+    let bytes = minicbor::to_vec(&items).unwrap();
+
+    let mut buf = Vec::new();
+
+    println!();
+    bench("encode/primitives", || {
+        buf.clear();
+        minicbor::encode(&items, &mut buf).is_ok()
+    });
+    bench("decode/primitives", || {
+        minicbor::decode::<Vec<(u32, i64, &str, f64)>>(&bytes).is_ok()
+    });
+    bench("decode/primitives/limits", || {
+        let mut d = minicbor::Decoder::new(&bytes);
+        d.set_limits(minicbor::decode::Limits::new().with_max_depth(16));
+        d.decode::<Vec<(u32, i64, &str, f64)>>().is_ok()
+    });
+}
+
+fn gen_word(g: &mut impl Rng) -> String {
+    let n = g.gen_range(1 .. 16);
+    iter::repeat_with(|| char::from(g.sample(Alphanumeric))).take(n).collect()
+}
+
 fn gen_addressbook(n: usize) -> AddressBook<'static> {
     fn gen_string(g: &mut ThreadRng) -> Cow<'static, str> {
         Cow::Owned(iter::repeat_with(|| char::from(g.sample(Alphanumeric))).take(128).collect())
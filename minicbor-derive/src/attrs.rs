@@ -36,7 +36,8 @@ enum Kind {
     ContextBound,
     CborLen,
     Tag,
-    Skip
+    Skip,
+    RejectDuplicateKeys
 }
 
 #[derive(Debug, Clone)]
@@ -53,7 +54,8 @@ enum Value {
     ContextBound(HashSet<syn::TraitBound>, proc_macro2::Span),
     CborLen(syn::ExprPath, proc_macro2::Span),
     Tag(u64, proc_macro2::Span),
-    Skip(proc_macro2::Span)
+    Skip(proc_macro2::Span),
+    RejectDuplicateKeys(proc_macro2::Span)
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -145,6 +147,8 @@ impl Attributes {
                 attrs.try_insert(Kind::Transparent, Value::Transparent(meta.path.span()))?
             } else if meta.path.is_ident("map") {
                 attrs.try_insert(Kind::Encoding, Value::Encoding(Encoding::Map, meta.path.span()))?
+            } else if meta.path.is_ident("reject_duplicate_keys") {
+                attrs.try_insert(Kind::RejectDuplicateKeys, Value::RejectDuplicateKeys(meta.path.span()))?
             } else if meta.path.is_ident("array") {
                 attrs.try_insert(Kind::Encoding, Value::Encoding(Encoding::Array, meta.path.span()))?
             } else if meta.path.is_ident("has_nil") {
@@ -262,6 +266,10 @@ impl Attributes {
         self.contains_key(Kind::Skip)
     }
 
+    pub fn reject_duplicate_keys(&self) -> bool {
+        self.contains_key(Kind::RejectDuplicateKeys)
+    }
+
     fn contains_key(&self, k: Kind) -> bool {
         self.1.contains_key(&k)
     }
@@ -285,6 +293,7 @@ impl Attributes {
                 | Kind::Transparent
                 | Kind::ContextBound
                 | Kind::Tag
+                | Kind::RejectDuplicateKeys
                 => {}
                 | Kind::TypeParam
                 | Kind::Codec
@@ -315,6 +324,7 @@ impl Attributes {
                 | Kind::IndexOnly
                 | Kind::Transparent
                 | Kind::ContextBound
+                | Kind::RejectDuplicateKeys
                 => {
                     let msg = format!("attribute is not supported on {}-level", self.0);
                     return Err(syn::Error::new(val.span(), msg))
@@ -325,6 +335,7 @@ impl Attributes {
                 | Kind::IndexOnly
                 | Kind::ContextBound
                 | Kind::Tag
+                | Kind::RejectDuplicateKeys
                 => {}
                 | Kind::TypeParam
                 | Kind::Codec
@@ -344,6 +355,7 @@ impl Attributes {
                 | Kind::Encoding
                 | Kind::Index
                 | Kind::Tag
+                | Kind::RejectDuplicateKeys
                 => {}
                 | Kind::TypeParam
                 | Kind::Codec
@@ -513,7 +525,8 @@ impl Value {
             Value::ContextBound(_, s) => *s,
             Value::CborLen(_, s)      => *s,
             Value::Tag(_, s)          => *s,
-            Value::Skip(s)            => *s
+            Value::Skip(s)            => *s,
+            Value::RejectDuplicateKeys(s) => *s
         }
     }
 
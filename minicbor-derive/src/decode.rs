@@ -71,7 +71,7 @@ fn on_struct(inp: &mut syn::DeriveInput) -> syn::Result<proc_macro2::TokenStream
         return make_transparent_impl(&inp.ident, f, impl_generics, typ_generics, where_clause)
     }
 
-    let statements = gen_statements(&fields, attrs.encoding().unwrap_or_default())?;
+    let statements = gen_statements(&fields, attrs.encoding().unwrap_or_default(), attrs.reject_duplicate_keys(), &name.to_string())?;
 
     let result = if let syn::Fields::Named(_) = data.fields {
         let nils      = nils(fields.fields());
@@ -101,14 +101,20 @@ fn on_struct(inp: &mut syn::DeriveInput) -> syn::Result<proc_macro2::TokenStream
     };
 
     let tag = decode_tag(&attrs);
+    let name_str = name.to_string();
 
     Ok(quote! {
         impl #impl_generics minicbor::Decode<'bytes, Ctx> for #name #typ_generics #where_clause {
             fn decode(__d777: &mut minicbor::Decoder<'bytes>, __ctx777: &mut Ctx) -> core::result::Result<#name #typ_generics, minicbor::decode::Error> {
-                #tag
-                let __p777 = __d777.position();
-                #statements
-                #result
+                __d777.enter()?;
+                let __r777 = (|| -> core::result::Result<#name #typ_generics, minicbor::decode::Error> {
+                    #tag
+                    let __p777 = __d777.position();
+                    #statements
+                    #result
+                })();
+                __d777.leave();
+                __r777.map_err(|e| e.in_type(#name_str))
             }
         }
     })
@@ -139,10 +145,15 @@ fn on_enum(inp: &mut syn::DeriveInput) -> syn::Result<proc_macro2::TokenStream>
         let con = &var.ident;
         let tag = decode_tag(attrs);
         let row = if let syn::Fields::Unit = var.fields {
+            let var_str = format!("{name}::{con}");
             if index_only {
-                quote!(#idx => Ok(#name::#con),)
+                quote!(#idx => {
+                    __n777 = #var_str;
+                    Ok(#name::#con)
+                })
             } else {
                 quote!(#idx => {
+                    __n777 = #var_str;
                     #tag
                     __d777.skip()?;
                     Ok(#name::#con)
@@ -159,15 +170,18 @@ fn on_enum(inp: &mut syn::DeriveInput) -> syn::Result<proc_macro2::TokenStream>
             blacklist.extend(collect_type_params(&inp.generics, fields.fields().filter(|f| {
                 f.attrs.codec().map(|c| c.is_decode()).unwrap_or(false)
             })));
-            let statements = gen_statements(&fields, encoding)?;
+            let reject = attrs.reject_duplicate_keys() || enum_attrs.reject_duplicate_keys();
+            let statements = gen_statements(&fields, encoding, reject, &format!("{name}::{con}"))?;
             if let syn::Fields::Named(_) = var.fields {
                 let nils      = nils(fields.fields());
                 let indices   = fields.fields().indices();
                 let idents    = fields.fields().idents();
                 let field_str = fields.fields().idents().map(|n| format!("{}::{}::{}", name, con, n));
                 let skipped   = fields.skipped().idents();
+                let var_str = format!("{name}::{con}");
                 quote! {
                     #idx => {
+                        __n777 = #var_str;
                         #tag
                         #statements
                         Ok(#name::#con {
@@ -187,6 +201,7 @@ fn on_enum(inp: &mut syn::DeriveInput) -> syn::Result<proc_macro2::TokenStream>
                 let expr = field_inits(&pref, &fields);
                 quote! {
                     #idx => {
+                        __n777 = #pref;
                         #tag
                         #statements
                         Ok(#name::#con(#expr))
@@ -225,16 +240,23 @@ fn on_enum(inp: &mut syn::DeriveInput) -> syn::Result<proc_macro2::TokenStream>
     };
 
     let tag = decode_tag(&enum_attrs);
+    let name_str = name.to_string();
 
     Ok(quote! {
         impl #impl_generics minicbor::Decode<'bytes, Ctx> for #name #typ_generics #where_clause {
             fn decode(__d777: &mut minicbor::Decoder<'bytes>, __ctx777: &mut Ctx) -> core::result::Result<#name #typ_generics, minicbor::decode::Error> {
-                #tag
-                #check
-                match __d777.u32()? {
-                    #(#rows)*
-                    n => Err(minicbor::decode::Error::unknown_variant(n).at(__p778))
-                }
+                __d777.enter()?;
+                let mut __n777: &'static str = #name_str;
+                let __r777 = (|| -> core::result::Result<#name #typ_generics, minicbor::decode::Error> {
+                    #tag
+                    #check
+                    match __d777.u32()? {
+                        #(#rows)*
+                        n => Err(minicbor::decode::Error::unknown_variant(n).at(__p778))
+                    }
+                })();
+                __d777.leave();
+                __r777.map_err(|e| e.in_type(__n777))
             }
         }
     })
@@ -254,11 +276,15 @@ fn on_enum(inp: &mut syn::DeriveInput) -> syn::Result<proc_macro2::TokenStream>
 // unknown enum variant was decoded -- we skip the variant value and
 // continue decoding.
 //
+// With map encoding we also remember which fields have been seen, so that
+// a repeated index can be reported as an error if `reject` is set or the
+// decoder rejects duplicate keys.
+//
 // --------------------------------------------------------------------
 // [1]: These variables will later be deconstructed in `on_enum` and
 // `on_struct` and their inner value will be used to initialise a field.
 // If not present, an error will be produced.
-fn gen_statements(fields: &Fields, encoding: Encoding) -> syn::Result<proc_macro2::TokenStream> {
+fn gen_statements(fields: &Fields, encoding: Encoding, reject: bool, name: &str) -> syn::Result<proc_macro2::TokenStream> {
     let default_decode_fn: syn::ExprPath = syn::parse_str("minicbor::Decode::decode")?;
 
     let actions = fields.fields().map(|field| {
@@ -310,13 +336,19 @@ fn gen_statements(fields: &Fields, encoding: Encoding) -> syn::Result<proc_macro
 
             let tag  = decode_tag(&field.attrs);
             let name = &field.ident;
+            let idx  = field.index.val();
+            let fstr = if field.is_name {
+                name.to_string().trim_start_matches("r#").to_string()
+            } else {
+                field.pos.to_string()
+            };
 
             quote! {{
                 #tag
                 match #decode_fn(__d777, __ctx777) {
                     Ok(__v777) => #name = #value,
                     #unknown_var_err
-                    Err(e) => return Err(e)
+                    Err(e) => return Err(e.in_field(#fstr, #idx))
                 }
             }}
     })
@@ -357,25 +389,58 @@ fn gen_statements(fields: &Fields, encoding: Encoding) -> syn::Result<proc_macro
                 __d777.skip()?
             }
         },
-        Encoding::Map => quote! {
-            #(let mut #idents : core::option::Option<#types> = #inits;)*
-
+        Encoding::Map if indices.is_empty() => quote! {
             if let Some(__len777) = __d777.map()? {
                 for _ in 0 .. __len777 {
-                    match __d777.u32()? {
-                        #(#indices => #actions)*
-                        _          => __d777.skip()?
-                    }
+                    __d777.u32()?;
+                    __d777.skip()?
                 }
             } else {
                 while minicbor::data::Type::Break != __d777.datatype()? {
-                    match __d777.u32()? {
-                        #(#indices => #actions)*
-                        _          => __d777.skip()?
-                    }
+                    __d777.u32()?;
+                    __d777.skip()?
                 }
                 __d777.skip()?
             }
+        },
+        Encoding::Map => {
+            let num = indices.len();
+            let pos = 0 .. num;
+            let dup = fields.fields().map(|f| format!("{} ({name}::{})", f.index.val(), f.ident));
+            let reject = if reject {
+                quote!(true)
+            } else {
+                quote!(__d777.rejects_duplicate_keys())
+            };
+            let checks = pos.zip(dup).map(|(i, d)| quote! {
+                if core::mem::replace(&mut __seen777[#i], true) && #reject {
+                    return Err(__d777.duplicate_map_key(__p779).with_message(#d))
+                }
+            });
+            let actions = checks.zip(actions).map(|(c, a)| quote!({ #c #a })).collect::<Vec<_>>();
+            quote! {
+                #(let mut #idents : core::option::Option<#types> = #inits;)*
+                let mut __seen777 = [false; #num];
+
+                if let Some(__len777) = __d777.map()? {
+                    for _ in 0 .. __len777 {
+                        let __p779 = __d777.position();
+                        match __d777.u32()? {
+                            #(#indices => #actions)*
+                            _          => __d777.skip()?
+                        }
+                    }
+                } else {
+                    while minicbor::data::Type::Break != __d777.datatype()? {
+                        let __p779 = __d777.position();
+                        match __d777.u32()? {
+                            #(#indices => #actions)*
+                            _          => __d777.skip()?
+                        }
+                    }
+                    __d777.skip()?
+                }
+            }
         }
     })
 }
@@ -90,6 +90,7 @@
 //! - [`#[b(...)]` and `#[cbor(b(...))]`](#n-and-b-or-cborn-and-cborb)
 //! - [`#[cbor(array)]`](#cborarray)
 //! - [`#[cbor(map)]`](#cbormap)
+//! - [`#[cbor(reject_duplicate_keys)]`](#cborreject_duplicate_keys)
 //! - [`#[cbor(index_only)]`](#cborindex_only)
 //! - [`#[cbor(transparent)]`](#cbortransparent)
 //! - [`#[cbor(skip)]`](#cborskip)
@@ -138,6 +139,16 @@
 //! If neither `#[cbor(array)]` nor `#[cbor(map)]` are specified, `#[cbor(array)]`
 //! is used by default.
 //!
+//! ## `#[cbor(reject_duplicate_keys)]`
+//!
+//! By default, if a map-encoded struct, enum or enum variant contains the same
+//! field index more than once, the last value wins. With this attribute, the
+//! generated `Decode` impl returns an error instead, for which
+//! `minicbor::decode::Error::is_duplicate_map_key` returns `true`. The same
+//! check is performed without this attribute if the decoder has been configured
+//! with `minicbor::Decoder::set_reject_duplicate_keys`. Only indices of known
+//! fields are checked. Array encoding is unaffected by this attribute.
+//!
 //! ## `#[cbor(index_only)]`
 //!
 //! Enumerations which do not contain fields may have this attribute attached to
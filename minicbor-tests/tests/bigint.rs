@@ -0,0 +1,57 @@
+#![cfg(feature = "num-bigint")]
+
+use minicbor::Decoder;
+use minicbor::data::Int;
+use num_bigint::{BigInt, BigUint};
+
+#[test]
+fn encoding() {
+    let n = BigUint::from(u64::MAX);
+    assert_eq!(minicbor::to_vec(&n).unwrap(), hex::decode("1bffffffffffffffff").unwrap());
+    assert_eq!(minicbor::to_vec(n + 1u8).unwrap(), hex::decode("c249010000000000000000").unwrap());
+
+    let n = -BigInt::from(u64::MAX) - 1;
+    assert_eq!(minicbor::to_vec(&n).unwrap(), hex::decode("3bffffffffffffffff").unwrap());
+    assert_eq!(minicbor::to_vec(n - 1).unwrap(), hex::decode("c349010000000000000000").unwrap());
+
+    // 2^200
+    let n = BigUint::from(1u8) << 200;
+    let mut b = hex::decode("c2581a01").unwrap();
+    b.extend([0; 25]);
+    assert_eq!(minicbor::to_vec(&n).unwrap(), b);
+    assert_eq!(n, minicbor::decode(&b).unwrap());
+    assert_eq!(-BigInt::from(n) - 1, minicbor::decode(&[&[0xc3][..], &b[1 ..]].concat()).unwrap())
+}
+
+#[test]
+fn decoding() {
+    // Leading zeros are accepted, except in strict mode.
+    let b = hex::decode("c243000102").unwrap();
+    assert_eq!(BigUint::from(258u16), minicbor::decode(&b).unwrap());
+    let mut d = Decoder::new(&b);
+    d.set_strict(true);
+    assert!(d.decode::<BigInt>().unwrap_err().is_non_minimal_int());
+
+    // Negative values can not be decoded as `BigUint`.
+    assert!(minicbor::decode::<BigUint>(&[0x20]).unwrap_err().is_type_mismatch());
+    let b = hex::decode("c349010000000000000000").unwrap();
+    assert!(minicbor::decode::<BigUint>(&b).unwrap_err().is_tag_mismatch());
+
+    // Other tags are rejected.
+    let b = hex::decode("c440").unwrap();
+    assert!(minicbor::decode::<BigInt>(&b).unwrap_err().is_tag_mismatch())
+}
+
+#[test]
+fn int_conversions() {
+    for i in [minicbor::data::MIN_INT, Int::from(-1), Int::from(0), minicbor::data::MAX_INT] {
+        let n = BigInt::from(i);
+        assert_eq!(i128::from(i), i128::try_from(&n).unwrap());
+        assert_eq!(i, Int::try_from(n).unwrap())
+    }
+    assert!(Int::try_from(BigInt::from(-2_i128.pow(64) - 1)).is_err());
+    assert!(Int::try_from(BigUint::from(2_u128.pow(64))).is_err());
+    assert_eq!(Int::from(u64::MAX), Int::try_from(BigUint::from(u64::MAX)).unwrap());
+    assert_eq!(BigUint::from(7u8), BigUint::try_from(Int::from(7)).unwrap());
+    assert!(BigUint::try_from(Int::from(-1)).is_err())
+}
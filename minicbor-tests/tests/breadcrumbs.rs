@@ -0,0 +1,108 @@
+#![cfg(feature = "std")]
+
+use std::collections::BTreeMap;
+
+use minicbor::{Decode, Encode};
+use minicbor::bytes::ByteVec;
+use minicbor::decode::Frame;
+
+#[derive(Debug, Encode, Decode)]
+struct Config {
+    #[n(0)] name: String,
+    #[n(1)] servers: Vec<Server>
+}
+
+#[derive(Debug, Encode, Decode)]
+struct Server {
+    #[n(0)] host: String,
+    #[n(1)] port: u16
+}
+
+#[derive(Debug, Encode, Decode)]
+#[cbor(map)]
+enum Shape {
+    #[n(0)] Circle { #[n(0)] radius: u8 },
+    #[n(1)] Rect(#[n(0)] u8, #[n(1)] u8)
+}
+
+#[test]
+fn nested_field() {
+    let mut e = minicbor::Encoder::new(Vec::new());
+    e.array(2).unwrap().str("test").unwrap().array(3).unwrap();
+    for i in 0 .. 3 {
+        e.array(2).unwrap().str(&format!("host{i}")).unwrap();
+        if i == 2 {
+            e.str("http").unwrap();
+        } else {
+            e.u16(80).unwrap();
+        }
+    }
+    let b = e.into_writer();
+
+    let e = minicbor::decode::<Config>(&b).unwrap_err();
+    assert!(e.is_type_mismatch());
+    assert_eq!(&[
+        Frame::Field("port", 1),
+        Frame::Type("Server"),
+        Frame::Index(2),
+        Frame::Field("servers", 1),
+        Frame::Type("Config")
+    ], e.path());
+    assert_eq!(
+        "Config.servers[2].port (index 1): unexpected type string at position 32: expected u16",
+        e.to_string()
+    )
+}
+
+#[test]
+fn missing_value() {
+    // ["x", [["a"], ["b"]]]
+    let b = minicbor::to_vec(("x", [vec!["a"], vec!["b"]])).unwrap();
+    let e = minicbor::decode::<Config>(&b).unwrap_err();
+    assert!(e.is_missing_value());
+    assert_eq!(
+        "Config.servers[0]: missing value at index 1 (Server::port) in map or array starting at position 4",
+        e.to_string()
+    )
+}
+
+#[test]
+fn enums_and_maps() {
+    let mut m = BTreeMap::new();
+    m.insert("a", Shape::Circle { radius: 1 });
+    m.insert("b", Shape::Rect(1, 2));
+    let mut b = minicbor::to_vec(&m).unwrap();
+    // Replace the `2` of `Rect` with an invalid value.
+    *b.last_mut().unwrap() = 0xf6;
+
+    let e = minicbor::decode::<BTreeMap<String, Shape>>(&b).unwrap_err();
+    assert_eq!(&[Frame::Field("1", 1), Frame::Type("Shape::Rect"), Frame::Key("\"b\"".into())], e.path());
+    assert!(e.to_string().starts_with("[\"b\"].1 (index 1): unexpected type null"), "{e}");
+
+    let b = minicbor::to_vec(BTreeMap::from([(-1, [1]), (2, [300])])).unwrap();
+    let e = minicbor::decode::<BTreeMap<i8, Vec<u8>>>(&b).unwrap_err();
+    assert_eq!("[2][0]: 300 overflows target type at position 6: when converting u16 to u8", e.to_string());
+
+    let b = minicbor::to_vec(BTreeMap::from([(ByteVec::from(vec![1, 2]), "x")])).unwrap();
+    let e = minicbor::decode::<BTreeMap<ByteVec, u8>>(&b).unwrap_err();
+    assert_eq!(&[Frame::Key("h'0102'".into())], e.path())
+}
+
+#[test]
+fn no_path() {
+    let e = minicbor::decode::<u8>(&[0xf6]).unwrap_err();
+    assert!(e.path().is_empty());
+    assert_eq!("unexpected type null at position 0: expected u8", e.to_string());
+
+    // Unknown enum variants keep their error kind.
+    let e = minicbor::decode::<Shape>(&[0x82, 0x07, 0x80]).unwrap_err();
+    assert!(e.is_unknown_variant());
+    assert_eq!(&[Frame::Type("Shape")], e.path())
+}
+
+#[test]
+fn error_size() {
+    // The message and path are boxed, so results stay small.
+    assert!(std::mem::size_of::<minicbor::decode::Error>() <= 8 * std::mem::size_of::<usize>());
+    assert_eq!(std::mem::size_of::<minicbor::decode::Error>(), std::mem::size_of::<Result<u64, minicbor::decode::Error>>())
+}
@@ -0,0 +1,59 @@
+#![cfg(feature = "chrono")]
+
+use chrono::{FixedOffset, NaiveDate, TimeZone, Utc};
+
+quickcheck::quickcheck! {
+    fn utc(s: i64, n: u32) -> bool {
+        let s = s.rem_euclid(253_402_300_800);
+        let x = chrono::DateTime::from_timestamp(s, n % 1_000_000_000).unwrap();
+        let b = minicbor::to_vec(x).unwrap();
+        assert_eq!(b.len(), minicbor::len(x));
+        x == minicbor::decode::<chrono::DateTime<Utc>>(&b).unwrap()
+    }
+
+    fn naive_date(d: i32) -> bool {
+        let x = NaiveDate::from_num_days_from_ce_opt(d.rem_euclid(3_652_059) + 1).unwrap();
+        let b = minicbor::to_vec(x).unwrap();
+        assert_eq!(b.len(), minicbor::len(x));
+        x == minicbor::decode::<NaiveDate>(&b).unwrap()
+    }
+}
+
+#[test]
+fn encoding() {
+    let x = Utc.with_ymd_and_hms(2013, 3, 21, 20, 4, 0).unwrap();
+    let b = hex::decode("c074323031332d30332d32315432303a30343a30305a").unwrap();
+    assert_eq!(b, minicbor::to_vec(x).unwrap());
+    assert_eq!(x, minicbor::decode::<chrono::DateTime<Utc>>(&b).unwrap());
+
+    // Tag 1
+    let b = hex::decode("c11a514b67b0").unwrap();
+    assert_eq!(x, minicbor::decode::<chrono::DateTime<Utc>>(&b).unwrap());
+
+    let o = FixedOffset::west_opt(8 * 3600).unwrap();
+    let x = o.with_ymd_and_hms(1996, 12, 19, 16, 39, 57).unwrap();
+    let b = minicbor::to_vec(x).unwrap();
+    assert_eq!(b, minicbor::to_vec(minicbor::data::Tagged::<0, _>::new("1996-12-19T16:39:57-08:00")).unwrap());
+    let y = minicbor::decode::<chrono::DateTime<FixedOffset>>(&b).unwrap();
+    assert_eq!(x, y);
+    assert_eq!(x.offset(), y.offset());
+
+    let x = NaiveDate::from_ymd_opt(1940, 10, 9).unwrap();
+    let b = hex::decode("d903ec6a313934302d31302d3039").unwrap();
+    assert_eq!(b, minicbor::to_vec(x).unwrap());
+    assert_eq!(x, minicbor::decode::<NaiveDate>(&b).unwrap());
+    assert_eq!(x, minicbor::decode::<NaiveDate>(&hex::decode("d8643929b3").unwrap()).unwrap())
+}
+
+#[test]
+fn unsupported() {
+    let x = Utc.with_ymd_and_hms(10000, 1, 1, 0, 0, 0).unwrap();
+    assert!(minicbor::to_vec(x).is_err());
+
+    let o = FixedOffset::east_opt(30).unwrap();
+    let x = o.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).unwrap();
+    assert!(minicbor::to_vec(x).is_err());
+
+    let x = NaiveDate::from_ymd_opt(-1, 1, 1).unwrap();
+    assert!(minicbor::to_vec(x).is_err())
+}
@@ -0,0 +1,134 @@
+#![cfg(feature = "std")]
+
+use minicbor::data::{Date, DateTime, Timestamp};
+
+#[test]
+fn rfc_examples() {
+    let b = hex::decode("c074323031332d30332d32315432303a30343a30305a").unwrap();
+    let d = DateTime::utc(Timestamp::from_secs(1363896240)).unwrap();
+    assert_eq!(d, minicbor::decode(&b).unwrap());
+    assert_eq!(b, minicbor::to_vec(d).unwrap());
+
+    let b = hex::decode("c11a514b67b0").unwrap();
+    let t = Timestamp::from_secs(1363896240);
+    assert_eq!(t, minicbor::decode(&b).unwrap());
+    assert_eq!(b, minicbor::to_vec(t).unwrap());
+
+    let b = hex::decode("c1fb41d452d9ec200000").unwrap();
+    let t = Timestamp::new(1363896240, 500_000_000).unwrap();
+    assert_eq!(t, minicbor::decode(&b).unwrap());
+    assert_eq!(b, minicbor::to_vec(t).unwrap());
+
+    // RFC 8943
+    let b = hex::decode("d903ec6a313934302d31302d3039").unwrap();
+    let d = Date::new(1940, 10, 9).unwrap();
+    assert_eq!(d, minicbor::decode(&b).unwrap());
+    assert_eq!(b, minicbor::to_vec(d).unwrap());
+    assert_eq!(d, minicbor::decode(&hex::decode("d8643929b3").unwrap()).unwrap());
+
+    let d = Date::new(1980, 12, 8).unwrap();
+    assert_eq!(d, minicbor::decode(&hex::decode("d864190f9a").unwrap()).unwrap());
+}
+
+#[test]
+fn either_tag() {
+    // Tag 1 decodes as UTC `DateTime`.
+    let b = hex::decode("c11a514b67b0").unwrap();
+    let d: DateTime = minicbor::decode(&b).unwrap();
+    assert_eq!("2013-03-21T20:04:00Z", d.to_string());
+
+    // Tag 0 decodes as `Timestamp`.
+    let b = minicbor::to_vec(d).unwrap();
+    assert_eq!(Timestamp::from_secs(1363896240), minicbor::decode(&b).unwrap());
+
+    // Negative fractional timestamps round towards negative infinity.
+    let b = hex::decode("c1f9be00").unwrap();
+    let t = Timestamp::new(-2, 500_000_000).unwrap();
+    assert_eq!(t, minicbor::decode(&b).unwrap());
+    assert_eq!(b, minicbor::to_vec(t).unwrap());
+    assert_eq!("1969-12-31T23:59:58.5Z", DateTime::utc(t).unwrap().to_string());
+
+    let e = minicbor::decode::<DateTime>(&hex::decode("c201").unwrap()).unwrap_err();
+    assert_eq!("unexpected tag 2 at position 0: expected tag 0 or 1", e.to_string());
+    let e = minicbor::decode::<Date>(&hex::decode("c001").unwrap()).unwrap_err();
+    assert_eq!("unexpected tag 0 at position 0: expected tag 1004 or 100", e.to_string());
+}
+
+#[test]
+fn parse_and_display() {
+    for (s, t) in [
+        ("1985-04-12T23:20:50.52Z",      "1985-04-12T23:20:50.52Z"),
+        ("1996-12-19T16:39:57-08:00",    "1996-12-19T16:39:57-08:00"),
+        ("1937-01-01T12:00:27.87+00:20", "1937-01-01T12:00:27.87+00:20"),
+        ("1990-12-31t23:59:59z",         "1990-12-31T23:59:59Z"),
+        ("2000-01-01T00:00:00-00:00",    "2000-01-01T00:00:00Z"),
+        ("2000-01-01T00:00:00.1000Z",    "2000-01-01T00:00:00.1Z"),
+        ("2000-01-01T00:00:00.0000000019Z", "2000-01-01T00:00:00.000000001Z"),
+        ("0000-01-01T00:00:00Z",         "0000-01-01T00:00:00Z"),
+        ("9999-12-31T23:59:59.999999999Z", "9999-12-31T23:59:59.999999999Z"),
+    ] {
+        let d: DateTime = s.parse().unwrap();
+        assert_eq!(t, d.to_string())
+    }
+
+    let d: DateTime = "1996-12-19T16:39:57-08:00".parse().unwrap();
+    assert_eq!(851042397, d.timestamp().secs());
+    assert_eq!(-480, d.offset());
+
+    for (s, e) in [
+        ("1990-12-31T23:59:60Z",      "second out of range"),
+        ("2013-02-29T00:00:00Z",      "day out of range"),
+        ("2013-13-01T00:00:00Z",      "month out of range"),
+        ("2013-01-01T24:00:00Z",      "hour out of range"),
+        ("2013-01-01T00:00:00+24:00", "offset out of range"),
+        ("2013-01-01 00:00:00Z",      "invalid RFC 3339 format"),
+        ("2013-01-01T00:00:00",       "invalid RFC 3339 format"),
+        ("2013-01-01T00:00:00.Z",     "invalid RFC 3339 format"),
+        ("2013-01-01T00:00:00ZZ",     "invalid RFC 3339 format"),
+        ("13-01-01T00:00:00Z",        "invalid RFC 3339 format"),
+    ] {
+        assert_eq!(e, s.parse::<DateTime>().unwrap_err().to_string(), "{s}")
+    }
+
+    assert_eq!(Date::new(2012, 2, 29).unwrap(), "2012-02-29".parse().unwrap());
+    assert!("2100-02-29".parse::<Date>().is_err());
+    assert!(Date::new(2000, 4, 31).is_err());
+    assert!(Date::new(10000, 1, 1).is_err());
+    assert!(Date::from_days(-719_529).is_err())
+}
+
+#[test]
+fn decode_errors() {
+    // Leap second.
+    let b = minicbor::to_vec(minicbor::data::Tagged::<0, _>::new("1990-12-31T23:59:60Z")).unwrap();
+    let e = minicbor::decode::<DateTime>(&b).unwrap_err();
+    assert_eq!("decode error at position 1: second out of range", e.to_string());
+
+    // Year 10000.
+    let b = minicbor::to_vec(minicbor::data::Tagged::<1, _>::new(253_402_300_800_i64)).unwrap();
+    assert!(minicbor::decode::<Timestamp>(&b).is_ok());
+    assert!(minicbor::decode::<DateTime>(&b).is_err());
+
+    // Non-finite floats.
+    let b = hex::decode("c1f97c00").unwrap();
+    let e = minicbor::decode::<Timestamp>(&b).unwrap_err();
+    assert_eq!("decode error at position 1: timestamp out of range", e.to_string())
+}
+
+#[test]
+fn system_time() {
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    for t in [
+        UNIX_EPOCH,
+        UNIX_EPOCH + Duration::new(1363896240, 500),
+        UNIX_EPOCH - Duration::new(1, 0),
+        UNIX_EPOCH - Duration::new(1, 500_000_000),
+    ] {
+        let x = Timestamp::try_from(t).unwrap();
+        assert_eq!(t, SystemTime::try_from(x).unwrap())
+    }
+
+    let x = Timestamp::try_from(UNIX_EPOCH - Duration::new(1, 500_000_000)).unwrap();
+    assert_eq!((-2, 500_000_000), (x.secs(), x.nanos()))
+}
@@ -0,0 +1,45 @@
+#![cfg(feature = "std")]
+
+use minicbor::data::{Bigfloat, Decimal, Int};
+
+#[test]
+fn rfc_examples() {
+    // 273.15
+    let b = hex::decode("c48221196ab3").unwrap();
+    assert_eq!(Decimal::new(-2, Int::from(27315)), minicbor::decode(&b).unwrap());
+    assert_eq!(b, minicbor::to_vec(Decimal::new(-2, 27315)).unwrap());
+
+    // 1.5
+    let b = hex::decode("c5822003").unwrap();
+    assert_eq!(Bigfloat::new(-1, Int::from(3)), minicbor::decode(&b).unwrap());
+    assert_eq!(b, minicbor::to_vec(Bigfloat::new(-1, 3)).unwrap());
+}
+
+#[test]
+fn bignum_mantissa() {
+    // 18446744073709551616 * 10^-1
+    let b = hex::decode("c48220c249010000000000000000").unwrap();
+    let d: Decimal<i128> = minicbor::decode(&b).unwrap();
+    assert_eq!((-1, 2_i128.pow(64)), d.into_parts());
+    assert_eq!(b, minicbor::to_vec(d).unwrap());
+    assert!(minicbor::decode::<Decimal>(&b).unwrap_err().is_type_mismatch())
+}
+
+#[test]
+fn errors() {
+    // Wrong tag.
+    let b = hex::decode("c5822003").unwrap();
+    let e = minicbor::decode::<Decimal>(&b).unwrap_err();
+    assert!(e.is_tag_mismatch());
+    assert_eq!(Some(0), e.position());
+
+    // Wrong array length.
+    let b = hex::decode("c4832003f6").unwrap();
+    let e = minicbor::decode::<Decimal>(&b).unwrap_err();
+    assert!(e.is_message());
+    assert_eq!(Some(1), e.position());
+
+    // Exponent must be an integer.
+    let b = hex::decode("c482c2410103").unwrap();
+    assert!(minicbor::decode::<Decimal>(&b).unwrap_err().is_type_mismatch())
+}
@@ -0,0 +1,122 @@
+#![cfg(feature = "std")]
+
+use minicbor::{Encode, Decode};
+use minicbor::data::Value;
+use std::collections::{BTreeMap, HashMap};
+
+#[test]
+fn map_keys_are_sorted() {
+    // Keys of different types and lengths (RFC 8949, section 4.2.1).
+    let m = Value::Map(vec![
+        (Value::from(false), 1.into()),
+        (Value::Array(vec![Value::from(-1)]), 2.into()),
+        (Value::from("aa"), 3.into()),
+        (Value::from("z"), 4.into()),
+        (Value::from(-1), 5.into()),
+        (Value::from(100), 6.into()),
+        (Value::from(10), 7.into())
+    ]);
+    let v = minicbor::to_vec_deterministic(&m).unwrap();
+    assert_eq!("a70a071864062005617a0462616103812002f401", hex::encode(&v));
+
+    // Nested maps are sorted as well.
+    let m = BTreeMap::from([(1, HashMap::from([("b", 1), ("a", 2)]))]);
+    let v = minicbor::to_vec_deterministic(&m).unwrap();
+    assert_eq!("a101a2616102616201", hex::encode(&v))
+}
+
+#[test]
+fn derived_types() {
+    #[derive(Debug, Encode, Decode, PartialEq)]
+    #[cbor(map)]
+    struct T {
+        #[n(100)] a: f64,
+        #[n(2)]   b: HashMap<String, u32>,
+        #[n(10)]  c: Vec<f32>
+    }
+
+    let t = T {
+        a: 1.5,
+        b: HashMap::from([("yy".into(), 1), ("x".into(), 2)]),
+        c: vec![0.0, 100000.0, 0.1]
+    };
+
+    let v = minicbor::to_vec_deterministic(&t).unwrap();
+    assert_eq!("a302a26178026279790\
+                10a83f90000fa47c35000fa3dcccccd\
+                1864f93e00".replace(char::is_whitespace, ""), hex::encode(&v));
+    assert_eq!(t, minicbor::decode(&v).unwrap())
+}
+
+#[test]
+fn shortest_floats() {
+    for (x, s) in [
+        (0.0, "f90000"),
+        (-0.0, "f98000"),
+        (1.0, "f93c00"),
+        (65504.0, "f97bff"),
+        (65505.0, "fa477fe100"),
+        (5.960464477539063e-8, "f90001"),
+        (6.103515625e-5, "f90400"),
+        (-4.0, "f9c400"),
+        (1.0e-7, "fb3e7ad7f29abcaf48"),
+        (100000.0, "fa47c35000"),
+        (3.4028234663852886e+38, "fa7f7fffff"),
+        (1.0e+300, "fb7e37e43c8800759c"),
+        (f64::INFINITY, "f97c00"),
+        (f64::NEG_INFINITY, "f9fc00"),
+        (f64::NAN, "f97e00"),
+        (f64::from_bits(0x7ff0_0000_0000_0001), "f97e00")
+    ] {
+        assert_eq!(s, hex::encode(minicbor::to_vec_deterministic(x).unwrap()), "{x}")
+    }
+    assert_eq!("f97e00", hex::encode(minicbor::to_vec_deterministic(f32::NAN).unwrap()));
+    assert_eq!("f97e00", hex::encode(minicbor::to_vec_deterministic(Value::F16(f32::NAN)).unwrap()))
+}
+
+#[test]
+fn invalid_items() {
+    struct Indef;
+
+    impl<C> Encode<C> for Indef {
+        fn encode<W>(&self, e: &mut minicbor::Encoder<W>, _: &mut C) -> Result<(), minicbor::encode::Error<W::Error>>
+        where
+            W: minicbor::encode::Write
+        {
+            e.begin_array()?.u8(1)?.end()?.ok()
+        }
+    }
+
+    assert!(minicbor::to_vec_deterministic(Indef).unwrap_err().is_message());
+    assert!(minicbor::to_vec_deterministic([Indef]).unwrap_err().is_message());
+
+    let dup = Value::Map(vec![(1.into(), 1.into()), (1.into(), 2.into())]);
+    assert!(minicbor::to_vec_deterministic(&dup).unwrap_err().is_message())
+}
+
+#[test]
+fn deep_nesting() {
+    // `[[...[0]...]]` or `{0: {0: ... {0: 0}...}}`
+    struct Nested(usize, bool);
+
+    impl<C> Encode<C> for Nested {
+        fn encode<W>(&self, e: &mut minicbor::Encoder<W>, _: &mut C) -> Result<(), minicbor::encode::Error<W::Error>>
+        where
+            W: minicbor::encode::Write
+        {
+            for _ in 0 .. self.0 {
+                if self.1 {
+                    e.map(1)?.u8(0)?;
+                } else {
+                    e.array(1)?;
+                }
+            }
+            e.u8(0)?.ok()
+        }
+    }
+
+    for x in [Nested(100_000, false), Nested(100_000, true)] {
+        let v = minicbor::to_vec_deterministic(&x).unwrap();
+        assert_eq!(minicbor::to_vec(&x).unwrap(), v)
+    }
+}
@@ -0,0 +1,87 @@
+#![cfg(feature = "alloc")]
+
+use minicbor::diag::parse;
+
+fn roundtrip(s: &str) -> bool {
+    let b = parse(s).unwrap();
+    let t = minicbor::display(&b).to_string();
+    s == t
+}
+
+type Item = (String, Vec<u8>, Option<f64>, i64, Option<f32>);
+
+quickcheck::quickcheck! {
+    fn display_then_parse(v: Vec<Item>) -> bool {
+        // Unescaped quotes followed by delimiters can not be parsed.
+        let v = v.into_iter()
+            .map(|(s, b, f, i, g)| (s.replace('"', ""), minicbor::bytes::ByteVec::from(b), f, i, g))
+            .collect::<Vec<_>>();
+        let b = minicbor::to_vec(&v).unwrap();
+        let s = minicbor::display(&b).to_string();
+        roundtrip(&s)
+    }
+}
+
+#[test]
+fn rfc_examples() {
+    for s in [
+        "0", "23", "24", "1000000000000", "18446744073709551615", "-18446744073709551616",
+        "-1", "-1000", "0e0", "-0e0", "1e0", "1.1e0", "1.5e0", "6.5504e4", "1e5", "3.4028235e38",
+        "1e300", "5.9604645e-8", "6.1035156e-5", "-4e0", "-4.1e0", "inf", "NaN", "-inf",
+        "false", "true", "null", "undefined", "simple(16)", "simple(255)",
+        r#"0("2013-03-21T20:04:00Z")"#, "1(1363896240)", "1(1.3638962405e9)",
+        "23(h'01 02 03 04')", "24(h'64 49 45 54 46')", "h''", "h'01 02 03 04'",
+        r#""""#, r#""a""#, r#""ü""#, r#"""\""#, "[]", "[1, 2, 3]", "[1, [2, 3], [4, 5]]",
+        "{}", "{1: 2, 3: 4}", r#"{"a": 1, "b": [2, 3]}"#, r#"["a", {"b": "c"}]"#,
+        "(_ h'01 02', h'03 04 05')", r#"(_ "strea", "ming")"#, "''_", r#"""_"#,
+        "[_ ]", "{_ }", "[_ 1, [2, 3], [_ 4, 5]]", r#"{_ "a": 1, "b": [_ 2, 3]}"#,
+        r#"{_ "Fun": true, "Amt": -2}"#, "1(2(3([4])))", r#"[", ", "a"b", "]"]"#
+    ] {
+        assert!(roundtrip(s), "{s}")
+    }
+}
+
+#[test]
+fn encoding() {
+    // Floats use the narrowest type which displays the same.
+    assert_eq!(parse("1.5e0").unwrap(), [0xf9, 0x3e, 0x00]);
+    assert_eq!(parse("1e-1").unwrap(), [0xfa, 0x3d, 0xcc, 0xcc, 0xcd]);
+    assert_eq!(parse("1.0000000149011612e-1").unwrap(), [0xfb, 0x3f, 0xb9, 0x99, 0x99, 0xa0, 0x00, 0x00, 0x00]);
+
+    // Other notations are accepted too.
+    assert_eq!(parse("1.5").unwrap(), [0xf9, 0x3e, 0x00]);
+    assert_eq!(parse("Infinity").unwrap(), [0xf9, 0x7c, 0x00]);
+    assert_eq!(parse(" [ 1 ,2 ] ").unwrap(), [0x82, 0x01, 0x02]);
+    assert_eq!(parse("h'0A0b'").unwrap(), [0x42, 0x0a, 0x0b]);
+
+    // Sequences.
+    assert_eq!(parse("1, \"a\", []").unwrap(), [0x01, 0x61, 0x61, 0x80])
+}
+
+#[test]
+fn errors() {
+    for (s, pos, msg) in [
+        ("", 0, "unexpected end of input"),
+        ("[1, 2", 5, "expected `,` or `]`"),
+        ("[1 2]", 3, "expected `,` or `]`"),
+        ("{1 2}", 3, "expected `:`"),
+        ("{1: 2", 5, "expected `,` or `}`"),
+        ("\"abc", 4, "unterminated text string"),
+        ("h'01", 4, "unterminated byte string"),
+        ("h'0x'", 3, "invalid hex digit"),
+        ("h'012'", 5, "odd number of hex digits"),
+        ("(_ h'01', \"a\")", 10, "chunks of indefinite-length strings must be of the same type"),
+        ("(_ 1)", 3, "expected byte or text string"),
+        ("simple(24)", 7, "invalid simple value"),
+        ("18446744073709551616", 0, "integer out of range"),
+        ("-1(0)", 0, "invalid tag"),
+        ("[trux]", 1, "invalid value"),
+        ("1(2", 3, "expected `)`"),
+        ("1 2", 2, "expected `,` or end of input"),
+        ("[#]", 1, "unexpected character")
+    ] {
+        let e = parse(s).unwrap_err();
+        assert_eq!((pos, msg), (e.position(), e.to_string().rsplit_once(" at position").unwrap().0), "{s}");
+    }
+    assert!(parse(&"[".repeat(1000)).unwrap_err().to_string().starts_with("nesting depth exceeds limit"))
+}
@@ -0,0 +1,107 @@
+#![cfg(feature = "std")]
+
+use std::collections::{BTreeMap, HashMap};
+use minicbor::{Decode, Decoder};
+use minicbor::decode::{Error, Frame};
+
+fn decode_unique<'b, T: Decode<'b, ()>>(b: &'b [u8]) -> Result<T, Error> {
+    let mut d = Decoder::new(b);
+    d.set_reject_duplicate_keys(true);
+    d.decode()
+}
+
+fn assert_duplicate(e: Error, pos: usize, msg: &str) {
+    assert!(e.is_duplicate_map_key(), "{e}");
+    assert_eq!(Some(pos), e.position());
+    assert_eq!(format!("duplicate map key at position {pos}: {msg}"), e.to_string())
+}
+
+fn assert_duplicate_in(e: Error, ty: &'static str, pos: usize, msg: &str) {
+    assert!(e.is_duplicate_map_key(), "{e}");
+    assert_eq!(Some(pos), e.position());
+    assert_eq!(&[Frame::Type(ty)], e.path());
+    assert_eq!(format!("{ty}: duplicate map key at position {pos}: {msg}"), e.to_string())
+}
+
+#[derive(Debug, Decode)]
+#[cbor(map)]
+struct Lenient {
+    #[n(0)] a: u8,
+    #[n(1)] b: Option<u8>
+}
+
+#[derive(Debug, Decode)]
+#[cbor(map, reject_duplicate_keys)]
+struct Strict {
+    #[n(0)] a: u8,
+    #[n(1)] b: Option<u8>
+}
+
+#[derive(Debug, Decode)]
+#[cbor(map)]
+enum E {
+    #[n(0)] A { #[n(0)] a: u8 },
+    #[n(1)] #[cbor(reject_duplicate_keys)] B { #[n(0)] a: u8 }
+}
+
+#[test]
+fn collections() {
+    // {"a": 1, "b": 2, "a": 3}
+    let b = hex::decode("a3616101616202616103").unwrap();
+
+    let m: BTreeMap<&str, u8> = minicbor::decode(&b).unwrap();
+    assert_eq!(Some(&3), m.get("a"));
+    let m: HashMap<&str, u8> = minicbor::decode(&b).unwrap();
+    assert_eq!(Some(&3), m.get("a"));
+
+    assert_duplicate(decode_unique::<BTreeMap<&str, u8>>(&b).unwrap_err(), 7, "\"a\"");
+    assert_duplicate(decode_unique::<HashMap<&str, u8>>(&b).unwrap_err(), 7, "\"a\"");
+
+    // {_ [1, 2]: 0, [1, 2]: 1}
+    let b = hex::decode("bf8201020082010201ff").unwrap();
+    assert_duplicate(decode_unique::<BTreeMap<Vec<u8>, u8>>(&b).unwrap_err(), 5, "[1, 2]");
+
+    // Keys which decode to the same value are duplicates too.
+    let b = hex::decode("a2010018010a").unwrap(); // {1: 0, 1: 10}, 2nd key non-minimal
+    assert_duplicate(decode_unique::<BTreeMap<u8, u8>>(&b).unwrap_err(), 3, "1");
+
+    let b = hex::decode("a3616101616202616303").unwrap();
+    assert_eq!(3, decode_unique::<BTreeMap<&str, u8>>(&b).unwrap().len())
+}
+
+#[test]
+fn derived() {
+    // {0: 1, 1: 2, 0: 3}
+    let b = hex::decode("a3000101020003").unwrap();
+
+    let x: Lenient = minicbor::decode(&b).unwrap();
+    assert_eq!((3, Some(2)), (x.a, x.b));
+    assert_duplicate_in(decode_unique::<Lenient>(&b).unwrap_err(), "Lenient", 5, "0 (Lenient::a)");
+    assert_duplicate_in(minicbor::decode::<Strict>(&b).unwrap_err(), "Strict", 5, "0 (Strict::a)");
+
+    // {_ 0: 1, 1: null, 1: 2}
+    let b = hex::decode("bf000101f60102ff").unwrap();
+    assert_duplicate_in(minicbor::decode::<Strict>(&b).unwrap_err(), "Strict", 5, "1 (Strict::b)");
+
+    // Unknown indices are ignored.
+    let b = hex::decode("a300010201020a").unwrap();
+    let x: Strict = minicbor::decode(&b).unwrap();
+    assert_eq!((1, None), (x.a, x.b));
+
+    // [0, {0: 1, 0: 2}] and [1, {0: 1, 0: 2}]
+    let b = hex::decode("8200a200010002").unwrap();
+    assert!(matches!(minicbor::decode(&b).unwrap(), E::A { a: 2 }));
+    assert_duplicate_in(decode_unique::<E>(&b).unwrap_err(), "E::A", 5, "0 (E::A::a)");
+    let b = hex::decode("8201a10005").unwrap();
+    assert!(matches!(minicbor::decode(&b).unwrap(), E::B { a: 5 }));
+    let b = hex::decode("8201a200010002").unwrap();
+    assert_duplicate_in(minicbor::decode::<E>(&b).unwrap_err(), "E::B", 5, "0 (E::B::a)")
+}
+
+#[test]
+fn strict_mode() {
+    let b = hex::decode("a2616101616102").unwrap(); // {"a": 1, "a": 2}
+    let mut d = Decoder::new(&b);
+    d.set_strict(true);
+    assert_duplicate(d.decode::<BTreeMap<&str, u8>>().unwrap_err(), 4, "\"a\"")
+}
@@ -0,0 +1,102 @@
+#![cfg(feature = "std")]
+
+use minicbor::{CborLen, Decode, Decoder, Encode, Encoder};
+use minicbor::data::{Embedded, Raw};
+use minicbor::decode::Limits;
+use minicbor::encode::{self, Write};
+
+#[derive(Debug, PartialEq, Encode, Decode, CborLen)]
+struct Msg {
+    #[n(0)] id: u32,
+    #[n(1)] body: Option<Embedded<Vec<String>>>
+}
+
+quickcheck::quickcheck! {
+    fn roundtrip(id: u32, body: Option<Vec<String>>) -> bool {
+        let m = Msg { id, body: body.map(Embedded::new) };
+        let b = minicbor::to_vec(&m).unwrap();
+        b.len() == minicbor::len(&m) && m == minicbor::decode(&b).unwrap()
+    }
+
+    fn nested(x: Vec<u64>) -> bool {
+        let e = Embedded::new(Embedded::new(x));
+        let b = minicbor::to_vec(&e).unwrap();
+        let y: Embedded<Embedded<Vec<u64>>> = minicbor::decode(&b).unwrap();
+        b.len() == minicbor::len(&e) && e == y
+    }
+}
+
+#[test]
+fn deferred_decoding() {
+    let b = minicbor::to_vec(Embedded::new(("a", 1))).unwrap();
+    let e: Embedded<Raw> = minicbor::decode(&b).unwrap();
+    assert_eq!(("a", 1), e.decode::<(&str, u8)>().unwrap());
+    assert_eq!(b, minicbor::to_vec(e).unwrap())
+}
+
+#[test]
+fn errors() {
+    // 24(h'8301') with an incomplete array.
+    let b = hex::decode("d818428301").unwrap();
+    let e = minicbor::decode::<Embedded<Vec<u8>>>(&b).unwrap_err();
+    assert!(e.is_end_of_input());
+    let e = minicbor::decode::<Embedded<Raw>>(&b).unwrap_err();
+    assert!(e.is_end_of_input());
+
+    // 24(h'820101') where the second element is not a string.
+    let b = hex::decode("d81843820101").unwrap();
+    let e = minicbor::decode::<Embedded<(u8, &str)>>(&b).unwrap_err();
+    assert!(e.is_type_mismatch());
+    assert_eq!(Some(5), e.position());
+
+    // 24(h'0102') with trailing bytes.
+    let b = hex::decode("d818420102").unwrap();
+    let e = minicbor::decode::<Embedded<u8>>(&b).unwrap_err();
+    assert_eq!(Some(4), e.position());
+    assert!(minicbor::decode::<Embedded<Raw>>(&b).is_err());
+
+    // Wrong tag.
+    let b = hex::decode("d81941a0").unwrap();
+    let e = minicbor::decode::<Embedded<u8>>(&b).unwrap_err();
+    assert!(e.is_tag_mismatch());
+    assert_eq!(Some(0), e.position())
+}
+
+#[test]
+fn settings_are_inherited() {
+    // 24(h'8181818180')
+    let b = hex::decode("d81845818181818080").unwrap();
+    let mut d = Decoder::new(&b[.. 8]);
+    d.set_limits(Limits::new().with_max_depth(3));
+    let e = d.decode::<Embedded<minicbor::data::Value>>().unwrap_err();
+    assert!(e.is_limit_exceeded());
+    assert_eq!(Some(6), e.position());
+
+    // 24(h'1801')
+    let b = hex::decode("d818421801").unwrap();
+    let mut d = Decoder::new(&b);
+    d.set_strict(true);
+    let e = d.decode::<Embedded<u8>>().unwrap_err();
+    assert!(e.is_non_minimal_int());
+    assert_eq!(Some(3), e.position())
+}
+
+/// A type whose `CborLen` impl is wrong.
+struct Liar;
+
+impl<C> Encode<C> for Liar {
+    fn encode<W: Write>(&self, e: &mut Encoder<W>, _: &mut C) -> Result<(), encode::Error<W::Error>> {
+        e.u16(1000)?.ok()
+    }
+}
+
+impl<C> CborLen<C> for Liar {
+    fn cbor_len(&self, _: &mut C) -> usize {
+        1
+    }
+}
+
+#[test]
+fn wrong_length() {
+    assert!(minicbor::to_vec(Embedded::new(Liar)).unwrap_err().is_message())
+}
@@ -0,0 +1,99 @@
+#![cfg(feature = "std")]
+
+use minicbor::{CborLen, Decode, Decoder, Encode, Encoder};
+use quickcheck::quickcheck;
+
+fn same(a: f64, b: f64) -> bool {
+    a.to_bits() == b.to_bits() || a.is_nan() && b.is_nan()
+}
+
+quickcheck! {
+    fn f64_identity(x: f64) -> bool {
+        let mut e = Encoder::new(Vec::new());
+        e.float(x).unwrap();
+        let v = e.into_writer();
+        let y: f64 = minicbor::float::decode(&mut Decoder::new(&v), &mut ()).unwrap();
+        v.len() == minicbor::float::cbor_len(&x, &mut ()) && v.len() <= 9 && same(x, y)
+    }
+
+    fn f32_identity(x: f32) -> bool {
+        let mut e = Encoder::new(Vec::new());
+        e.float(f64::from(x)).unwrap();
+        let v = e.into_writer();
+        let y: f32 = minicbor::float::decode(&mut Decoder::new(&v), &mut ()).unwrap();
+        v.len() == minicbor::float::cbor_len(&x, &mut ()) && v.len() <= 5 && same(x.into(), y.into())
+    }
+
+    fn f16_identity(x: u16) -> bool {
+        let x = half::f16::from_bits(x);
+        let mut e = Encoder::new(Vec::new());
+        e.float(x.to_f64()).unwrap();
+        let v = e.into_writer();
+        v.len() == 3 && (x.is_nan() || v[1 ..] == x.to_bits().to_be_bytes())
+    }
+}
+
+#[test]
+fn shortest_forms() {
+    for (x, s) in [
+        (0.0, "f90000"),
+        (-0.0, "f98000"),
+        (1.0, "f93c00"),
+        (1.5, "f93e00"),
+        (65504.0, "f97bff"),
+        (5.960464477539063e-8, "f90001"),
+        (0.00006103515625, "f90400"),
+        (-4.0, "f9c400"),
+        (100000.0, "fa47c35000"),
+        (3.4028234663852886e+38, "fa7f7fffff"),
+        (1.1, "fb3ff199999999999a"),
+        (1.0e+300, "fb7e37e43c8800759c"),
+        (-4.1, "fbc010666666666666"),
+        (f64::INFINITY, "f97c00"),
+        (f64::NEG_INFINITY, "f9fc00"),
+        (f64::NAN, "f97e00")
+    ] {
+        let mut e = Encoder::new(Vec::new());
+        e.float(x).unwrap();
+        assert_eq!(s, hex::encode(e.writer()), "{x}")
+    }
+}
+
+#[test]
+fn derived_fields() {
+    #[derive(Debug, PartialEq, Encode, Decode, CborLen)]
+    struct T {
+        #[cbor(n(0), with = "minicbor::float")]
+        a: f64,
+        #[cbor(n(1), with = "minicbor::float")]
+        b: f32,
+        #[cbor(n(2), with = "minicbor::float")]
+        c: Option<f64>,
+        #[n(3)]
+        d: f64
+    }
+
+    let t = T { a: 1.0, b: 0.1, c: Some(0.5), d: 1.0 };
+    let v = minicbor::to_vec(&t).unwrap();
+    assert_eq!("84f93c00fa3dcccccdf93800fb3ff0000000000000", hex::encode(&v));
+    assert_eq!(v.len(), minicbor::len(&t));
+    assert_eq!(t, minicbor::decode(&v).unwrap());
+
+    let t = T { a: 1.0e300, b: 1.0, c: None, d: 1.0 };
+    let v = minicbor::to_vec(&t).unwrap();
+    assert_eq!(v.len(), minicbor::len(&t));
+    assert_eq!(t, minicbor::decode(&v).unwrap())
+}
+
+#[test]
+fn full_width() {
+    for x in [0.0, 1.5, 100000.0, 0.1, 1.0e300, f64::INFINITY, f64::NAN] {
+        let v = minicbor::to_vec(x).unwrap();
+        assert_eq!(v.len(), minicbor::len(x));
+        assert!(same(x, minicbor::decode(&v).unwrap()));
+        let w = minicbor::to_vec(x as f32).unwrap();
+        assert_eq!(w.len(), minicbor::len(x as f32));
+        assert!(same(f64::from(x as f32), minicbor::decode::<f32>(&w).unwrap().into()));
+        assert_eq!((9, 5), (v.len(), w.len()))
+    }
+}
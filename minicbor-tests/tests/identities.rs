@@ -58,6 +58,22 @@ fn i64() {
     quickcheck(identity as fn(i64) -> bool)
 }
 
+#[test]
+fn u128() {
+    quickcheck(identity as fn(u128) -> bool);
+    for n in [0, u64::MAX.into(), u128::from(u64::MAX) + 1, u128::MAX] {
+        assert!(identity(n))
+    }
+}
+
+#[test]
+fn i128() {
+    quickcheck(identity as fn(i128) -> bool);
+    for n in [i128::MIN, -2_i128.pow(64) - 1, -2_i128.pow(64), -1, 0, 2_i128.pow(64), i128::MAX] {
+        assert!(identity(n))
+    }
+}
+
 #[test]
 fn int() {
     assert!(identity(Int::try_from(-2_i128.pow(64)).unwrap()));
@@ -605,6 +621,7 @@ impl Arbitrary for Ia {
             Base64,
             Regex,
             Mime,
+            Uuid,
             HomogenousArray,
             TypedArrayU8,
             TypedArrayU8Clamped,
@@ -630,7 +647,13 @@ impl Arbitrary for Ia {
             TypedArrayF64L,
             TypedArrayF128L,
             MultiDimArrayR,
-            MultiDimArrayC
+            MultiDimArrayC,
+            EpochDays,
+            FullDate,
+            StringRef,
+            SharedValue,
+            SharedRef,
+            StringRefNamespace
         ];
         Self(*g.choose(TAGS).expect("some tag"))
     }
@@ -647,3 +670,95 @@ fn tags() {
     }
     quickcheck(property as fn(_) -> bool)
 }
+
+#[cfg(feature = "num-bigint")]
+#[test]
+fn big_uint() {
+    fn property(b: Vec<u8>) -> bool {
+        identity(num_bigint::BigUint::from_bytes_be(&b))
+    }
+    quickcheck(property as fn(_) -> bool)
+}
+
+#[cfg(feature = "num-bigint")]
+#[test]
+fn big_int() {
+    fn property(neg: bool, b: Vec<u8>) -> bool {
+        let n = num_bigint::BigInt::from(num_bigint::BigUint::from_bytes_be(&b));
+        identity(if neg { -n } else { n })
+    }
+    quickcheck(property as fn(_, _) -> bool)
+}
+
+#[cfg(feature = "num-bigint")]
+#[test]
+fn big_int_same_as_i128() {
+    fn property(n: i128) -> bool {
+        let b = minicbor::to_vec(n).unwrap();
+        b == minicbor::to_vec(num_bigint::BigInt::from(n)).unwrap()
+            && num_bigint::BigInt::from(n) == minicbor::decode(&b).unwrap()
+    }
+    quickcheck(property as fn(_) -> bool)
+}
+
+#[test]
+fn decimal() {
+    fn property(e: i64, m: i64) -> bool {
+        identity(minicbor::data::Decimal::new(e, Int::from(m)))
+    }
+    quickcheck(property as fn(_, _) -> bool)
+}
+
+#[test]
+fn bigfloat() {
+    fn property(e: i64, m: i128) -> bool {
+        identity(minicbor::data::Bigfloat::new(e, m))
+    }
+    quickcheck(property as fn(_, _) -> bool)
+}
+
+/// Seconds since the Unix epoch of 0000-01-01T00:00:00Z and 9999-12-31T23:59:59Z.
+const DATETIME_RANGE: (i64, i64) = (-62_167_219_200, 253_402_300_799);
+
+fn datetime(secs: i64, nanos: u32, offset: i16) -> minicbor::data::DateTime {
+    let (min, max) = DATETIME_RANGE;
+    let s = min + 1440 * 60 + secs.rem_euclid(max - min - 2880 * 60);
+    let t = minicbor::data::Timestamp::new(s, nanos % 1_000_000_000).unwrap();
+    minicbor::data::DateTime::new(t, offset % 1440).unwrap()
+}
+
+#[test]
+fn timestamp() {
+    fn property(s: i64) -> bool {
+        identity(minicbor::data::Timestamp::from_secs(s))
+    }
+    quickcheck(property as fn(_) -> bool)
+}
+
+#[test]
+fn datetime_identity() {
+    fn property(s: i64, n: u32, o: i16) -> bool {
+        identity(datetime(s, n, o))
+    }
+    quickcheck(property as fn(_, _, _) -> bool)
+}
+
+#[test]
+fn datetime_parse() {
+    fn property(s: i64, n: u32, o: i16) -> bool {
+        let d = datetime(s, n, o);
+        d == d.to_string().parse().unwrap()
+    }
+    quickcheck(property as fn(_, _, _) -> bool)
+}
+
+#[test]
+fn date() {
+    fn property(d: i32) -> bool {
+        use minicbor::data::Date;
+        let d = Date::from_days(i64::from(d).rem_euclid(2_932_896 + 719_528) - 719_528).unwrap();
+        let e = Date::new(d.year(), d.month(), d.day()).unwrap();
+        d == e && d == d.to_string().parse().unwrap() && identity(d)
+    }
+    quickcheck(property as fn(_) -> bool)
+}
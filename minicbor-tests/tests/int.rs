@@ -30,6 +30,54 @@ fn int_bounds() {
     assert!(Int::try_from(2_i128.pow(64)).is_err())
 }
 
+#[test]
+fn bignums() {
+    // Integers are used when possible, bignums otherwise.
+    assert_eq!(minicbor::to_vec(u128::from(u64::MAX)).unwrap(), hex::decode("1bffffffffffffffff").unwrap());
+    assert_eq!(minicbor::to_vec(u128::from(u64::MAX) + 1).unwrap(), hex::decode("c249010000000000000000").unwrap());
+    assert_eq!(minicbor::to_vec(-2_i128.pow(64)).unwrap(), hex::decode("3bffffffffffffffff").unwrap());
+    assert_eq!(minicbor::to_vec(-2_i128.pow(64) - 1).unwrap(), hex::decode("c349010000000000000000").unwrap());
+    assert_eq!(minicbor::to_vec(i128::MIN).unwrap(), hex::decode("c3507fffffffffffffffffffffffffffffff").unwrap());
+
+    // Leading zeros and small bignums are accepted.
+    let b = hex::decode("c243000102").unwrap();
+    assert_eq!(258, minicbor::decode::<u128>(&b).unwrap());
+    assert_eq!(258, minicbor::decode::<i128>(&b).unwrap());
+    let b = hex::decode("c340").unwrap();
+    assert_eq!(-1, minicbor::decode::<i128>(&b).unwrap());
+
+    // ... but not in strict mode.
+    for b in ["c243000102", "c249000000000000000001", "c24101"] {
+        let b = hex::decode(b).unwrap();
+        let mut d = Decoder::new(&b);
+        d.set_strict(true);
+        assert!(d.u128().unwrap_err().is_non_minimal_int())
+    }
+    let b = hex::decode("c249010000000000000000").unwrap();
+    let mut d = Decoder::new(&b);
+    d.set_strict(true);
+    assert_eq!(2_u128.pow(64), d.u128().unwrap());
+
+    // Overflow is rejected.
+    let b = hex::decode("c2510100000000000000000000000000000000").unwrap();
+    let e = minicbor::decode::<u128>(&b).unwrap_err();
+    assert!(e.is_overflow());
+    assert_eq!(Some(0), e.position());
+    let b = hex::decode("c25080000000000000000000000000000000").unwrap();
+    assert!(minicbor::decode::<i128>(&b).unwrap_err().is_overflow());
+    let b = hex::decode("c35080000000000000000000000000000000").unwrap();
+    assert!(minicbor::decode::<i128>(&b).unwrap_err().is_overflow());
+
+    // Negative values and other tags are rejected.
+    let b = hex::decode("c340").unwrap();
+    assert!(minicbor::decode::<u128>(&b).unwrap_err().is_tag_mismatch());
+    assert!(minicbor::decode::<u128>(&[0x20]).unwrap_err().is_type_mismatch());
+    let b = hex::decode("c440").unwrap();
+    assert!(minicbor::decode::<i128>(&b).unwrap_err().is_tag_mismatch());
+    let b = hex::decode("c2f6").unwrap();
+    assert!(minicbor::decode::<i128>(&b).unwrap_err().is_type_mismatch())
+}
+
 quickcheck! {
     fn to_from_i8(n: i8) -> bool {
         let i = Int::from(n);
@@ -0,0 +1,212 @@
+#![cfg(feature = "std")]
+
+use minicbor::{Decode, Decoder};
+use minicbor::data::{Value, ValueRef};
+use minicbor::decode::{Error, Limits};
+
+fn decode_limited<'b, T: Decode<'b, ()>>(b: &'b [u8], limits: Limits) -> Result<T, Error> {
+    let mut d = Decoder::new(b);
+    d.set_limits(limits);
+    let v = d.decode()?;
+    assert_eq!(0, d.depth());
+    Ok(v)
+}
+
+fn skip_limited(b: &[u8], limits: Limits) -> Result<(), Error> {
+    let mut d = Decoder::new(b);
+    d.set_limits(limits);
+    d.skip()?;
+    assert_eq!(0, d.depth());
+    assert_eq!(b.len(), d.position());
+    Ok(())
+}
+
+fn nested_arrays(n: usize) -> Vec<u8> {
+    let mut b = vec![0x81; n];
+    b.push(0x80);
+    b
+}
+
+fn assert_limit_exceeded(e: Error, pos: usize) {
+    assert!(e.is_limit_exceeded(), "{e}");
+    assert_eq!(Some(pos), e.position())
+}
+
+#[derive(Debug, Decode)]
+struct Tree {
+    #[n(0)] children: Vec<Tree>
+}
+
+#[derive(Debug, Decode)]
+enum List {
+    #[n(0)] Nil,
+    #[n(1)] Cons(#[n(0)] u8, #[b(1)] Box<List>)
+}
+
+#[test]
+fn unlimited_by_default() {
+    let b = nested_arrays(10_000);
+    let mut d = Decoder::new(&b);
+    assert_eq!(Limits::new(), d.limits());
+    assert_eq!(Limits::default(), d.limits());
+    d.skip().unwrap();
+    assert_eq!(b.len(), d.position())
+}
+
+#[test]
+fn max_depth() {
+    let limits = Limits::new().with_max_depth(4);
+
+    let b = nested_arrays(3);
+    assert!(decode_limited::<Vec<Vec<Vec<Vec<u8>>>>>(&b, limits).is_ok());
+    assert!(decode_limited::<Value>(&b, limits).is_ok());
+    assert!(decode_limited::<ValueRef>(&b, limits).is_ok());
+    assert!(skip_limited(&b, limits).is_ok());
+
+    let b = nested_arrays(4);
+    assert_limit_exceeded(decode_limited::<Vec<Vec<Vec<Vec<Vec<u8>>>>>>(&b, limits).unwrap_err(), 4);
+    assert_limit_exceeded(decode_limited::<Value>(&b, limits).unwrap_err(), 4);
+    assert_limit_exceeded(decode_limited::<ValueRef>(&b, limits).unwrap_err(), 4);
+    assert_limit_exceeded(skip_limited(&b, limits).unwrap_err(), 4);
+
+    // [_ [_ [_ [_ [_ ]]]]]
+    let b = [0x9f, 0x9f, 0x9f, 0x9f, 0x9f, 0xff, 0xff, 0xff, 0xff, 0xff];
+    assert_limit_exceeded(decode_limited::<Value>(&b, limits).unwrap_err(), 4);
+    assert_limit_exceeded(skip_limited(&b, limits).unwrap_err(), 4);
+
+    // {0: {0: {0: {0: {0: 0}}}}}
+    let b = hex::decode("a100a100a100a100a10000").unwrap();
+    assert_limit_exceeded(decode_limited::<Value>(&b, limits).unwrap_err(), 8);
+    assert_limit_exceeded(skip_limited(&b, limits).unwrap_err(), 8);
+
+    // 24(24(24(24(24(0)))))
+    let b = hex::decode("d818d818d818d818d81800").unwrap();
+    assert!(skip_limited(&b, limits).is_ok());
+    assert_limit_exceeded(decode_limited::<Value>(&b, limits).unwrap_err(), 8);
+    assert_limit_exceeded(decode_limited::<ValueRef>(&b, limits).unwrap_err(), 8)
+}
+
+#[test]
+fn max_depth_derived() {
+    let limits = Limits::new().with_max_depth(5);
+
+    // Every level of `Tree` adds a struct and an array.
+    let t = hex::decode("818181818180").unwrap(); // [[[[[[]]]]]]
+    assert!(decode_limited::<Tree>(&t[2 ..], limits).is_ok());
+    assert_limit_exceeded(decode_limited::<Tree>(&t, limits).unwrap_err(), 5);
+
+    // Cons(1, Cons(1, Cons(1, Cons(1, Cons(1, Nil)))))
+    let l = hex::decode("8201820182018201820182018201820182018201820080").unwrap();
+    assert!(decode_limited::<List>(&l[8 ..], limits).is_ok());
+    assert_limit_exceeded(decode_limited::<List>(&l, limits).unwrap_err(), 20);
+
+    let x: Tree = minicbor::decode(&t[2 ..]).unwrap();
+    assert_eq!(1, x.children.len());
+    assert!(matches!(minicbor::decode(&l[16 ..]).unwrap(), List::Cons(1, x) if matches!(*x, List::Nil)));
+
+    // The depth is restored after errors.
+    let mut d = Decoder::new(&t);
+    d.set_limits(limits);
+    assert!(d.decode::<Tree>().is_err());
+    assert_eq!(0, d.depth())
+}
+
+#[test]
+fn max_items() {
+    let limits = Limits::new().with_max_items(3);
+
+    assert!(decode_limited::<Vec<u8>>(&[0x83, 1, 2, 3], limits).is_ok());
+    assert!(decode_limited::<Vec<u8>>(&[0x9f, 1, 2, 3, 0xff], limits).is_ok());
+    assert!(skip_limited(&[0x9f, 1, 2, 3, 0xff], limits).is_ok());
+
+    let b = [0x84, 1, 2, 3, 4];
+    assert_limit_exceeded(decode_limited::<Vec<u8>>(&b, limits).unwrap_err(), 0);
+    assert_limit_exceeded(skip_limited(&b, limits).unwrap_err(), 0);
+
+    let b = [0x9f, 1, 2, 3, 4, 0xff];
+    assert_limit_exceeded(decode_limited::<Vec<u8>>(&b, limits).unwrap_err(), 4);
+    assert_limit_exceeded(decode_limited::<Value>(&b, limits).unwrap_err(), 4);
+    assert_limit_exceeded(skip_limited(&b, limits).unwrap_err(), 4);
+
+    // {_ 1: 1, 2: 2, 3: 3, 4: 4}
+    let b = hex::decode("bf0101020203030404ff").unwrap();
+    assert_limit_exceeded(decode_limited::<Value>(&b, limits).unwrap_err(), 7);
+    assert_limit_exceeded(skip_limited(&b, limits).unwrap_err(), 7);
+
+    // A huge announced length is rejected before anything is allocated.
+    let b = hex::decode("9bffffffffffffffff").unwrap();
+    assert_limit_exceeded(decode_limited::<Vec<u8>>(&b, limits).unwrap_err(), 0)
+}
+
+#[test]
+fn max_len() {
+    let limits = Limits::new().with_max_len(4);
+
+    assert!(decode_limited::<&str>(b"\x64abcd", limits).is_ok());
+    assert!(decode_limited::<Value>(b"\x7f\x62ab\x62cd\xff", limits).is_ok());
+    assert!(skip_limited(b"\x7f\x62ab\x62cd\xff", limits).is_ok());
+
+    assert_limit_exceeded(decode_limited::<&str>(b"\x65abcde", limits).unwrap_err(), 0);
+    assert_limit_exceeded(decode_limited::<&minicbor::bytes::ByteSlice>(b"\x45abcde", limits).unwrap_err(), 0);
+    assert_limit_exceeded(skip_limited(b"\x65abcde", limits).unwrap_err(), 0);
+
+    // Chunks of indefinite strings are added up.
+    let b = b"\x7f\x62ab\x62cd\x61e\xff";
+    assert_limit_exceeded(decode_limited::<Value>(b, limits).unwrap_err(), 0);
+    assert_limit_exceeded(skip_limited(b, limits).unwrap_err(), 0);
+
+    let b = b"\x5f\x42ab\x42cd\x41e\xff";
+    assert_limit_exceeded(decode_limited::<ValueRef>(b, limits).unwrap_err(), 0)
+}
+
+#[test]
+fn max_alloc() {
+    let limits = Limits::new().with_max_alloc(10);
+
+    // ["abc", "def"] = 2 + 3 + 3
+    let b = b"\x82\x63abc\x63def";
+    assert!(decode_limited::<Vec<String>>(b, limits).is_ok());
+    assert!(skip_limited(b, limits).is_ok());
+
+    // ["abc", "def", "g"] = 3 + 3 + 3 + 1
+    let b = b"\x83\x63abc\x63def\x61g";
+    assert!(decode_limited::<Vec<String>>(b, limits).is_ok());
+
+    // ["abc", "def", "gh"] = 3 + 3 + 3 + 2
+    let b = b"\x83\x63abc\x63def\x62gh";
+    assert_limit_exceeded(decode_limited::<Vec<String>>(b, limits).unwrap_err(), 9);
+    assert_limit_exceeded(decode_limited::<Value>(b, limits).unwrap_err(), 9);
+    assert_limit_exceeded(skip_limited(b, limits).unwrap_err(), 9)
+}
+
+#[test]
+fn error_display() {
+    let b = nested_arrays(2);
+    let e = decode_limited::<Value>(&b, Limits::new().with_max_depth(1)).unwrap_err();
+    assert_eq!("[0]: nesting depth exceeds limit 1 at position 1", e.to_string())
+}
+
+#[test]
+fn iterators_do_not_change_depth() {
+    // [1, 2]
+    let b = [0x82, 0x01, 0x02];
+    let mut d = Decoder::new(&b);
+    d.set_limits(Limits::new().with_max_depth(0));
+    let mut it = d.array_iter::<u8>().unwrap();
+    assert_eq!(Some(1), it.next().transpose().unwrap());
+    assert_eq!(2, d.u8().unwrap());
+    assert_eq!(0, d.depth())
+}
+
+#[test]
+fn max_alloc_embedded() {
+    use minicbor::data::Embedded;
+
+    let limits = Limits::new().with_max_alloc(30);
+
+    // [24(<<["abc", "def"]>>), 24(<<["abc", "def"]>>)] = 2 + 2 * (9 + 2 + 3 + 3)
+    let x = Embedded::new(vec!["abc".to_string(), "def".to_string()]);
+    let b = minicbor::to_vec([&x, &x]).unwrap();
+    assert!(decode_limited::<Vec<Embedded<Vec<String>>>>(&b, Limits::new().with_max_alloc(36)).is_ok());
+    assert_limit_exceeded(decode_limited::<Vec<Embedded<Vec<String>>>>(&b, limits).unwrap_err(), 17)
+}
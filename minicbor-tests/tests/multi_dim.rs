@@ -0,0 +1,134 @@
+#![cfg(feature = "alloc")]
+
+use minicbor::data::{ArrayOrder, MultiDimArray, TypedArray};
+
+/// Create a row-major array with up to 4 dimensions of up to 4 elements each.
+fn array(dims: Vec<u8>) -> MultiDimArray<Vec<u32>> {
+    let dims: Vec<usize> = dims.into_iter().take(4).map(|d| usize::from(d % 5)).collect();
+    let dims = if dims.is_empty() { vec![1] } else { dims };
+    let n = dims.iter().product::<usize>() as u32;
+    MultiDimArray::new(dims, ArrayOrder::RowMajor, (0 .. n).collect()).unwrap()
+}
+
+/// All indices of the given dimensions in row-major order.
+fn indices(dims: &[usize]) -> Vec<Vec<usize>> {
+    dims.iter().fold(vec![Vec::new()], |acc, &d| {
+        acc.into_iter().flat_map(|i| (0 .. d).map(move |k| [i.as_slice(), &[k]].concat())).collect()
+    })
+}
+
+quickcheck::quickcheck! {
+    fn identity(dims: Vec<u8>) -> bool {
+        let a = array(dims);
+        let b = minicbor::to_vec(&a).unwrap();
+        assert_eq!(b.len(), minicbor::len(&a));
+        a == minicbor::decode(&b).unwrap()
+    }
+
+    fn order(dims: Vec<u8>) -> bool {
+        let a = array(dims);
+        let c = a.clone().into_order(ArrayOrder::ColumnMajor).unwrap();
+        assert_eq!(ArrayOrder::ColumnMajor, c.order());
+        for i in indices(a.dims()) {
+            assert_eq!(a.get(&i), c.get(&i))
+        }
+        let b = minicbor::to_vec(&c).unwrap();
+        assert_eq!(b.len(), minicbor::len(&c));
+        let c: MultiDimArray<Vec<u32>> = minicbor::decode(&b).unwrap();
+        a == c.into_order(ArrayOrder::RowMajor).unwrap()
+    }
+}
+
+#[test]
+fn column_major() {
+    // 1040([[2, 3], [1, 4, 2, 5, 3, 6]])
+    let b = hex::decode("d904108282020386010402050306").unwrap();
+    let a: MultiDimArray<Vec<u8>> = minicbor::decode(&b).unwrap();
+    assert_eq!(ArrayOrder::ColumnMajor, a.order());
+    assert_eq!(Some(&4), a.get(&[1, 0]));
+    assert_eq!(Some(&3), a.get(&[0, 2]));
+    assert_eq!(None, a.get(&[2, 0]));
+    assert_eq!(None, a.get(&[0]));
+    assert_eq!(&[1, 2, 3, 4, 5, 6], a.clone().into_order(ArrayOrder::RowMajor).unwrap().elements().as_slice());
+    assert_eq!(vec![vec![1, 2, 3], vec![4, 5, 6]], Vec::<Vec<u8>>::try_from(a.clone()).unwrap());
+    assert_eq!(b, minicbor::to_vec(&a).unwrap())
+}
+
+#[test]
+fn typed_array() {
+    // 40([[2, 3], 65(h'000200040008000400100100')])
+    let b = hex::decode("d82882820203d8414c000200040008000400100100").unwrap();
+    let a: MultiDimArray<TypedArray<u16>> = minicbor::decode(&b).unwrap();
+    assert_eq!([2, 3], a.dims());
+    assert_eq!(b, minicbor::to_vec(&a).unwrap());
+    assert_eq!(b.len(), minicbor::len(&a));
+
+    let a = a.map(|t| t.to_vec());
+    assert_eq!(Some(&0x100), a.get(&[1, 2]));
+
+    let xs = [1.5f32, 2.5, -1.0, 0.0];
+    let a = MultiDimArray::new(vec![2, 2], ArrayOrder::RowMajor, TypedArray::new(&xs)).unwrap();
+    let b = minicbor::to_vec(&a).unwrap();
+    let a: MultiDimArray<TypedArray<f32>> = minicbor::decode(&b).unwrap();
+    assert_eq!(xs.to_vec(), a.into_elements().to_vec())
+}
+
+#[test]
+fn nested() {
+    let x = vec![vec![vec![1, 2], vec![3, 4], vec![5, 6]], vec![vec![7, 8], vec![9, 10], vec![11, 12]]];
+    let a = MultiDimArray::try_from(x.clone()).unwrap();
+    assert_eq!([2, 3, 2], a.dims());
+    assert_eq!(Some(&10), a.get(&[1, 1, 1]));
+    let c = a.into_order(ArrayOrder::ColumnMajor).unwrap();
+    assert_eq!(&[1, 7, 3, 9, 5, 11, 2, 8, 4, 10, 6, 12], c.elements().as_slice());
+    assert_eq!(x, Vec::<Vec<Vec<u8>>>::try_from(c.clone()).unwrap());
+    assert!(Vec::<Vec<u8>>::try_from(c).is_err());
+
+    let a = MultiDimArray::try_from(vec![Vec::<u8>::new(), Vec::new()]).unwrap();
+    assert_eq!([2, 0], a.dims());
+    assert_eq!(vec![Vec::<u8>::new(), Vec::new()], Vec::<Vec<u8>>::try_from(a).unwrap());
+
+    assert!(MultiDimArray::try_from(vec![vec![1], vec![2, 3]]).is_err());
+    assert!(MultiDimArray::<Vec<u8>>::try_from(vec![vec![vec![1]], vec![vec![2, 3]]]).is_err());
+}
+
+#[test]
+fn dimension_errors() {
+    assert!(MultiDimArray::new(vec![2, 2], ArrayOrder::RowMajor, vec![1, 2, 3]).is_err());
+    assert!(MultiDimArray::new(vec![], ArrayOrder::RowMajor, Vec::<u8>::new()).is_err());
+    assert!(MultiDimArray::new(vec![usize::MAX, 2], ArrayOrder::RowMajor, Vec::<u8>::new()).is_err());
+    assert!(MultiDimArray::new(vec![1, 2], ArrayOrder::RowMajor, TypedArray::new(&[1u16])).is_err());
+    assert!(MultiDimArray::new(vec![1, 2], ArrayOrder::ColumnMajor, [1, 2].as_slice()).is_ok());
+
+    // Elements changed after creation are checked again.
+    let mut a = MultiDimArray::new(vec![2, 2], ArrayOrder::RowMajor, vec![1, 2, 3, 4]).unwrap();
+    a.elements_mut().pop();
+    assert!(a.clone().into_order(ArrayOrder::ColumnMajor).is_err());
+    assert!(Vec::<Vec<u8>>::try_from(a).is_err())
+}
+
+#[test]
+fn decode_errors() {
+    let e = minicbor::decode::<MultiDimArray<Vec<u8>>>(&hex::decode("d829828102820102").unwrap()).unwrap_err();
+    assert!(e.is_tag_mismatch());
+    assert_eq!("unexpected tag 41 at position 0: expected tag 40 or 1040", e.to_string());
+
+    let e = minicbor::decode::<MultiDimArray<Vec<u8>>>(&hex::decode("d828838102820102").unwrap()).unwrap_err();
+    assert_eq!("decode error at position 2: expected array of length 2", e.to_string());
+
+    let e = minicbor::decode::<MultiDimArray<Vec<u8>>>(&hex::decode("d828828082").unwrap()).unwrap_err();
+    assert_eq!("decode error at position 3: invalid dimensions", e.to_string());
+
+    let e = minicbor::decode::<MultiDimArray<Vec<u8>>>(&hex::decode("d828828103820102").unwrap()).unwrap_err();
+    assert_eq!("decode error at position 5: number of elements does not match dimensions", e.to_string());
+
+    // Indefinite-length elements
+    let e = minicbor::decode::<MultiDimArray<Vec<u8>>>(&hex::decode("d8288281039f0102ff").unwrap()).unwrap_err();
+    assert_eq!("decode error at position 5: number of elements does not match dimensions", e.to_string());
+    let a = minicbor::decode::<MultiDimArray<Vec<u8>>>(&hex::decode("d8288281029f0102ff").unwrap()).unwrap();
+    assert_eq!(&[1, 2], a.elements().as_slice());
+
+    // Typed array elements
+    let e = minicbor::decode::<MultiDimArray<TypedArray<u16>>>(&hex::decode("d828828103d8414400010002").unwrap()).unwrap_err();
+    assert_eq!("decode error at position 5: number of elements does not match dimensions", e.to_string())
+}
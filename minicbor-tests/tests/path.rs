@@ -0,0 +1,94 @@
+#![cfg(feature = "std")]
+
+use minicbor::Decoder;
+use minicbor::data::{Tag, Type, Value};
+use minicbor::decode::Segment;
+
+fn doc() -> Vec<u8> {
+    // {1: "one", 3: [0, 1, 32({"name": "x", "id": 7, h'00': [_ 1, 2]})], -1: null}
+    let inner = Value::Tag(Tag::new(32), Box::new(Value::Map(vec![
+        ("name".into(), "x".into()),
+        ("id".into(), 7u8.into()),
+        (Value::Bytes(vec![0]), Value::Null)
+    ])));
+    let v = Value::Map(vec![
+        (1u8.into(), "one".into()),
+        (3u8.into(), Value::Array(vec![0u8.into(), 1u8.into(), inner])),
+        ((-1i8).into(), Value::Null)
+    ]);
+    let mut b = minicbor::to_vec(&v).unwrap();
+    // Replace the null value of key h'00' with an indefinite array.
+    let n = b.iter().position(|x| *x == 0x40 + 1).unwrap() + 2;
+    assert_eq!(0xf6, b[n]);
+    b.splice(n .. n + 1, [0x9f, 0x01, 0x02, 0xff]);
+    b
+}
+
+#[test]
+fn found() {
+    let b = doc();
+    let d = Decoder::new(&b);
+    assert_eq!("one", d.query(&[Segment::Int(1.into())]).unwrap().str().unwrap());
+    assert_eq!(Type::Null, d.query(&[Segment::Int((-1).into())]).unwrap().datatype().unwrap());
+    assert_eq!(Type::Tag, d.query(&[Segment::Int(3.into()), Segment::Index(2)]).unwrap().datatype().unwrap());
+
+    let path = [Segment::Int(3.into()), Segment::Index(2), Segment::Str("id")];
+    assert_eq!(7, d.query(&path).unwrap().u8().unwrap());
+
+    let path = [Segment::Int(3.into()), Segment::Index(2), Segment::Bytes(&[0]), Segment::Index(1)];
+    let r = d.query_span(&path).unwrap();
+    assert_eq!([0x02], b[r]);
+
+    // The decoder itself does not move.
+    assert_eq!(0, d.position());
+    assert_eq!(b.len(), d.query_span(&[]).unwrap().end)
+}
+
+#[test]
+fn indefinite_items() {
+    // {_ "a": [_ 1, 2, 3], (_ "b", "c"): 4}
+    let b = hex::decode("bf61619f010203ff7f61626163ff04ff").unwrap();
+    let d = Decoder::new(&b);
+    assert_eq!(3, d.query(&[Segment::Str("a"), Segment::Index(2)]).unwrap().u8().unwrap());
+    assert_eq!(4, d.query(&[Segment::Str("bc")]).unwrap().u8().unwrap());
+    assert!(d.query(&[Segment::Str("a"), Segment::Index(3)]).unwrap_err().is_not_found());
+    assert!(d.query(&[Segment::Str("b")]).unwrap_err().is_not_found());
+    assert!(d.query(&[Segment::Str("abc")]).unwrap_err().is_not_found());
+}
+
+#[test]
+fn errors() {
+    let b = doc();
+    let d = Decoder::new(&b);
+
+    let e = d.query(&[Segment::Int(3.into()), Segment::Index(3)]).unwrap_err();
+    assert!(e.is_not_found());
+    assert_eq!(Some(7), e.position());
+    assert_eq!("path segment not found in item at position 7: \
+        array index out of bounds for segment 1 ([3])", e.to_string());
+
+    let e = d.query(&[Segment::Int(3.into()), Segment::Index(2), Segment::Str("ID")]).unwrap_err();
+    assert!(e.is_not_found());
+    assert_eq!(Some(12), e.position());
+    assert_eq!("path segment not found in item at position 12: \
+        map key not found for segment 2 (\"ID\")", e.to_string());
+
+    let e = d.query(&[Segment::Int(1.into()), Segment::Index(0)]).unwrap_err();
+    assert!(e.is_type_mismatch());
+    assert_eq!(Some(2), e.position());
+    assert_eq!("unexpected type string at position 2: \
+        expected array for segment 1 ([0])", e.to_string());
+
+    let e = d.query(&[Segment::Index(0)]).unwrap_err();
+    assert!(e.is_type_mismatch());
+    assert_eq!(Some(0), e.position());
+
+    // Keys of other types do not match.
+    let e = d.query(&[Segment::Str("1")]).unwrap_err();
+    assert!(e.is_not_found());
+
+    // Truncated input is reported.
+    let path = [Segment::Int(3.into()), Segment::Index(2), Segment::Str("id")];
+    let e = Decoder::new(&b[.. 16]).query(&path).unwrap_err();
+    assert!(e.is_end_of_input())
+}
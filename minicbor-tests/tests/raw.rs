@@ -0,0 +1,75 @@
+#![cfg(feature = "std")]
+
+use minicbor::{CborLen, Decode, Encode};
+use minicbor::data::{Raw, RawBuf};
+
+#[derive(Debug, Encode, Decode, CborLen)]
+struct Envelope<'a> {
+    #[n(0)] kind: u8,
+    #[b(1)] payload: Raw<'a>
+}
+
+#[derive(Debug, Encode, Decode, CborLen)]
+struct OwnedEnvelope {
+    #[n(0)] kind: u8,
+    #[n(1)] payload: RawBuf
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[cbor(map)]
+struct Payload {
+    #[n(0)] name: String,
+    #[n(1)] values: Vec<i32>
+}
+
+#[test]
+fn deferred_decoding() {
+    let p = Payload { name: "x".into(), values: vec![1, -2, 3] };
+    let b = minicbor::to_vec((7, &p)).unwrap();
+
+    let e: Envelope = minicbor::decode(&b).unwrap();
+    assert_eq!(7, e.kind);
+    assert_eq!(minicbor::to_vec(&p).unwrap(), e.payload.as_bytes());
+    assert_eq!(p, e.payload.decode::<Payload>().unwrap());
+    assert_eq!(b.len(), minicbor::len(&e));
+
+    let e: OwnedEnvelope = minicbor::decode(&b).unwrap();
+    assert_eq!(p, e.payload.decode::<Payload>().unwrap());
+    assert_eq!(b, minicbor::to_vec(&e).unwrap());
+    assert_eq!(b.len(), minicbor::len(&e))
+}
+
+#[test]
+fn forwarded_verbatim() {
+    // [0, [_ 1, (_ "a", "b"), 0x1801]], i.e. with indefinite items and a
+    // non-minimal integer which are not re-encoded.
+    let b = hex::decode("82009f017f61616162ff1801ff").unwrap();
+    let e: Envelope = minicbor::decode(&b).unwrap();
+    assert_eq!(&b[2 ..], e.payload.as_bytes());
+    assert_eq!(b, minicbor::to_vec(&e).unwrap());
+
+    let r = e.payload.to_raw_buf();
+    assert_eq!(r, RawBuf::from(e.payload));
+    assert_eq!(e.payload, r.as_raw());
+    assert_eq!(b[2 ..], r.clone().into_bytes());
+    assert_eq!("Raw(9f017f61616162ff1801ff)", format!("{:?}", e.payload))
+}
+
+#[test]
+fn validation() {
+    assert!(Raw::new(&[0x82, 0x01, 0x02]).is_ok());
+    assert!(RawBuf::new(vec![0xf6]).is_ok());
+
+    // Trailing bytes.
+    let e = Raw::new(&[0x01, 0x02]).unwrap_err();
+    assert_eq!(Some(1), e.position());
+    assert!(RawBuf::new(vec![0x80, 0x80]).is_err());
+
+    // Incomplete or malformed items.
+    assert!(Raw::new(&[]).unwrap_err().is_end_of_input());
+    assert!(Raw::new(&[0x82, 0x01]).unwrap_err().is_end_of_input());
+    assert!(Raw::new(&[0xff]).unwrap_err().is_type_mismatch());
+    assert!(Raw::new(&[0x82, 0x01, 0xff]).unwrap_err().is_type_mismatch());
+    assert!(Raw::new(&[0x62, 0xff, 0xfe]).is_err());
+    assert!(minicbor::decode::<Envelope>(&[0x82, 0x00, 0x9f, 0x01]).is_err())
+}
@@ -0,0 +1,211 @@
+#![cfg(feature = "std")]
+
+use std::collections::BTreeMap;
+use std::io::{self, Read};
+use minicbor::data::Value;
+use minicbor::decode::{Limits, ReadDecoder};
+
+/// A reader returning at most one byte per read, with an interruption
+/// before every byte.
+struct Trickle<'a>(&'a [u8], bool);
+
+impl Read for Trickle<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.1 = !self.1;
+        if self.1 {
+            return Err(io::ErrorKind::Interrupted.into())
+        }
+        match (self.0.split_first(), buf.first_mut()) {
+            (Some((x, rest)), Some(b)) => {
+                *b = *x;
+                self.0 = rest;
+                Ok(1)
+            }
+            _ => Ok(0)
+        }
+    }
+}
+
+/// A reader which fails after the given bytes.
+struct Failing<'a>(&'a [u8]);
+
+impl Read for Failing<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.0.is_empty() {
+            return Err(io::Error::other("broken pipe"))
+        }
+        self.0.read(buf)
+    }
+}
+
+quickcheck::quickcheck! {
+    fn sequence(items: Vec<(u64, String, Vec<i32>)>) -> bool {
+        let mut b = Vec::new();
+        for x in &items {
+            minicbor::encode(x, &mut b).unwrap()
+        }
+        let mut d = ReadDecoder::new(Trickle(&b, false));
+        for x in &items {
+            if Some(x) != d.decode::<(u64, String, Vec<i32>)>().unwrap().as_ref() {
+                return false
+            }
+        }
+        d.decode::<u8>().unwrap().is_none()
+    }
+}
+
+#[test]
+fn items_larger_than_chunks() {
+    let v = vec![Value::Bytes(vec![7; 100_000]), Value::String("x".repeat(20_000)), Value::Null];
+    let b = minicbor::to_vec(&v).unwrap();
+    let w: Value = minicbor::decode_from_reader(b.as_slice()).unwrap();
+    assert_eq!(Value::Array(v), w);
+
+    // (_ "abc", "d") followed by an indefinite array of 50,000 elements.
+    let mut b = hex::decode("7f636162636164ff9f").unwrap();
+    b.resize(b.len() + 50_000, 0x01);
+    b.push(0xff);
+    let mut d = ReadDecoder::new(b.as_slice());
+    assert_eq!(Some("abcd"), d.decode::<Value>().unwrap().as_ref().and_then(Value::as_str));
+    assert_eq!(Some(50_000), d.decode::<Vec<u8>>().unwrap().map(|v| v.len()));
+    assert!(d.read_item().unwrap().is_none())
+}
+
+#[test]
+fn borrowed_items() {
+    let b = hex::decode("6449455446820102").unwrap(); // "IETF", [1, 2]
+    let mut d = ReadDecoder::new(b.as_slice());
+    assert_eq!(Some("IETF"), d.decode::<&str>().unwrap());
+    assert_eq!(Some(&[0x82, 0x01, 0x02][..]), d.read_item().unwrap());
+    assert_eq!(None, d.read_item().unwrap())
+}
+
+#[test]
+fn errors() {
+    // End of input in the middle of an item.
+    let b = hex::decode("0183010203830405").unwrap();
+    let mut d = ReadDecoder::new(b.as_slice());
+    assert_eq!(Some(1), d.decode::<u8>().unwrap());
+    assert_eq!(Some(vec![1, 2, 3]), d.decode::<Vec<u8>>().unwrap());
+    assert!(d.decode::<Vec<u8>>().unwrap_err().is_end_of_input());
+    assert!(minicbor::decode_from_reader::<u8, _>(io::empty()).unwrap_err().is_end_of_input());
+
+    // I/O errors.
+    let mut d = ReadDecoder::new(Failing(&[0x01, 0x82, 0x01]));
+    assert_eq!(Some(1), d.decode::<u8>().unwrap());
+    let e = d.decode::<Vec<u8>>().unwrap_err();
+    assert!(e.is_custom());
+    assert_eq!("broken pipe", std::error::Error::source(&e).unwrap().to_string());
+
+    // Items exceeding the max. length are not read.
+    let b = hex::decode("5a0000ffff").unwrap();
+    let mut d = ReadDecoder::new(b.as_slice());
+    d.set_max_len(1024);
+    assert!(d.read_item().unwrap_err().is_limit_exceeded());
+
+    // Decoding limits apply.
+    let b = hex::decode("8181818180").unwrap();
+    let mut d = ReadDecoder::new(b.as_slice());
+    d.set_limits(Limits::new().with_max_depth(2));
+    assert!(d.decode::<Value>().unwrap_err().is_limit_exceeded());
+
+    // Strict mode and duplicate key rejection apply.
+    let b = hex::decode("1801a201020103").unwrap(); // 1, {1: 2, 1: 3}
+    let mut d = ReadDecoder::new(b.as_slice());
+    d.set_strict(true);
+    assert!(d.decode::<u8>().unwrap_err().is_non_minimal_int());
+    let mut d = ReadDecoder::new(&b[2 ..]);
+    d.set_reject_duplicate_keys(true);
+    assert!(d.decode::<BTreeMap<u8, u8>>().unwrap_err().is_duplicate_map_key());
+
+    // Decoding errors do not affect the next item.
+    let b = hex::decode("6161820102").unwrap(); // "a", [1, 2]
+    let mut d = ReadDecoder::new(b.as_slice());
+    assert!(d.decode::<u8>().unwrap_err().is_type_mismatch());
+    assert_eq!(Some(vec![1, 2]), d.decode::<Vec<u8>>().unwrap())
+}
+
+#[test]
+fn into_parts() {
+    let b = hex::decode("01020304").unwrap();
+    let mut d = ReadDecoder::new(b.as_slice());
+    assert_eq!(Some(1), d.decode::<u8>().unwrap());
+    let (r, rest) = d.into_parts();
+    assert!(r.is_empty());
+    assert_eq!([2, 3, 4], rest.as_slice())
+}
+
+#[test]
+fn streamed_arrays() {
+    // An array of 1,000,000 elements is read one element at a time,
+    // although the array as a whole exceeds the max. item length.
+    let n = 1_000_000;
+    let head: &[u8] = &[0x9a, 0x00, 0x0f, 0x42, 0x40];
+    let mut d = ReadDecoder::new(head.chain(io::repeat(0x01).take(n)));
+    d.set_max_len(16);
+    let mut count = 0;
+    for x in d.array_iter::<u8>().unwrap() {
+        assert_eq!(1, x.unwrap());
+        count += 1
+    }
+    assert_eq!(n, count);
+    assert!(d.read_item().unwrap().is_none());
+
+    // [_ "a", "b"], ["c"] where the elements of the first array are streamed.
+    let b = hex::decode("9f61616162ff816163").unwrap();
+    let mut d = ReadDecoder::new(Trickle(&b, false));
+    let v = d.array_iter::<String>().unwrap().collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(["a", "b"], v.as_slice());
+    assert_eq!(Some(vec!["c".to_string()]), d.decode::<Vec<String>>().unwrap());
+    assert!(d.read_item().unwrap().is_none())
+}
+
+#[test]
+fn streamed_maps() {
+    // {1: "a", 2: "b"}, {_ 3: [4]}
+    let b = hex::decode("a2016161026162bf038104ff").unwrap();
+    let mut d = ReadDecoder::new(Trickle(&b, false));
+    let m = d.map_iter::<u8, String>().unwrap().collect::<Result<BTreeMap<_, _>, _>>().unwrap();
+    assert_eq!(BTreeMap::from([(1, "a".to_string()), (2, "b".to_string())]), m);
+    let m = d.map_iter::<u8, Vec<u8>>().unwrap().collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(vec![(3, vec![4])], m);
+    assert!(d.read_item().unwrap().is_none())
+}
+
+#[test]
+fn streamed_errors() {
+    // Not an array.
+    let b = hex::decode("a0").unwrap();
+    assert!(ReadDecoder::new(b.as_slice()).array_iter::<u8>().unwrap_err().is_type_mismatch());
+
+    // The reader ends early.
+    let b = hex::decode("83010203").unwrap();
+    let mut d = ReadDecoder::new(&b[.. 3]);
+    let mut it = d.array_iter::<u8>().unwrap();
+    assert_eq!(Some(1), it.next().transpose().unwrap());
+    assert_eq!(Some(2), it.next().transpose().unwrap());
+    assert!(it.next().unwrap().unwrap_err().is_end_of_input());
+    assert!(ReadDecoder::new(io::empty()).array_iter::<u8>().unwrap_err().is_end_of_input());
+
+    // Limits apply to the array as a whole.
+    let b = hex::decode("8281018102").unwrap(); // [[1], [2]]
+    let mut d = ReadDecoder::new(b.as_slice());
+    d.set_limits(Limits::new().with_max_depth(1));
+    let e = d.array_iter::<Vec<u8>>().unwrap().next().unwrap().unwrap_err();
+    assert!(e.is_limit_exceeded());
+    let mut d = ReadDecoder::new(b.as_slice());
+    d.set_limits(Limits::new().with_max_items(1));
+    assert!(d.array_iter::<Vec<u8>>().unwrap_err().is_limit_exceeded());
+    let b = hex::decode("9f0102ff").unwrap();
+    let mut d = ReadDecoder::new(b.as_slice());
+    d.set_limits(Limits::new().with_max_items(1));
+    let mut it = d.array_iter::<u8>().unwrap();
+    assert_eq!(Some(1), it.next().transpose().unwrap());
+    assert!(it.next().unwrap().unwrap_err().is_limit_exceeded());
+
+    // Strict mode applies to the head.
+    let b = hex::decode("980101").unwrap();
+    let mut d = ReadDecoder::new(b.as_slice());
+    d.set_strict(true);
+    assert!(d.array_iter::<u8>().unwrap_err().is_non_minimal_int())
+}
@@ -0,0 +1,285 @@
+#![cfg(feature = "std")]
+
+use std::rc::Rc;
+use std::sync::Arc;
+
+use minicbor::{Decode, Decoder, Encode, Encoder};
+use minicbor::bytes::ByteVec;
+use minicbor::data::{Embedded, Raw, Shared, SharedValues, StringRefs, ValueRef};
+use minicbor::decode;
+use minicbor::encode::{self, Write};
+
+quickcheck::quickcheck! {
+    fn string_refs_identity(xs: Vec<(u8, bool)>) -> bool {
+        let xs: Vec<String> = xs.into_iter()
+            .map(|(n, b)| if b { format!("string-{}", n % 16) } else { n.to_string() })
+            .collect();
+        let b = minicbor::to_vec(StringRefs::new(&xs)).unwrap();
+        assert!(b.len() <= minicbor::len(&xs) + 3);
+        let ys: StringRefs<Vec<String>> = minicbor::decode(&b).unwrap();
+        xs == *ys
+    }
+
+    fn bytes_refs_identity(xs: Vec<u8>) -> bool {
+        let xs: Vec<ByteVec> = xs.into_iter().map(|n| ByteVec::from(vec![n % 4; 5])).collect();
+        let b = minicbor::to_vec(StringRefs::new(&xs)).unwrap();
+        let ys: StringRefs<Vec<ByteVec>> = minicbor::decode(&b).unwrap();
+        xs == *ys
+    }
+}
+
+#[test]
+fn stringref_example() {
+    // Example from <http://cbor.schmorp.de/stringref>.
+    let xs = [
+        "1", "222", "333", "4", "555", "666", "777", "888", "999", "aaa",
+        "bbb", "ccc", "ddd", "eee", "fff", "ggg", "hhh", "iii", "jjj", "kkk",
+        "lll", "mmm", "nnn", "ooo", "ppp", "qqq", "rrr", "333", "ssss", "qqq",
+        "rrr", "ssss"
+    ];
+    let b = minicbor::to_vec(StringRefs::new(xs)).unwrap();
+    assert_eq!(minicbor::display(&b).to_string(), concat!(
+        r#"256(["1", "222", "333", "4", "555", "666", "777", "888", "999", "aaa", "#,
+        r#""bbb", "ccc", "ddd", "eee", "fff", "ggg", "hhh", "iii", "jjj", "kkk", "#,
+        r#""lll", "mmm", "nnn", "ooo", "ppp", "qqq", "rrr", 25(1), "ssss", 25(23), "#,
+        r#""rrr", 25(24)])"#
+    ));
+    let ys: StringRefs<Vec<&str>> = minicbor::decode(&b).unwrap();
+    assert_eq!(xs.as_slice(), ys.as_slice())
+}
+
+#[test]
+fn nested_namespaces() {
+    let x = ("aaa", StringRefs::new(["aaa", "aaa"]), "aaa");
+    let b = minicbor::to_vec(StringRefs::new(x)).unwrap();
+    assert_eq!(r#"256(["aaa", 256(["aaa", 25(0)]), 25(0)])"#, minicbor::display(&b).to_string());
+    let y: StringRefs<(&str, StringRefs<[&str; 2]>, &str)> = minicbor::decode(&b).unwrap();
+    assert_eq!(x, *y);
+
+    // Strings outside of any namespace are not referenced.
+    let b = minicbor::to_vec(("aaa", StringRefs::new("aaa"), "aaa")).unwrap();
+    assert_eq!(r#"["aaa", 256("aaa"), "aaa"]"#, minicbor::display(&b).to_string());
+}
+
+#[test]
+fn skip_nested_namespace() {
+    #[derive(Encode)]
+    struct A {
+        #[n(0)] inner: StringRefs<Vec<String>>,
+        #[n(1)] x: String,
+        #[n(2)] y: String
+    }
+
+    #[derive(Debug, PartialEq, Decode)]
+    struct B {
+        #[n(1)] x: String,
+        #[n(2)] y: String
+    }
+
+    let a = A {
+        inner: StringRefs::new(vec!["inner".into(), "inner".into()]),
+        x: "outer".into(),
+        y: "outer".into()
+    };
+    let b = minicbor::to_vec(StringRefs::new(a)).unwrap();
+    assert_eq!(r#"256([256(["inner", 25(0)]), "outer", 25(0)])"#, minicbor::display(&b).to_string());
+    let b: StringRefs<B> = minicbor::decode(&b).unwrap();
+    assert_eq!(B { x: "outer".into(), y: "outer".into() }, b.into_value())
+}
+
+#[test]
+fn indefinite_strings_are_not_referenced() {
+    struct Chunks;
+
+    impl<C> Encode<C> for Chunks {
+        fn encode<W: Write>(&self, e: &mut Encoder<W>, _: &mut C) -> Result<(), encode::Error<W::Error>> {
+            e.array(3)?
+                .begin_str()?.str("abc")?.str("abc")?.end()?
+                .str("abc")?
+                .str("abc")?
+                .ok()
+        }
+    }
+
+    let b = minicbor::to_vec(StringRefs::new(Chunks)).unwrap();
+    assert_eq!(r#"256([(_ "abc", "abc"), "abc", 25(0)])"#, minicbor::display(&b).to_string());
+    let x: StringRefs<(ValueRef, &str, &str)> = minicbor::decode(&b).unwrap();
+    assert_eq!((ValueRef::StringIndef(vec!["abc", "abc"]), "abc", "abc"), x.into_value())
+}
+
+#[test]
+fn raw_in_namespace() {
+    // Strings of raw items count like any other, including duplicates.
+    let r = minicbor::to_vec(["abcdef", "abcdef"]).unwrap();
+    let r = minicbor::decode::<Raw>(&r).unwrap();
+    let x = (r, "xyzxyz", "xyzxyz", "abcdef");
+    let b = minicbor::to_vec(StringRefs::new(x)).unwrap();
+    assert_eq!(r#"256([["abcdef", "abcdef"], "xyzxyz", 25(2), 25(0)])"#, minicbor::display(&b).to_string());
+    let y: StringRefs<([&str; 2], &str, &str, &str)> = minicbor::decode(&b).unwrap();
+    assert_eq!((["abcdef", "abcdef"], "xyzxyz", "xyzxyz", "abcdef"), y.into_value());
+
+    // Nested namespaces in raw items are skipped over.
+    let r = minicbor::to_vec(StringRefs::new(["abcdef", "abcdef"])).unwrap();
+    let r = minicbor::decode::<Raw>(&r).unwrap();
+    let x = (r, "xyzxyz", "xyzxyz");
+    let b = minicbor::to_vec(StringRefs::new(x)).unwrap();
+    assert_eq!(r#"256([256(["abcdef", 25(0)]), "xyzxyz", 25(0)])"#, minicbor::display(&b).to_string());
+    let y: StringRefs<(StringRefs<[&str; 2]>, &str, &str)> = minicbor::decode(&b).unwrap();
+    assert_eq!(("xyzxyz", "xyzxyz"), (y.1, y.2))
+}
+
+#[test]
+fn embedded_in_namespace() {
+    // The byte string of an embedded item takes part in the namespace,
+    // the embedded item itself does not.
+    let e = Embedded::new("abcdef".to_string());
+    let x = (&e, "xyzxyz", "xyzxyz", &e);
+    let b = minicbor::to_vec(StringRefs::new(x)).unwrap();
+    assert_eq!(r#"256([24(h'66 61 62 63 64 65 66'), "xyzxyz", 25(1), 24(25(0))])"#, minicbor::display(&b).to_string());
+    let y: StringRefs<(Embedded<String>, &str, &str, Embedded<String>)> = minicbor::decode(&b).unwrap();
+    assert_eq!((e.clone(), "xyzxyz", "xyzxyz", e), y.into_value())
+}
+
+#[test]
+fn string_ref_errors() {
+    // 256([25(0)])
+    let e = minicbor::decode::<StringRefs<Vec<String>>>(&[0xd9, 0x01, 0x00, 0x81, 0xd8, 0x19, 0x00]).unwrap_err();
+    assert_eq!("[0]: decode error at position 4: unknown string reference", e.to_string());
+
+    // 256([h'616263', 25(0)])
+    let b = [0xd9, 0x01, 0x00, 0x82, 0x43, 0x61, 0x62, 0x63, 0xd8, 0x19, 0x00];
+    let e = minicbor::decode::<StringRefs<(ByteVec, String)>>(&b).unwrap_err();
+    assert!(e.is_type_mismatch());
+    assert_eq!(Some(8), e.position());
+    assert!(minicbor::decode::<StringRefs<(ByteVec, ByteVec)>>(&b).is_ok());
+
+    // Without namespace references are not resolved.
+    assert!(minicbor::decode::<(ByteVec, ByteVec)>(&b[3 ..]).is_err());
+
+    let e = minicbor::decode::<StringRefs<u8>>(&[0x01]).unwrap_err();
+    assert!(e.is_type_mismatch());
+}
+
+#[test]
+fn probe_in_namespace() {
+    /// Decodes an array of strings, probing ahead before each one.
+    #[derive(Debug, PartialEq)]
+    struct Probed(Vec<String>);
+
+    impl<'b, C> Decode<'b, C> for Probed {
+        fn decode(d: &mut Decoder<'b>, _: &mut C) -> Result<Self, decode::Error> {
+            let mut xs = Vec::new();
+            for _ in 0 .. d.array()?.unwrap_or(0) {
+                let mut p = d.probe();
+                let x = p.str()?.to_string();
+                // Strings seen by the probe are not added to the decoder's table.
+                while p.str().is_ok() {}
+                assert_eq!(x, d.str()?);
+                xs.push(x)
+            }
+            Ok(Probed(xs))
+        }
+    }
+
+    // 256(["aaa", "bbb", 25(0), 25(1)])
+    let b = hex::decode("d90100846361616163626262d81900d81901").unwrap();
+    let x: StringRefs<Probed> = minicbor::decode(&b).unwrap();
+    assert_eq!(["aaa", "bbb", "aaa", "bbb"].as_slice(), x.0.as_slice());
+
+    // 256(["aaa", "bbb", 25(2)])
+    let b = hex::decode("d90100836361616163626262d81902").unwrap();
+    let e = minicbor::decode::<StringRefs<Probed>>(&b).unwrap_err();
+    assert_eq!("decode error at position 12: unknown string reference", e.to_string())
+}
+
+#[test]
+fn rc_and_arc() {
+    let b = minicbor::to_vec((Rc::new(1u8), Arc::new("x"))).unwrap();
+    assert_eq!(b, minicbor::to_vec((1u8, "x")).unwrap());
+    let (x, y): (Rc<u8>, Arc<str>) = minicbor::decode(&b).unwrap();
+    assert_eq!((1, "x"), (*x, &*y));
+    assert_eq!(b.len(), minicbor::len((x, y)))
+}
+
+#[test]
+fn shared_values() {
+    let x = Rc::new("shared".to_string());
+    let y = Rc::new("shared".to_string());
+    let xs = vec![Shared::new(x.clone()), Shared::new(y), Shared::new(x)];
+    let b = minicbor::to_vec_with(&xs, &mut SharedValues::new()).unwrap();
+    assert_eq!(r#"[28("shared"), 28("shared"), 29(0)]"#, minicbor::display(&b).to_string());
+
+    let ys: Vec<Shared<Rc<String>>> = minicbor::decode_with(&b, &mut SharedValues::new()).unwrap();
+    assert_eq!(xs, ys);
+    assert!(Rc::ptr_eq(&ys[0], &ys[2]));
+    assert!(!Rc::ptr_eq(&ys[0], &ys[1]));
+
+    let zs: Vec<Shared<Arc<String>>> = minicbor::decode_with(&b, &mut SharedValues::new()).unwrap();
+    assert!(Arc::ptr_eq(&zs[0], &zs[2]));
+    assert_eq!(b, minicbor::to_vec_with(&zs, &mut SharedValues::new()).unwrap());
+
+    // Untagged values are not shared.
+    let b = minicbor::to_vec(["a", "a"]).unwrap();
+    let ys: Vec<Shared<Rc<String>>> = minicbor::decode_with(&b, &mut SharedValues::new()).unwrap();
+    assert!(!Rc::ptr_eq(&ys[0], &ys[1]))
+}
+
+#[test]
+fn shared_values_derive() {
+    #[derive(Debug, Encode, Decode)]
+    #[cbor(context_bound = "AsMut<minicbor::data::SharedValues>")]
+    struct Doc {
+        #[n(0)] config: Shared<Arc<Config>>,
+        #[n(1)] items: Vec<Item>
+    }
+
+    #[derive(Debug, Encode, Decode)]
+    #[cbor(context_bound = "AsMut<minicbor::data::SharedValues>")]
+    struct Item {
+        #[n(0)] id: u32,
+        #[n(1)] config: Shared<Arc<Config>>
+    }
+
+    #[derive(Debug, PartialEq, Encode, Decode)]
+    struct Config {
+        #[n(0)] name: String,
+        #[n(1)] values: Vec<u32>
+    }
+
+    let config = Arc::new(Config { name: "default".into(), values: vec![1, 2, 3] });
+    let doc = Doc {
+        config: Shared::new(config.clone()),
+        items: (0 .. 3).map(|id| Item { id, config: Shared::new(config.clone()) }).collect()
+    };
+    let b = minicbor::to_vec_with(&doc, &mut SharedValues::new()).unwrap();
+    assert_eq!(r#"[28(["default", [1, 2, 3]]), [[0, 29(0)], [1, 29(0)], [2, 29(0)]]]"#, minicbor::display(&b).to_string());
+
+    let d: Doc = minicbor::decode_with(&b, &mut SharedValues::new()).unwrap();
+    assert_eq!(*config, **d.config);
+    assert!(d.items.iter().all(|i| Arc::ptr_eq(&d.config, &i.config)));
+
+    // Shared values within string reference namespaces.
+    let b = minicbor::to_vec_with(StringRefs::new(&doc), &mut SharedValues::new()).unwrap();
+    let d: StringRefs<Doc> = minicbor::decode_with(&b, &mut SharedValues::new()).unwrap();
+    assert!(d.items.iter().all(|i| Arc::ptr_eq(&d.config, &i.config)))
+}
+
+#[test]
+fn shared_value_errors() {
+    type T = Shared<Rc<String>>;
+
+    // 29(0)
+    let e = minicbor::decode_with::<_, T>(&[0xd8, 0x1d, 0x00], &mut SharedValues::new()).unwrap_err();
+    assert_eq!("decode error at position 2: unknown shared value reference", e.to_string());
+
+    // 28(29(0))
+    let b = [0xd8, 0x1c, 0xd8, 0x1d, 0x00];
+    let e = minicbor::decode_with::<_, Shared<Rc<T>>>(&b, &mut SharedValues::new()).unwrap_err();
+    assert_eq!("decode error at position 4: cyclic shared value reference", e.to_string());
+
+    // [28("a"), 29(0)]
+    let b = [0x82, 0xd8, 0x1c, 0x61, 0x61, 0xd8, 0x1d, 0x00];
+    let e = minicbor::decode_with::<_, (T, Shared<Arc<String>>)>(&b, &mut SharedValues::new()).unwrap_err();
+    assert_eq!("decode error at position 7: unexpected type of shared value", e.to_string());
+    assert!(minicbor::decode_with::<_, (T, T)>(&b, &mut SharedValues::new()).is_ok())
+}
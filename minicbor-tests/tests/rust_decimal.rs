@@ -0,0 +1,63 @@
+#![cfg(feature = "rust_decimal")]
+
+use minicbor::data::Decimal;
+
+quickcheck::quickcheck! {
+    fn identity(m: i64, scale: u8) -> bool {
+        let x = rust_decimal::Decimal::new(m, u32::from(scale % 29));
+        let b = minicbor::to_vec(x).unwrap();
+        assert_eq!(b.len(), minicbor::len(x));
+        let y: rust_decimal::Decimal = minicbor::decode(&b).unwrap();
+        x == y && x.scale() == y.scale()
+    }
+}
+
+#[test]
+fn encoding() {
+    // 273.15
+    let x = rust_decimal::Decimal::new(27315, 2);
+    let b = hex::decode("c48221196ab3").unwrap();
+    assert_eq!(b, minicbor::to_vec(x).unwrap());
+    assert_eq!(x, minicbor::decode::<rust_decimal::Decimal>(&b).unwrap());
+
+    // Max. value has a bignum mantissa.
+    let x = rust_decimal::Decimal::MAX;
+    let b = hex::decode("c48200c24cffffffffffffffffffffffff").unwrap();
+    assert_eq!(b, minicbor::to_vec(x).unwrap());
+    assert_eq!(x, minicbor::decode::<rust_decimal::Decimal>(&b).unwrap())
+}
+
+#[test]
+fn conversions() {
+    // Positive exponents are applied to the mantissa.
+    let x = rust_decimal::Decimal::try_from(Decimal::new(3, -15_i128)).unwrap();
+    assert_eq!(rust_decimal::Decimal::new(-15000, 0), x);
+
+    // Trailing zeros are removed if the scale would be too large.
+    let x = rust_decimal::Decimal::try_from(Decimal::new(-30, 500_i128)).unwrap();
+    assert_eq!(rust_decimal::Decimal::new(5, 28), x);
+
+    // Values out of range are rejected.
+    assert!(rust_decimal::Decimal::try_from(Decimal::new(29, 1_i128)).is_err());
+    assert!(rust_decimal::Decimal::try_from(Decimal::new(-29, 1_i128)).is_err());
+    assert!(rust_decimal::Decimal::try_from(Decimal::new(0, 2_i128.pow(96))).is_err());
+
+    assert!(rust_decimal::Decimal::try_from(Decimal::new(i64::MIN, 1_i128)).is_err());
+
+    // Zero is accepted with any exponent.
+    let x = rust_decimal::Decimal::try_from(Decimal::new(50, 0_i128)).unwrap();
+    assert!(x.is_zero() && x.scale() == 0);
+    let x = rust_decimal::Decimal::try_from(Decimal::new(-100, 0_i128)).unwrap();
+    assert!(x.is_zero() && x.scale() == 28);
+    let x = rust_decimal::Decimal::try_from(Decimal::new(-2, 0_i128)).unwrap();
+    assert!(x.is_zero() && x.scale() == 2);
+
+    // 1e-9223372036854775808
+    let b = hex::decode("c4823b7fffffffffffffff01").unwrap();
+    assert!(minicbor::decode::<rust_decimal::Decimal>(&b).unwrap_err().is_message());
+
+    let b = minicbor::to_vec(Decimal::new(i64::MAX, 1_i128)).unwrap();
+    let e = minicbor::decode::<rust_decimal::Decimal>(&b).unwrap_err();
+    assert!(e.is_message());
+    assert_eq!(Some(0), e.position())
+}
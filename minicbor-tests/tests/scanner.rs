@@ -0,0 +1,131 @@
+#![cfg(feature = "alloc")]
+
+use minicbor::Decoder;
+use minicbor::data::Value;
+use minicbor::decode::info::{Progress, Scanner};
+
+/// Feed `input` byte by byte and check the scanner only completes at the end.
+fn scan_incrementally<const N: usize>(input: &[u8]) {
+    let mut s = Scanner::<N>::new();
+    let mut end = 0;
+    while end < input.len() {
+        match s.scan(&input[.. end]).unwrap() {
+            Progress::Incomplete(n) => {
+                assert!(n > 0 && end + n <= input.len());
+                end += 1
+            }
+            Progress::Complete(n) => panic!("complete after {n} of {} bytes", input.len())
+        }
+    }
+    assert_eq!(Progress::Complete(input.len()), s.scan(input).unwrap());
+    assert_eq!(Progress::Complete(input.len()), s.scan(input).unwrap())
+}
+
+quickcheck::quickcheck! {
+    fn scan_arbitrary_values(v: Vec<(String, Vec<u8>, Vec<f64>)>) -> bool {
+        let v = Value::Array(v.into_iter()
+            .map(|(s, b, f)| Value::Map(vec![
+                (s.into(), Value::Bytes(b)),
+                (Value::Null, Value::Array(f.into_iter().map(Value::from).collect()))
+            ]))
+            .collect());
+        let b = minicbor::to_vec(&v).unwrap();
+        scan_incrementally::<0>(&b);
+        true
+    }
+
+    fn scan_sequence(v: Vec<Vec<u16>>) -> bool {
+        let mut b = Vec::new();
+        let mut lens = Vec::new();
+        for x in &v {
+            let n = b.len();
+            minicbor::encode(x, &mut b).unwrap();
+            lens.push(b.len() - n)
+        }
+        let mut s: Scanner = Scanner::new();
+        let mut offset = 0;
+        for n in lens {
+            s.reset();
+            if s.scan(&b[offset ..]).unwrap() != Progress::Complete(n) {
+                return false
+            }
+            offset += n
+        }
+        offset == b.len()
+    }
+}
+
+#[test]
+fn indefinite_items() {
+    for s in [
+        "5f42010243030405ff", "7f657374726561646d696e67ff", "9f018202039f0405ffff",
+        "9fff", "5fff", "bfff", "bf61610161629f0203ffff", "83019f0203ff820405",
+        "d8189f5f4100ff7f60ffff", "9f9f9f9fffffffff", "a1bfff9f9fffff"
+    ] {
+        let b = hex::decode(s).unwrap();
+        let mut d = Decoder::new(&b);
+        d.skip().unwrap();
+        assert_eq!(b.len(), d.position());
+        scan_incrementally::<4>(&b);
+    }
+}
+
+#[test]
+fn missing_bytes() {
+    let mut s: Scanner = Scanner::new();
+    // "IETF" with the last three bytes missing.
+    assert_eq!(Progress::Incomplete(3), s.scan(&hex::decode("6449").unwrap()).unwrap());
+    let mut s: Scanner = Scanner::new();
+    // u64 with a partial argument.
+    assert_eq!(Progress::Incomplete(7), s.scan(&hex::decode("1b01").unwrap()).unwrap());
+    let mut s: Scanner = Scanner::new();
+    // 24(h'') with the tagged item missing.
+    assert_eq!(Progress::Incomplete(1), s.scan(&hex::decode("d818").unwrap()).unwrap());
+    assert_eq!(Progress::Incomplete(2), s.scan(&hex::decode("d81842").unwrap()).unwrap());
+    assert_eq!(Progress::Complete(5), s.scan(&hex::decode("d818420102ff").unwrap()).unwrap())
+}
+
+#[test]
+fn invalid_items() {
+    let err = |s: &str| Scanner::<2>::new().scan(&hex::decode(s).unwrap()).unwrap_err();
+
+    let e = err("ff");
+    assert!(e.is_type_mismatch());
+    assert_eq!(Some(0), e.position());
+
+    let e = err("82ff");
+    assert!(e.is_type_mismatch());
+    assert_eq!(Some(1), e.position());
+
+    let e = err("1c");
+    assert_eq!(Some(0), e.position());
+
+    // Chunks of indefinite strings must be definite strings of the same type.
+    let e = err("5f6161ff");
+    assert!(e.is_type_mismatch());
+    assert_eq!(Some(1), e.position());
+    let e = err("7f7fffff");
+    assert!(e.is_type_mismatch());
+    assert_eq!(Some(1), e.position());
+
+    let e = err("9f9f9fffffff");
+    assert!(e.is_limit_exceeded());
+    assert_eq!(Some(2), e.position());
+
+    let e = err("9bffffffffffffffff9bffffffffffffffff");
+    assert!(e.to_string().contains("overflows"));
+    assert_eq!(Some(9), e.position())
+}
+
+#[test]
+fn break_after_map_key() {
+    // Indefinite maps must not end between a key and its value.
+    for (s, p) in [
+        ("bf00ff", 2), ("bf0000bf00ffff", 5), ("bf9fffff", 3), ("bfd81800ff", 4), ("a1bf00ff", 3)
+    ] {
+        let b = hex::decode(s).unwrap();
+        let e = Scanner::<4>::new().scan(&b).unwrap_err();
+        assert!(e.is_type_mismatch());
+        assert_eq!(Some(p), e.position(), "{s}")
+    }
+}
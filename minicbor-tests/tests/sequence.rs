@@ -0,0 +1,78 @@
+#![cfg(feature = "std")]
+
+use minicbor::{Decode, Decoder};
+
+quickcheck::quickcheck! {
+    fn roundtrip(items: Vec<(u32, String)>) -> bool {
+        let mut b = Vec::new();
+        minicbor::encode_sequence(&items, &mut b).unwrap();
+        let mut d = Decoder::new(&b);
+        let v = d.sequence::<(u32, String)>().collect::<Result<Vec<_>, _>>().unwrap();
+        v == items && d.position() == b.len()
+    }
+}
+
+/// Counts the number of decoded values.
+#[derive(Debug, Decode)]
+#[cbor(context_bound = "Counter")]
+struct Item(#[n(0)] #[cbor(decode_with = "count")] u8);
+
+trait Counter {
+    fn inc(&mut self);
+}
+
+impl Counter for usize {
+    fn inc(&mut self) {
+        *self += 1
+    }
+}
+
+fn count<Ctx: Counter>(d: &mut Decoder<'_>, ctx: &mut Ctx) -> Result<u8, minicbor::decode::Error> {
+    ctx.inc();
+    d.u8()
+}
+
+#[test]
+fn with_context() {
+    let b = hex::decode("810181028103").unwrap(); // [1], [2], [3]
+    let mut n = 0;
+    let v = Decoder::new(&b)
+        .sequence_with::<_, Item>(&mut n)
+        .map(|x| x.map(|x| x.0))
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(vec![1, 2, 3], v);
+    assert_eq!(3, n)
+}
+
+#[test]
+fn errors() {
+    assert_eq!(0, Decoder::new(&[]).sequence::<u8>().count());
+
+    // 1, 2, [3, ...
+    let b = hex::decode("010282").unwrap();
+    let mut d = Decoder::new(&b);
+    let mut s = d.sequence::<minicbor::data::Value>();
+    assert!(s.next().unwrap().is_ok());
+    assert!(s.next().unwrap().is_ok());
+    assert!(s.next().unwrap().unwrap_err().is_end_of_input());
+    assert!(s.next().is_none());
+
+    // Iteration ends after the first error.
+    let b = hex::decode("01616102").unwrap(); // 1, "a", 2
+    let v = Decoder::new(&b).sequence::<u8>().collect::<Vec<_>>();
+    assert_eq!(2, v.len());
+    assert!(v[1].as_ref().unwrap_err().is_type_mismatch())
+}
+
+#[test]
+fn trailing_data() {
+    let b = hex::decode("8201020304").unwrap(); // [1, 2], 3, 4
+    assert_eq!(vec![1, 2], minicbor::decode::<Vec<u8>>(&b).unwrap());
+    let e = minicbor::decode_exact::<Vec<u8>>(&b).unwrap_err();
+    assert!(e.is_trailing_data());
+    assert_eq!(Some(3), e.position());
+    assert_eq!("trailing data at position 3", e.to_string());
+    assert_eq!(vec![1, 2], minicbor::decode_exact_with::<_, Vec<u8>>(&b[.. 3], &mut ()).unwrap());
+    assert!(minicbor::decode_exact::<Vec<u8>>(&b[.. 2]).unwrap_err().is_end_of_input())
+}
@@ -0,0 +1,122 @@
+#![cfg(feature = "alloc")]
+
+use minicbor::Decoder;
+use minicbor::data::Value;
+use minicbor::decode::Error;
+
+fn decode_strict(s: &str) -> Result<Value, Error> {
+    let b = hex::decode(s).unwrap();
+    let mut d = Decoder::new(&b);
+    d.set_strict(true);
+    let v = d.decode()?;
+    assert_eq!(b.len(), d.position());
+    Ok(v)
+}
+
+quickcheck::quickcheck! {
+    fn deterministic_encoding_is_accepted(v: Vec<(String, Vec<f64>)>) -> bool {
+        let m = Value::Map(v.into_iter()
+            .map(|(k, xs)| (k.into(), Value::Array(xs.into_iter().map(Value::from).collect())))
+            .collect());
+        let Ok(b) = minicbor::to_vec_deterministic(&m) else {
+            return true // duplicate keys
+        };
+        let mut d = Decoder::new(&b);
+        d.set_strict(true);
+        let w: Value = d.decode().unwrap();
+        b == minicbor::to_vec_deterministic(&w).unwrap()
+    }
+}
+
+#[test]
+fn accepted() {
+    for s in [
+        "00", "17", "1818", "18ff", "190100", "1a00010000", "1b0000000100000000", "20", "3818",
+        "38ff", "390100", "40", "4101", "60", "6161", "80", "8101", "a0", "a201020304",
+        "a3616101616202626161f6", "a20a0020f4", "c11a514b67b0", "d818f6", "f4", "f5", "f6",
+        "f7", "f93c00", "f97e00", "fa47c35000", "fb3ff199999999999a", "f9fc00"
+    ] {
+        assert!(decode_strict(s).is_ok(), "{s}")
+    }
+}
+
+#[test]
+fn rejected() {
+    for (s, pred, pos) in [
+        ("1800", Error::is_non_minimal_int as fn(&Error) -> bool, 0),
+        ("1817", Error::is_non_minimal_int, 0),
+        ("190017", Error::is_non_minimal_int, 0),
+        ("1900ff", Error::is_non_minimal_int, 0),
+        ("1a0000ffff", Error::is_non_minimal_int, 0),
+        ("1b00000000ffffffff", Error::is_non_minimal_int, 0),
+        ("3800", Error::is_non_minimal_int, 0),
+        ("82011810", Error::is_non_minimal_int, 2),
+        ("5801ff", Error::is_non_minimal_int, 0),
+        ("780161", Error::is_non_minimal_int, 0),
+        ("9800", Error::is_non_minimal_int, 0),
+        ("b800", Error::is_non_minimal_int, 0),
+        ("d80100", Error::is_non_minimal_int, 0),
+        ("f814", Error::is_not_well_formed, 0),
+        ("f801", Error::is_not_well_formed, 0),
+        ("fa3f800000", Error::is_non_shortest_float, 0),
+        ("fa7fc00000", Error::is_non_shortest_float, 0),
+        ("fb3ff8000000000000", Error::is_non_shortest_float, 0),
+        ("fb3fb99999a0000000", Error::is_non_shortest_float, 0),
+        ("fb7ff8000000000000", Error::is_non_shortest_float, 0),
+        ("8201fb3ff0000000000000", Error::is_non_shortest_float, 2),
+        ("5fff", Error::is_indefinite_length, 0),
+        ("7fff", Error::is_indefinite_length, 0),
+        ("9fff", Error::is_indefinite_length, 0),
+        ("bfff", Error::is_indefinite_length, 0),
+        ("a1019f01ff", Error::is_indefinite_length, 2),
+        ("a202000100", Error::is_unsorted_map_keys, 3),
+        ("a2616201616101", Error::is_unsorted_map_keys, 4),
+        ("a2f4002000", Error::is_unsorted_map_keys, 3),
+        ("a262616100616200", Error::is_unsorted_map_keys, 5),
+        ("a3010002000200", Error::is_duplicate_map_key, 5),
+        ("a101a202000201", Error::is_duplicate_map_key, 5),
+        ("a1a202000100f6", Error::is_unsorted_map_keys, 4),
+        ("82a0a202000100", Error::is_unsorted_map_keys, 5),
+        ("a201a1820102a2020001000200", Error::is_unsorted_map_keys, 9),
+        ("f0", Error::is_unknown_simple, 0),
+        ("e0", Error::is_unknown_simple, 0),
+        ("f820", Error::is_unknown_simple, 0),
+        ("82f6f8ff", Error::is_unknown_simple, 2)
+    ] {
+        let e = decode_strict(s).unwrap_err();
+        assert!(pred(&e), "{s}: {e}");
+        assert_eq!(Some(pos), e.position(), "{s}: {e}")
+    }
+}
+
+#[test]
+fn skip_is_strict() {
+    for s in ["82181001", "a1029f01ff", "d80101", "81fa3f800000", "f801"] {
+        let b = hex::decode(s).unwrap();
+        assert!(Decoder::new(&b).skip().is_ok());
+        let mut d = Decoder::new(&b);
+        d.set_strict(true);
+        assert!(d.skip().is_err(), "{s}")
+    }
+}
+
+#[test]
+fn deeply_nested_maps() {
+    // {0: {0: ... {0: 0} ... }}
+    let n = 100_000;
+    let mut b = [0xa1, 0x00].repeat(n);
+    b.push(0x00);
+    let mut d = Decoder::new(&b);
+    d.set_strict(true);
+    assert!(d.skip().is_ok());
+    assert_eq!(b.len(), d.position());
+
+    b[2 * n - 1] = 0x01;
+    b.extend_from_slice(&[0x00, 0x00]);
+    b[2 * n - 2] = 0xa2;
+    let mut d = Decoder::new(&b);
+    d.set_strict(true);
+    let e = d.skip().unwrap_err();
+    assert!(e.is_unsorted_map_keys(), "{e}");
+    assert_eq!(Some(2 * n + 1), e.position())
+}
@@ -0,0 +1,51 @@
+#![cfg(feature = "time")]
+
+use time::{Date, Month, OffsetDateTime, UtcOffset};
+
+quickcheck::quickcheck! {
+    fn offset_date_time(s: i64, n: u32, o: i16) -> bool {
+        let s = s.rem_euclid(253_402_300_800 - 2 * 86_400) + 86_400;
+        let t = OffsetDateTime::from_unix_timestamp(s).unwrap().replace_nanosecond(n % 1_000_000_000).unwrap();
+        let x = t.to_offset(UtcOffset::from_whole_seconds(i32::from(o % 1440) * 60).unwrap());
+        let b = minicbor::to_vec(x).unwrap();
+        assert_eq!(b.len(), minicbor::len(x));
+        let y = minicbor::decode::<OffsetDateTime>(&b).unwrap();
+        x == y && x.offset() == y.offset()
+    }
+
+    fn date(d: i32) -> bool {
+        let x = Date::from_julian_day(d.rem_euclid(3_652_059) + 1_721_060).unwrap();
+        let b = minicbor::to_vec(x).unwrap();
+        assert_eq!(b.len(), minicbor::len(x));
+        x == minicbor::decode::<Date>(&b).unwrap()
+    }
+}
+
+#[test]
+fn encoding() {
+    let x = OffsetDateTime::from_unix_timestamp(1363896240).unwrap();
+    let b = hex::decode("c074323031332d30332d32315432303a30343a30305a").unwrap();
+    assert_eq!(b, minicbor::to_vec(x).unwrap());
+    assert_eq!(x, minicbor::decode::<OffsetDateTime>(&b).unwrap());
+
+    // Tag 1
+    let b = hex::decode("c1fb41d452d9ec200000").unwrap();
+    let y = x.replace_millisecond(500).unwrap();
+    assert_eq!(y, minicbor::decode::<OffsetDateTime>(&b).unwrap());
+
+    let x = Date::from_calendar_date(1940, Month::October, 9).unwrap();
+    let b = hex::decode("d903ec6a313934302d31302d3039").unwrap();
+    assert_eq!(b, minicbor::to_vec(x).unwrap());
+    assert_eq!(x, minicbor::decode::<Date>(&b).unwrap());
+    assert_eq!(x, minicbor::decode::<Date>(&hex::decode("d8643929b3").unwrap()).unwrap())
+}
+
+#[test]
+fn unsupported() {
+    let o = UtcOffset::from_whole_seconds(30).unwrap();
+    let x = OffsetDateTime::from_unix_timestamp(0).unwrap().to_offset(o);
+    assert!(minicbor::to_vec(x).is_err());
+
+    let x = Date::from_calendar_date(-1, Month::January, 1).unwrap();
+    assert!(minicbor::to_vec(x).is_err())
+}
@@ -0,0 +1,127 @@
+#![cfg(feature = "alloc")]
+
+use minicbor::Decoder;
+use minicbor::data::{Tag, Token, Value};
+use minicbor::decode::{SpannedToken, Tokenizer};
+
+fn depths(b: &[u8]) -> Vec<(usize, Token<'_>)> {
+    Tokenizer::new(b)
+        .spanned()
+        .map(|t| t.map(|t| (t.depth, t.token)))
+        .collect::<Result<_, _>>()
+        .unwrap()
+}
+
+quickcheck::quickcheck! {
+    fn spans_cover_input(v: Vec<(String, Vec<u8>, Option<Vec<i64>>)>) -> bool {
+        let v = Value::Array(v.into_iter()
+            .map(|(s, b, i)| Value::Map(vec![
+                (s.into(), Value::Bytes(b)),
+                (Value::Null, i.map(|i| Value::Array(i.into_iter().map(Value::from).collect())).unwrap_or(Value::Null))
+            ]))
+            .collect());
+        let b = minicbor::to_vec(&v).unwrap();
+        let mut offset = 0;
+        for t in Tokenizer::new(&b).spanned() {
+            let SpannedToken { span, depth, token } = t.unwrap();
+            if span.start != offset || depth > 3 {
+                return false
+            }
+            // Every span decodes to the same token on its own.
+            if Decoder::new(&b[span.clone()]).decode::<Token>().unwrap() != token {
+                return false
+            }
+            offset = span.end
+        }
+        offset == b.len()
+    }
+
+    fn annotated_hex_is_input(v: Vec<(String, Vec<u8>, Option<Vec<i64>>)>) -> bool {
+        let b = minicbor::to_vec(&v).unwrap();
+        let hex = Tokenizer::new(&b)
+            .annotated()
+            .to_string()
+            .lines()
+            .flat_map(|l| l.split('#').next().unwrap().split_whitespace().map(String::from).collect::<Vec<_>>())
+            .collect::<String>();
+        hex == hex::encode(&b)
+    }
+}
+
+#[test]
+fn depth() {
+    // [1, [2], {3: []}, 4], 5
+    let b = hex::decode("84018102a103800405").unwrap();
+    assert_eq!(depths(&b), [
+        (0, Token::Array(4)),
+        (1, Token::U8(1)),
+        (1, Token::Array(1)),
+        (2, Token::U8(2)),
+        (1, Token::Map(1)),
+        (2, Token::U8(3)),
+        (2, Token::Array(0)),
+        (1, Token::U8(4)),
+        (0, Token::U8(5))
+    ]);
+
+    // [_ "a", (_ h'01'), 24(true)], 1(2(null))
+    let b = hex::decode("9f61615f4101ffd818f5ffc1c2f6").unwrap();
+    assert_eq!(depths(&b), [
+        (0, Token::BeginArray),
+        (1, Token::String("a")),
+        (1, Token::BeginBytes),
+        (2, Token::Bytes(&[1])),
+        (1, Token::Break),
+        (1, Token::Tag(Tag::new(24))),
+        (2, Token::Bool(true)),
+        (0, Token::Break),
+        (0, Token::Tag(Tag::new(1))),
+        (1, Token::Tag(Tag::new(2))),
+        (2, Token::Null)
+    ]);
+
+    // A break without a matching beginning does not change the depth.
+    let b = hex::decode("81ff00").unwrap();
+    assert_eq!(depths(&b), [(0, Token::Array(1)), (1, Token::Break), (1, Token::U8(0))])
+}
+
+#[test]
+fn spans() {
+    // 1000, "IETF", 1.5
+    let b = hex::decode("1903e86449455446f93e00").unwrap();
+    let spans = Tokenizer::new(&b)
+        .spanned()
+        .map(|t| t.map(|t| t.span))
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(spans, [0 .. 3, 3 .. 8, 8 .. 11]);
+
+    // Iteration ends after an error.
+    let b = hex::decode("011c02").unwrap();
+    let mut t = Tokenizer::new(&b).spanned();
+    assert_eq!(0 .. 1, t.next().unwrap().unwrap().span);
+    assert_eq!(Some(1), t.next().unwrap().unwrap_err().position());
+    assert!(t.next().is_none())
+}
+
+#[test]
+fn annotated() {
+    // {"a": h'0102'}, 24(null)
+    let b = hex::decode("a16161420102d818f6").unwrap();
+    let expected = "\
+a1          # M[1]
+   61       #    text(1)
+      61    #       \"a\"
+   42       #    bytes(2)
+      01 02
+d8 18       # T(24)
+   f6       #    null";
+    assert_eq!(expected, Tokenizer::new(&b).annotated().to_string());
+
+    // Errors end the output.
+    let b = hex::decode("011c02").unwrap();
+    assert_eq! {
+        "01 # 1\n!!! decoding error: unexpected type 0x1c at position 1: unknown cbor type",
+        Tokenizer::new(&b).annotated().to_string()
+    }
+}
@@ -0,0 +1,142 @@
+#![cfg(feature = "alloc")]
+
+use minicbor::data::{Endianness, Tag, TypedArray, TypedArrayElement};
+
+fn identity<T>(xs: Vec<T>) -> bool
+where
+    T: TypedArrayElement + PartialEq + std::fmt::Debug
+{
+    let a = TypedArray::new(&xs);
+    let b = minicbor::to_vec(a).unwrap();
+    assert_eq!(b.len(), minicbor::len(a));
+    let y: TypedArray<T> = minicbor::decode(&b).unwrap();
+    assert_eq!(a, y);
+    xs == y.to_vec()
+}
+
+quickcheck::quickcheck! {
+    fn u8s(xs: Vec<u8>) -> bool { identity(xs) }
+    fn u16s(xs: Vec<u16>) -> bool { identity(xs) }
+    fn u32s(xs: Vec<u32>) -> bool { identity(xs) }
+    fn u64s(xs: Vec<u64>) -> bool { identity(xs) }
+    fn i8s(xs: Vec<i8>) -> bool { identity(xs) }
+    fn i16s(xs: Vec<i16>) -> bool { identity(xs) }
+    fn i32s(xs: Vec<i32>) -> bool { identity(xs) }
+    fn i64s(xs: Vec<i64>) -> bool { identity(xs) }
+
+    fn f32s(xs: Vec<u32>) -> bool {
+        identity(xs.into_iter().map(f32::from_bits).filter(|x| !x.is_nan()).collect())
+    }
+
+    fn f64s(xs: Vec<u64>) -> bool {
+        identity(xs.into_iter().map(f64::from_bits).filter(|x| !x.is_nan()).collect())
+    }
+
+    fn f16s(xs: Vec<u16>) -> bool {
+        identity(xs.into_iter().map(half::f16::from_bits).filter(|x| !x.is_nan()).collect())
+    }
+
+    fn byte_order(xs: Vec<u32>) -> bool {
+        let be = xs.iter().flat_map(|x| x.to_be_bytes()).collect::<Vec<_>>();
+        let le = xs.iter().flat_map(|x| x.to_le_bytes()).collect::<Vec<_>>();
+        let a = TypedArray::<u32>::from_bytes(&be, Endianness::Big).unwrap();
+        let b = TypedArray::<u32>::from_bytes(&le, Endianness::Little).unwrap();
+        a == b && xs == a.to_vec() && xs == b.to_vec() && a.len() == xs.len()
+    }
+}
+
+#[test]
+fn tags() {
+    for (t, e) in [(0x41, Endianness::Big), (0x45, Endianness::Little)] {
+        assert_eq!(Some(e), u16::endianness(Tag::new(t)));
+        assert_eq!(Tag::new(t), u16::tag(e).tag())
+    }
+    assert_eq!(None, u16::endianness(Tag::new(0x49)));
+    assert_eq!(Tag::new(0x52), f64::tag(Endianness::Big).tag());
+    assert_eq!(Tag::new(0x54), half::f16::tag(Endianness::Little).tag());
+    assert_eq!(Tag::new(0x48), i8::tag(Endianness::Big).tag());
+
+    // Uint8Clamped
+    let b = [0xd8, 0x44, 0x42, 0x01, 0xff];
+    let a: TypedArray<u8> = minicbor::decode(&b).unwrap();
+    assert!(a.is_clamped());
+    assert_eq!(vec![1, 0xff], a.to_vec());
+    assert_eq!(b.len(), minicbor::len(a));
+    assert_eq!(b.as_slice(), minicbor::to_vec(a).unwrap());
+    assert_eq!([0xd8, 0x40, 0x42, 0x01, 0xff].as_slice(), minicbor::to_vec(a.with_clamped(false)).unwrap());
+    assert!(TypedArray::new(&[1u8]).with_clamped(true).is_clamped());
+    assert!(!minicbor::decode::<TypedArray<u8>>(&[0xd8, 0x40, 0x40]).unwrap().is_clamped())
+}
+
+#[test]
+fn encoding() {
+    // Little-endian `i32`s
+    let b = hex::decode("d84e48ffffffff02000000").unwrap();
+    let a: TypedArray<i32> = minicbor::decode(&b).unwrap();
+    assert_eq!(Endianness::Little, a.endianness());
+    assert_eq!(2, a.len());
+    assert_eq!(Some(-1), a.get(0));
+    assert_eq!(Some(2), a.get(1));
+    assert_eq!(None, a.get(2));
+    assert_eq!(b, minicbor::to_vec(a).unwrap());
+
+    // Big-endian `f64`s
+    let b = hex::decode("d85250bff8000000000000400c000000000000").unwrap();
+    let a: TypedArray<f64> = minicbor::decode(&b).unwrap();
+    assert_eq!(vec![-1.5, 3.5], a.to_vec());
+    assert_eq!(b, minicbor::to_vec(a).unwrap());
+
+    // Empty
+    let a: TypedArray<u64> = minicbor::decode(&[0xd8, 0x43, 0x40]).unwrap();
+    assert!(a.is_empty());
+    assert_eq!(None, a.get(0))
+}
+
+#[test]
+fn zero_copy() {
+    let xs = [1u32, 2, 3];
+    let a = TypedArray::new(&xs);
+    assert_eq!(Some(xs.as_slice()), a.as_slice());
+
+    let a: TypedArray<u8> = minicbor::decode(&[0xd8, 0x40, 0x42, 0x01, 0xff]).unwrap();
+    assert_eq!(Some([1, 0xff].as_slice()), a.as_slice());
+
+    // Aligned input in native byte order.
+    #[repr(align(4))]
+    struct Aligned([u8; 20]);
+
+    // The elements start after 3 bytes of tag and length.
+    let b = minicbor::to_vec(TypedArray::new(&xs)).unwrap();
+    let mut buf = Aligned([0; 20]);
+    let bytes = &mut buf.0;
+    let off = 1;
+    bytes[off .. off + b.len()].copy_from_slice(&b);
+    let a: TypedArray<u32> = minicbor::decode(&bytes[off .. off + b.len()]).unwrap();
+    assert_eq!(Some(xs.as_slice()), a.as_slice());
+
+    // Misaligned input.
+    bytes[off + 1 .. off + 1 + b.len()].copy_from_slice(&b);
+    let a: TypedArray<u32> = minicbor::decode(&bytes[off + 1 .. off + 1 + b.len()]).unwrap();
+    assert_eq!(None, a.as_slice());
+    assert_eq!(xs.to_vec(), a.to_vec());
+
+    // Foreign byte order.
+    let e = if Endianness::NATIVE == Endianness::Big { Endianness::Little } else { Endianness::Big };
+    let a = TypedArray::<u32>::from_bytes(a.as_bytes(), e).unwrap();
+    assert_eq!(None, a.as_slice())
+}
+
+#[test]
+fn decode_errors() {
+    let e = minicbor::decode::<TypedArray<u16>>(&hex::decode("d84243000100").unwrap()).unwrap_err();
+    assert!(e.is_tag_mismatch());
+    assert_eq!("unexpected tag 66 at position 0: unexpected typed array tag", e.to_string());
+
+    let e = minicbor::decode::<TypedArray<u16>>(&hex::decode("d84143000100").unwrap()).unwrap_err();
+    assert_eq!("decode error at position 2: typed array length is not a multiple of the element size", e.to_string());
+
+    let e = minicbor::decode::<TypedArray<u16>>(&hex::decode("420001").unwrap()).unwrap_err();
+    assert!(e.is_type_mismatch());
+
+    assert!(TypedArray::<u64>::from_bytes(&[0; 12], Endianness::Big).is_none())
+}
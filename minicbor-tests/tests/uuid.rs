@@ -0,0 +1,64 @@
+#![cfg(feature = "uuid")]
+
+use minicbor::{Decode, Encode};
+use minicbor::bytes::ByteArray;
+use uuid::Uuid;
+
+quickcheck::quickcheck! {
+    fn identity(x: u128) -> bool {
+        let x = Uuid::from_u128(x);
+        let b = minicbor::to_vec(x).unwrap();
+        assert_eq!(b.len(), minicbor::len(x));
+        x == minicbor::decode::<Uuid>(&b).unwrap()
+    }
+}
+
+#[test]
+fn encoding() {
+    let x = Uuid::parse_str("8f5ac6b0-61a3-4a6f-9d6e-3d440b7e1c2a").unwrap();
+    let b = hex::decode("d825508f5ac6b061a34a6f9d6e3d440b7e1c2a").unwrap();
+    assert_eq!(b, minicbor::to_vec(x).unwrap());
+    assert_eq!(x, minicbor::decode::<Uuid>(&b).unwrap())
+}
+
+#[test]
+fn decode_errors() {
+    // Untagged
+    let e = minicbor::decode::<Uuid>(&hex::decode("50000102030405060708090a0b0c0d0e0f").unwrap()).unwrap_err();
+    assert!(e.is_type_mismatch());
+
+    // Wrong tag
+    let e = minicbor::decode::<Uuid>(&hex::decode("d82450000102030405060708090a0b0c0d0e0f").unwrap()).unwrap_err();
+    assert!(e.is_tag_mismatch());
+    assert_eq!("unexpected tag 36 at position 0: expected tag 37", e.to_string());
+
+    // Wrong length
+    let e = minicbor::decode::<Uuid>(&hex::decode("d8254f000102030405060708090a0b0c0d0e").unwrap()).unwrap_err();
+    assert_eq!("decode error at position 2: expected 16 bytes", e.to_string())
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Item {
+    #[n(0)]
+    #[cbor(decode_with = "minicbor::uuid::decode_lenient")]
+    id: Uuid
+}
+
+#[derive(Encode)]
+struct LegacyItem {
+    #[n(0)] id: ByteArray<16>
+}
+
+#[test]
+fn lenient() {
+    let id = Uuid::from_u128(0x8f5a_c6b0_61a3_4a6f_9d6e_3d44_0b7e_1c2a);
+
+    let b = minicbor::to_vec(LegacyItem { id: ByteArray::from(*id.as_bytes()) }).unwrap();
+    assert_eq!(Item { id }, minicbor::decode(&b).unwrap());
+
+    let b = minicbor::to_vec(Item { id }).unwrap();
+    assert_eq!(Item { id }, minicbor::decode(&b).unwrap());
+
+    let b = hex::decode("81d8255500").unwrap();
+    assert!(minicbor::decode::<Item>(&b).is_err())
+}
@@ -0,0 +1,195 @@
+#![cfg(feature = "alloc")]
+
+use minicbor::data::{Int, Tag, Value, ValueRef};
+use minicbor::{Decoder, Encoder};
+use quickcheck::{Arbitrary, Gen};
+
+#[derive(Debug, Clone)]
+struct V(Value);
+
+impl Arbitrary for V {
+    fn arbitrary(g: &mut Gen) -> Self {
+        V(gen_value(g, 3))
+    }
+}
+
+fn gen_value(g: &mut Gen, depth: usize) -> Value {
+    let n = if depth == 0 { 10 } else { 14 };
+    match u8::arbitrary(g) % n {
+        0 => Value::Null,
+        1 => Value::Undefined,
+        2 => Value::Bool(bool::arbitrary(g)),
+        3 => Value::Simple(match u8::arbitrary(g) { n @ 0x14 ..= 0x1f => n - 0x14, n => n }),
+        4 => Value::Int(Int::try_from(i128::from(i64::arbitrary(g)) * 2).unwrap()),
+        5 => Value::F16(half::f16::from_bits(u16::arbitrary(g) & 0x7bff).to_f32()),
+        6 => Value::F32(f32::arbitrary(g)),
+        7 => Value::F64(f64::arbitrary(g)),
+        8 => Value::Bytes(Vec::arbitrary(g)),
+        9 => Value::String(String::arbitrary(g)),
+        10 => {
+            let k = usize::arbitrary(g) % 4;
+            Value::Array((0 .. k).map(|_| gen_value(g, depth - 1)).collect())
+        }
+        11 => {
+            let k = usize::arbitrary(g) % 4;
+            Value::Map((0 .. k).map(|_| (gen_value(g, depth - 1), gen_value(g, depth - 1))).collect())
+        }
+        12 => Value::Tag(Tag::new(u64::arbitrary(g)), Box::new(gen_value(g, depth - 1))),
+        _  => Value::Array(Vec::new())
+    }
+}
+
+fn is_nan(v: &Value) -> bool {
+    match v {
+        Value::F16(x) | Value::F32(x) => x.is_nan(),
+        Value::F64(x)  => x.is_nan(),
+        Value::Array(a) => a.iter().any(is_nan),
+        Value::Map(m)  => m.iter().any(|(k, v)| is_nan(k) || is_nan(v)),
+        Value::Tag(_, v) => is_nan(v),
+        _ => false
+    }
+}
+
+quickcheck::quickcheck! {
+    fn identity(v: V) -> quickcheck::TestResult {
+        if is_nan(&v.0) {
+            return quickcheck::TestResult::discard()
+        }
+        let len = minicbor::len(&v.0);
+        let vec = minicbor::to_vec(&v.0).unwrap();
+        assert_eq!(len, vec.len());
+        let mut d = Decoder::new(&vec);
+        let w: Value = d.decode().unwrap();
+        assert_eq!(d.position(), vec.len());
+        quickcheck::TestResult::from_bool(v.0 == w)
+    }
+}
+
+#[test]
+fn indefinite_items() {
+    // (_ h'0102', h'030405')
+    let v: Value = minicbor::decode(&hex::decode("5f42010243030405ff").unwrap()).unwrap();
+    assert_eq!(v, Value::Bytes(vec![1, 2, 3, 4, 5]));
+
+    // (_ "strea", "ming")
+    let v: Value = minicbor::decode(&hex::decode("7f657374726561646d696e67ff").unwrap()).unwrap();
+    assert_eq!(v, "streaming");
+
+    // [_ 1, [2, 3], [_ 4, 5]]
+    let v: Value = minicbor::decode(&hex::decode("9f018202039f0405ffff").unwrap()).unwrap();
+    assert_eq!(v[0], 1);
+    assert_eq!(v[1][1], 3);
+    assert_eq!(v[2][1], 5);
+    assert_eq!("8301820203820405", hex::encode(minicbor::to_vec(&v).unwrap()));
+
+    // {_ "a": 1, "b": [_ 2, 3]}
+    let v: Value = minicbor::decode(&hex::decode("bf61610161629f0203ffff").unwrap()).unwrap();
+    assert_eq!(v["a"], 1);
+    assert_eq!(v["b"][0], 2);
+    assert!(v["c"].is_null());
+}
+
+#[test]
+fn full_int_range() {
+    // -18446744073709551616
+    let v: Value = minicbor::decode(&hex::decode("3bffffffffffffffff").unwrap()).unwrap();
+    assert_eq!(v, Value::Int(minicbor::data::MIN_INT));
+    assert_eq!(None, v.as_i64());
+    assert_eq!("3bffffffffffffffff", hex::encode(minicbor::to_vec(&v).unwrap()));
+
+    let v: Value = minicbor::decode(&hex::decode("1bffffffffffffffff").unwrap()).unwrap();
+    assert_eq!(Some(u64::MAX), v.as_u64())
+}
+
+#[test]
+fn arbitrary_map_keys() {
+    let mut e = Encoder::new(Vec::new());
+    e.map(4).unwrap()
+        .u8(1).unwrap().str("one").unwrap()
+        .array(2).unwrap().u8(1).unwrap().u8(2).unwrap().bool(true).unwrap()
+        .null().unwrap().f32(1.5).unwrap()
+        .tag(Tag::new(32)).unwrap().str("x").unwrap().simple(16).unwrap();
+    let bytes = e.into_writer();
+    let v: Value = minicbor::decode(&bytes).unwrap();
+    assert_eq!(Some(&Value::from("one")), v.get(&1));
+    assert_eq!(Some(&Value::Bool(true)), v.get(&Value::Array(vec![1.into(), 2.into()])));
+    assert_eq!(Some(&Value::F32(1.5)), v.get(&Value::Null));
+    assert_eq!(Some(&Value::Simple(16)), v.get(&Value::Tag(Tag::new(32), Box::new("x".into()))));
+    assert_eq!(bytes, minicbor::to_vec(&v).unwrap())
+}
+
+#[test]
+fn tokenizer_items() {
+    for s in [
+        "00", "20", "3863", "f4", "f5", "f6", "f7", "f0", "f8ff", "f93c00", "fa47c35000",
+        "fb3ff199999999999a", "40", "4401020304", "60", "6449455446", "80", "a0",
+        "c11a514b67b0", "d818456449455446", "826161a161626163", "a201020304"
+    ] {
+        let b = hex::decode(s).unwrap();
+        let tokens = Decoder::new(&b).tokens().collect::<Result<Vec<_>, _>>().unwrap();
+        let v: Value = minicbor::decode(&b).unwrap();
+        assert_eq!(s, hex::encode(minicbor::to_vec(&v).unwrap()));
+        let mut e = Encoder::new(Vec::new());
+        e.tokens(&tokens).unwrap();
+        assert_eq!(e.into_writer(), minicbor::to_vec(&v).unwrap())
+    }
+}
+
+#[test]
+fn invalid_items() {
+    assert!(minicbor::decode::<Value>(&[0xff]).unwrap_err().is_type_mismatch());
+    assert!(minicbor::decode::<Value>(&[0x1c]).unwrap_err().is_type_mismatch());
+    assert!(minicbor::decode::<Value>(&[0x82, 0x01]).unwrap_err().is_end_of_input());
+}
+
+#[test]
+fn reserved_simple_values() {
+    for n in 0x14 ..= 0x1f {
+        assert!(minicbor::to_vec(Value::Simple(n)).is_err());
+        assert!(minicbor::to_vec(ValueRef::Simple(n)).is_err())
+    }
+    for (n, b) in [(0x13, &[0xf3][..]), (0x20, &[0xf8, 0x20]), (0xff, &[0xf8, 0xff])] {
+        assert_eq!(b, minicbor::to_vec(Value::Simple(n)).unwrap());
+        assert_eq!(b.len(), minicbor::len(Value::Simple(n)));
+        assert_eq!(b.len(), minicbor::len(ValueRef::Simple(n)));
+        assert_eq!(Value::Simple(n), minicbor::decode::<Value>(b).unwrap())
+    }
+}
+
+#[test]
+fn borrowed_identity() {
+    for s in [
+        "00", "3863", "f93c00", "fa47c35000", "fb3ff199999999999a", "f8ff", "4401020304",
+        "6449455446", "c11a514b67b0", "826161a161626163", "a201020304",
+        "5f42010243030405ff", "7f657374726561646d696e67ff", "9f018202039f0405ffff",
+        "9fff", "5fff", "bfff", "bf61610161629f0203ffff", "83019f0203ff820405",
+        "d8189f5f4100ff7f60ffff"
+    ] {
+        let b = hex::decode(s).unwrap();
+        let mut d = Decoder::new(&b);
+        let v: ValueRef = d.decode().unwrap();
+        assert_eq!(d.position(), b.len());
+        assert_eq!(b.len(), minicbor::len(&v));
+        assert_eq!(s, hex::encode(minicbor::to_vec(&v).unwrap()));
+        let w: Value = minicbor::decode(&b).unwrap();
+        assert_eq!(w, Value::from(&v))
+    }
+}
+
+#[test]
+fn borrowed_indefinite_items() {
+    let b = hex::decode("bf61610161629f0203ff637374727f657374726561646d696e67ffff").unwrap();
+    let v: ValueRef = minicbor::decode(&b).unwrap();
+    assert!(v.is_indefinite());
+    assert_eq!(v["a"], 1);
+    assert!(v["b"].is_indefinite());
+    assert_eq!(v["b"][1], 3);
+    assert_eq!(v["str"], "streaming");
+    assert_eq!(v["str"], ValueRef::StringIndef(vec!["strea", "ming"]));
+    assert_ne!(v["str"], "stream");
+    assert!(v["c"].is_null());
+
+    // Strings are borrowed from the input.
+    let v: ValueRef = minicbor::decode(&b[1 ..]).unwrap();
+    assert_eq!(Some(b[2 ..].as_ptr()), v.as_str().map(str::as_ptr))
+}
@@ -0,0 +1,125 @@
+//! Support for [`num_bigint::BigInt`] and [`num_bigint::BigUint`].
+//!
+//! Values are encoded as regular CBOR integers if they are within the value
+//! range of [`Int`] and as bignums (tag 2 or 3) otherwise. Decoding accepts
+//! either form (cf. [`Decoder::u128`] regarding leading zero bytes and
+//! strict mode).
+
+use num_bigint::{BigInt, BigUint, Sign};
+
+use crate::data::{IanaTag, Int, TryFromIntError, Type};
+use crate::decode::{self, Decode, Decoder};
+use crate::encode::{self, CborLen, Encode, Encoder, Write};
+
+impl<C> Encode<C> for BigUint {
+    fn encode<W: Write>(&self, e: &mut Encoder<W>, _: &mut C) -> Result<(), encode::Error<W::Error>> {
+        if let Ok(n) = u64::try_from(self) {
+            return e.u64(n)?.ok()
+        }
+        e.tag(IanaTag::PosBignum)?.bytes(&self.to_bytes_be())?.ok()
+    }
+}
+
+impl<C> CborLen<C> for BigUint {
+    fn cbor_len(&self, ctx: &mut C) -> usize {
+        if let Ok(n) = u64::try_from(self) {
+            return n.cbor_len(ctx)
+        }
+        let n = self.bits().div_ceil(8) as usize;
+        IanaTag::PosBignum.cbor_len(ctx) + n.cbor_len(ctx) + n
+    }
+}
+
+impl<'b, C> Decode<'b, C> for BigUint {
+    fn decode(d: &mut Decoder<'b>, _: &mut C) -> Result<Self, decode::Error> {
+        let p = d.position();
+        if d.datatype()? != Type::Tag {
+            return d.u64().map(BigUint::from)
+        }
+        match d.bignum_bytes(p)? {
+            (false, b) => Ok(BigUint::from_bytes_be(b)),
+            (true,  _) => Err(decode::Error::tag_mismatch(IanaTag::NegBignum.tag()).with_message("expected tag 2").at(p))
+        }
+    }
+}
+
+impl<C> Encode<C> for BigInt {
+    fn encode<W: Write>(&self, e: &mut Encoder<W>, ctx: &mut C) -> Result<(), encode::Error<W::Error>> {
+        if self.sign() != Sign::Minus {
+            return self.magnitude().encode(e, ctx)
+        }
+        let n = self.magnitude() - 1u8;
+        if let Ok(n) = u64::try_from(&n) {
+            return e.int(Int::neg(n))?.ok()
+        }
+        e.tag(IanaTag::NegBignum)?.bytes(&n.to_bytes_be())?.ok()
+    }
+}
+
+impl<C> CborLen<C> for BigInt {
+    fn cbor_len(&self, ctx: &mut C) -> usize {
+        if self.sign() != Sign::Minus {
+            return self.magnitude().cbor_len(ctx)
+        }
+        // Tags 2 and 3 as well as major types 0 and 1 have the same length.
+        (self.magnitude() - 1u8).cbor_len(ctx)
+    }
+}
+
+impl<'b, C> Decode<'b, C> for BigInt {
+    fn decode(d: &mut Decoder<'b>, _: &mut C) -> Result<Self, decode::Error> {
+        let p = d.position();
+        if d.datatype()? != Type::Tag {
+            return d.int().map(BigInt::from)
+        }
+        let (neg, b) = d.bignum_bytes(p)?;
+        let n = BigInt::from(BigUint::from_bytes_be(b));
+        Ok(if neg { -1 - n } else { n })
+    }
+}
+
+impl From<Int> for BigInt {
+    fn from(i: Int) -> Self {
+        BigInt::from(i128::from(i))
+    }
+}
+
+impl TryFrom<Int> for BigUint {
+    type Error = TryFromIntError;
+
+    fn try_from(i: Int) -> Result<Self, Self::Error> {
+        u64::try_from(i).map(BigUint::from).map_err(|_| TryFromIntError("BigUint"))
+    }
+}
+
+impl TryFrom<&BigInt> for Int {
+    type Error = TryFromIntError;
+
+    fn try_from(i: &BigInt) -> Result<Self, Self::Error> {
+        i128::try_from(i).map_err(|_| TryFromIntError("Int")).and_then(Int::try_from)
+    }
+}
+
+impl TryFrom<BigInt> for Int {
+    type Error = TryFromIntError;
+
+    fn try_from(i: BigInt) -> Result<Self, Self::Error> {
+        Int::try_from(&i)
+    }
+}
+
+impl TryFrom<&BigUint> for Int {
+    type Error = TryFromIntError;
+
+    fn try_from(i: &BigUint) -> Result<Self, Self::Error> {
+        u64::try_from(i).map(Int::from).map_err(|_| TryFromIntError("Int"))
+    }
+}
+
+impl TryFrom<BigUint> for Int {
+    type Error = TryFromIntError;
+
+    fn try_from(i: BigUint) -> Result<Self, Self::Error> {
+        Int::try_from(&i)
+    }
+}
@@ -1,3 +1,4 @@
+use minicbor::decode::{ReadDecoder, Tokenizer};
 use std::env;
 use std::fs;
 use std::process;
@@ -6,44 +7,82 @@ use std::io::{self, Read};
 const SUMMARY: &str = r#"Usage: cbor-display OPTION*
 
 With no OPTION or if OPTION is -, input is read from stdin.
+Every CBOR item of the input is displayed on a separate line.
+Options may be given in any order.
 
+  -a | --annotate   Display an annotated hex dump of every item.
   -f | --file PATH  Display contents of the file at PATH.
+  -m | --max-len N  Reject items larger than N bytes (default: unlimited).
   -h | --help       Show this help message."#;
 
 
 fn main() {
     let mut args = env::args().skip(1);
 
-    match args.next().as_deref() {
-        Some("-f") | Some("--file") =>
-            if let Some(p) = args.next() {
-                match fs::read(&p) {
-                    Ok(f)  => println!("{}", minicbor::display(&f)),
-                    Err(e) => {
-                        eprintln!("Failed to read \"{}\": {}.", p, e);
-                        process::exit(2)
+    let mut annotate = false;
+    let mut max_len = usize::MAX;
+    let mut file = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-a" | "--annotate" => annotate = true,
+            "-m" | "--max-len" =>
+                match args.next().map(|n| n.parse()) {
+                    Some(Ok(n)) => max_len = n,
+                    Some(Err(e)) => {
+                        eprintln!("Invalid max. length: {}.", e);
+                        process::exit(1)
+                    }
+                    None => {
+                        eprintln!("-m | --max-len requires a number as argument.");
+                        process::exit(1)
                     }
                 }
-            } else {
-                eprintln!("-f | --file requires a path as argument.");
+            "-f" | "--file" =>
+                if let Some(p) = args.next() {
+                    file = Some(p)
+                } else {
+                    eprintln!("-f | --file requires a path as argument.");
+                    process::exit(1)
+                }
+            "-" => file = None,
+            "-h" | "--help" => {
+                println!("{}", SUMMARY);
+                return
+            }
+            unknown => {
+                eprintln!("Unknown option: {}\n\n{}", unknown, SUMMARY);
                 process::exit(1)
             }
-        Some("-") | None => {
-            let mut v = Vec::new();
-            match io::stdin().read_to_end(&mut v) {
-                Ok(_)  => println!("{}", minicbor::display(&v)),
+        }
+    }
+
+    match file {
+        Some(p) =>
+            match fs::File::open(&p) {
+                Ok(f)  => display(f, annotate, max_len),
                 Err(e) => {
-                    eprintln!("Failed to read from stdin: {}.", e);
-                    process::exit(3)
+                    eprintln!("Failed to read \"{}\": {}.", p, e);
+                    process::exit(2)
                 }
             }
-        }
-        Some("-h") | Some("--help") => {
-            println!("{}", SUMMARY)
-        }
-        Some(unknown) => {
-            eprintln!("Unknown option: {}\n\n{}", unknown, SUMMARY);
-            process::exit(1)
+        None => display(io::stdin().lock(), annotate, max_len)
+    }
+}
+
+/// Display every CBOR item read from `r`.
+fn display<R: Read>(r: R, annotate: bool, max_len: usize) {
+    let mut d = ReadDecoder::new(r);
+    d.set_max_len(max_len);
+    loop {
+        match d.read_item() {
+            Ok(Some(item)) if annotate => println!("{}\n", Tokenizer::new(item).annotated()),
+            Ok(Some(item)) => println!("{}", minicbor::display(item)),
+            Ok(None)       => return,
+            Err(e)         => {
+                eprintln!("Failed to read CBOR item: {}.", e);
+                process::exit(3)
+            }
         }
     }
 }
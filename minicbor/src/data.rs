@@ -1,14 +1,56 @@
-//! CBOR data types, tokens and tags.
+//! CBOR data types, tokens, tags and values.
 
 #[cfg(feature = "half")]
 mod token;
 
+mod datetime;
+mod decimal;
+mod embedded;
+mod raw;
+mod typed_array;
+
+#[cfg(feature = "alloc")]
+mod multi_dim;
+
+#[cfg(feature = "alloc")]
+mod shared;
+
+#[cfg(feature = "alloc")]
+mod string_refs;
+
+#[cfg(feature = "alloc")]
+mod value;
+
 use core::fmt;
 use core::ops::{Deref, DerefMut};
 
 #[cfg(feature = "half")]
 pub use token::Token;
 
+pub use datetime::{Date, DateTime, DateTimeError, Timestamp};
+pub use decimal::{Bigfloat, Decimal};
+pub use embedded::Embedded;
+pub use raw::Raw;
+pub use typed_array::{Endianness, TypedArray, TypedArrayElement};
+
+#[cfg(feature = "alloc")]
+pub use raw::RawBuf;
+
+#[cfg(feature = "alloc")]
+pub use multi_dim::{ArrayElements, ArrayOrder, DimensionError, MultiDimArray};
+
+#[cfg(feature = "alloc")]
+pub use shared::{Shared, SharedValues};
+
+#[cfg(feature = "alloc")]
+pub use string_refs::StringRefs;
+
+#[cfg(feature = "alloc")]
+pub(crate) use string_refs::min_string_ref_len;
+
+#[cfg(feature = "alloc")]
+pub use value::{Value, ValueRef};
+
 /// CBOR data types.
 #[derive(Clone, Copy, PartialEq, PartialOrd, Eq, Ord, Debug, Hash)]
 pub enum Type {
@@ -130,6 +172,7 @@ pub enum IanaTag {
     Base64,
     Regex,
     Mime,
+    Uuid,
     // Typed arrays (RFC 8746):
     HomogenousArray,
     TypedArrayU8,
@@ -157,6 +200,14 @@ pub enum IanaTag {
     TypedArrayF128L,
     MultiDimArrayR, // row-major order
     MultiDimArrayC, // column-major order
+    // Dates (RFC 8943):
+    EpochDays,
+    FullDate,
+    // String references and value sharing:
+    StringRef,
+    SharedValue,
+    SharedRef,
+    StringRefNamespace,
 }
 
 impl IanaTag {
@@ -180,11 +231,15 @@ impl TryFrom<Tag> for IanaTag {
             0x16  => Ok(Self::ToBase64),
             0x17  => Ok(Self::ToBase16),
             0x18  => Ok(Self::Cbor),
+            0x19  => Ok(Self::StringRef),
+            0x1c  => Ok(Self::SharedValue),
+            0x1d  => Ok(Self::SharedRef),
             0x20  => Ok(Self::Uri),
             0x21  => Ok(Self::Base64Url),
             0x22  => Ok(Self::Base64),
             0x23  => Ok(Self::Regex),
             0x24  => Ok(Self::Mime),
+            0x25  => Ok(Self::Uuid),
             0x28  => Ok(Self::MultiDimArrayR),
             0x29  => Ok(Self::HomogenousArray),
             0x40  => Ok(Self::TypedArrayU8),
@@ -210,6 +265,9 @@ impl TryFrom<Tag> for IanaTag {
             0x55  => Ok(Self::TypedArrayF32L),
             0x56  => Ok(Self::TypedArrayF64L),
             0x57  => Ok(Self::TypedArrayF128L),
+            0x64  => Ok(Self::EpochDays),
+            0x100 => Ok(Self::StringRefNamespace),
+            0x3ec => Ok(Self::FullDate),
             0x410 => Ok(Self::MultiDimArrayC),
             _     => Err(UnknownTag(t))
         }
@@ -234,6 +292,7 @@ impl From<IanaTag> for Tag {
             IanaTag::Base64              => Tag::new(0x22),
             IanaTag::Regex               => Tag::new(0x23),
             IanaTag::Mime                => Tag::new(0x24),
+            IanaTag::Uuid                => Tag::new(0x25),
             IanaTag::MultiDimArrayR      => Tag::new(0x28),
             IanaTag::HomogenousArray     => Tag::new(0x29),
             IanaTag::TypedArrayU8        => Tag::new(0x40),
@@ -259,7 +318,13 @@ impl From<IanaTag> for Tag {
             IanaTag::TypedArrayF32L      => Tag::new(0x55),
             IanaTag::TypedArrayF64L      => Tag::new(0x56),
             IanaTag::TypedArrayF128L     => Tag::new(0x57),
-            IanaTag::MultiDimArrayC      => Tag::new(0x410)
+            IanaTag::MultiDimArrayC      => Tag::new(0x410),
+            IanaTag::EpochDays           => Tag::new(0x64),
+            IanaTag::FullDate            => Tag::new(0x3ec),
+            IanaTag::StringRef           => Tag::new(0x19),
+            IanaTag::SharedValue         => Tag::new(0x1c),
+            IanaTag::SharedRef           => Tag::new(0x1d),
+            IanaTag::StringRefNamespace  => Tag::new(0x100)
         }
     }
 }
@@ -571,7 +636,7 @@ impl From<Int> for i128 {
 
 /// Error when conversion of a CBOR [`Int`] to another type failed.
 #[derive(Debug)]
-pub struct TryFromIntError(&'static str);
+pub struct TryFromIntError(pub(crate) &'static str);
 
 impl fmt::Display for TryFromIntError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
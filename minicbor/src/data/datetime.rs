@@ -0,0 +1,865 @@
+use core::fmt;
+use core::str::FromStr;
+
+use crate::data::{IanaTag, Type};
+use crate::decode::{self, Decode, Decoder};
+use crate::encode::{self, CborLen, Encode, Encoder, Write};
+
+/// Seconds since the Unix epoch of 0000-01-01T00:00:00Z.
+const MIN_SECS: i64 = -62_167_219_200;
+
+/// Seconds since the Unix epoch of 9999-12-31T23:59:59Z.
+const MAX_SECS: i64 = 253_402_300_799;
+
+/// Days since the Unix epoch of 0000-01-01.
+const MIN_DAYS: i64 = -719_528;
+
+/// Days since the Unix epoch of 9999-12-31.
+const MAX_DAYS: i64 = 2_932_896;
+
+const SECS_PER_DAY: i64 = 86_400;
+
+/// A point in time as seconds since the Unix epoch (tag 1).
+///
+/// A `Timestamp` is encoded as tag 1 ([`IanaTag::Timestamp`]) followed by
+/// an integer if it has no fractional seconds, or by a floating-point
+/// number otherwise (which may lose sub-microsecond precision). Decoding
+/// also accepts tag 0 ([`IanaTag::DateTime`]), cf. [`DateTime`].
+///
+/// Note that `std::time::SystemTime` is encoded as an array of seconds and
+/// nanoseconds since the Unix epoch. Convert it to a `Timestamp` to encode
+/// it as tag 1 instead.
+///
+/// # Example
+///
+/// ```
+/// use minicbor::data::Timestamp;
+///
+/// let input = [0xc1, 0x1a, 0x51, 0x4b, 0x67, 0xb0];
+///
+/// let t: Timestamp = minicbor::decode(&input)?;
+/// assert_eq!(1363896240, t.secs());
+///
+/// assert_eq!(input.as_slice(), minicbor::to_vec(t)?);
+/// # Ok::<_, Box<dyn core::error::Error>>(())
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Timestamp {
+    secs: i64,
+    nanos: u32
+}
+
+impl Timestamp {
+    /// Create a timestamp from seconds and nanoseconds since the Unix epoch.
+    ///
+    /// Fails if `nanos` is not less than 1,000,000,000.
+    pub const fn new(secs: i64, nanos: u32) -> Result<Self, DateTimeError> {
+        if nanos >= 1_000_000_000 {
+            return Err(DateTimeError("nanoseconds out of range"))
+        }
+        Ok(Self { secs, nanos })
+    }
+
+    /// Create a timestamp from seconds since the Unix epoch.
+    pub const fn from_secs(secs: i64) -> Self {
+        Self { secs, nanos: 0 }
+    }
+
+    /// Seconds since the Unix epoch.
+    ///
+    /// For points in time before the epoch this is rounded towards negative
+    /// infinity, i.e. the nanoseconds are always added.
+    pub const fn secs(&self) -> i64 {
+        self.secs
+    }
+
+    /// Sub-second nanoseconds.
+    pub const fn nanos(&self) -> u32 {
+        self.nanos
+    }
+
+    fn as_f64(&self) -> f64 {
+        self.secs as f64 + f64::from(self.nanos) / 1e9
+    }
+
+    fn from_f64(x: f64) -> Option<Self> {
+        // NB: `i64::MAX as f64` is 2^63 and hence not included.
+        if !(x >= i64::MIN as f64 && x < i64::MAX as f64) {
+            return None
+        }
+        let mut secs = x as i64;
+        if secs as f64 > x {
+            secs -= 1
+        }
+        let nanos = ((x - secs as f64) * 1e9) as u64;
+        if nanos >= 1_000_000_000 {
+            return Some(Self { secs: secs.checked_add(1)?, nanos: 0 })
+        }
+        Some(Self { secs, nanos: nanos as u32 })
+    }
+
+    /// Decode the value following tag 1.
+    fn decode_value(d: &mut Decoder<'_>) -> Result<Self, decode::Error> {
+        let p = d.position();
+        match d.datatype()? {
+            Type::F16 | Type::F32 | Type::F64 => {
+                let x = d.f64()?;
+                Self::from_f64(x).ok_or_else(|| decode::Error::message("timestamp out of range").at(p))
+            }
+            _ => d.i64().map(Self::from_secs)
+        }
+    }
+}
+
+impl<'b, C> Decode<'b, C> for Timestamp {
+    fn decode(d: &mut Decoder<'b>, _: &mut C) -> Result<Self, decode::Error> {
+        match decode_tag(d)? {
+            Tagged::Text(p) => DateTime::decode_text(d, p).map(Timestamp::from),
+            Tagged::Epoch   => Timestamp::decode_value(d)
+        }
+    }
+}
+
+impl<C> Encode<C> for Timestamp {
+    fn encode<W: Write>(&self, e: &mut Encoder<W>, _: &mut C) -> Result<(), encode::Error<W::Error>> {
+        e.tag(IanaTag::Timestamp)?;
+        if self.nanos == 0 {
+            e.i64(self.secs)?.ok()
+        } else {
+            e.float(self.as_f64())?.ok()
+        }
+    }
+}
+
+impl<C> CborLen<C> for Timestamp {
+    fn cbor_len(&self, ctx: &mut C) -> usize {
+        let n = if self.nanos == 0 {
+            self.secs.cbor_len(ctx)
+        } else {
+            crate::float::cbor_len(&self.as_f64(), ctx)
+        };
+        IanaTag::Timestamp.cbor_len(ctx) + n
+    }
+}
+
+impl From<DateTime> for Timestamp {
+    fn from(d: DateTime) -> Self {
+        d.timestamp
+    }
+}
+
+#[cfg(feature = "std")]
+impl TryFrom<std::time::SystemTime> for Timestamp {
+    type Error = DateTimeError;
+
+    fn try_from(t: std::time::SystemTime) -> Result<Self, Self::Error> {
+        let out_of_range = || DateTimeError("timestamp out of range");
+        match t.duration_since(std::time::UNIX_EPOCH) {
+            Ok(d) => {
+                let secs = i64::try_from(d.as_secs()).map_err(|_| out_of_range())?;
+                Ok(Self { secs, nanos: d.subsec_nanos() })
+            }
+            Err(e) => {
+                let d = e.duration();
+                let secs = i64::try_from(d.as_secs()).map_err(|_| out_of_range())?;
+                if d.subsec_nanos() == 0 {
+                    return Ok(Self { secs: -secs, nanos: 0 })
+                }
+                let secs = (-secs).checked_sub(1).ok_or_else(out_of_range)?;
+                Ok(Self { secs, nanos: 1_000_000_000 - d.subsec_nanos() })
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl TryFrom<Timestamp> for std::time::SystemTime {
+    type Error = DateTimeError;
+
+    fn try_from(t: Timestamp) -> Result<Self, Self::Error> {
+        let d = std::time::Duration::new(t.secs.unsigned_abs(), 0);
+        let s = if t.secs < 0 {
+            std::time::UNIX_EPOCH.checked_sub(d)
+        } else {
+            std::time::UNIX_EPOCH.checked_add(d)
+        };
+        s.and_then(|s| s.checked_add(std::time::Duration::from_nanos(t.nanos.into())))
+            .ok_or(DateTimeError("timestamp out of range"))
+    }
+}
+
+/// A date and time with an offset from UTC (tag 0).
+///
+/// A `DateTime` is encoded as tag 0 ([`IanaTag::DateTime`]) followed by
+/// a text string in RFC 3339 format, e.g. `"2013-03-21T20:04:00Z"`. Years
+/// range from 0000 to 9999, the offset is given in minutes and leap seconds
+/// are not supported. Decoding also accepts tag 1 ([`IanaTag::Timestamp`]),
+/// which results in a UTC date and time.
+///
+/// # Example
+///
+/// ```
+/// use minicbor::data::DateTime;
+///
+/// let d: DateTime = "2013-03-21T20:04:00.5+01:00".parse()?;
+/// assert_eq!(1363892640, d.timestamp().secs());
+/// assert_eq!(60, d.offset());
+///
+/// let input = minicbor::to_vec(d)?;
+/// assert_eq!(d, minicbor::decode(&input)?);
+/// assert_eq!("2013-03-21T20:04:00.5+01:00", d.to_string());
+/// # Ok::<_, Box<dyn core::error::Error>>(())
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct DateTime {
+    timestamp: Timestamp,
+    offset: i16
+}
+
+impl DateTime {
+    /// Create a date and time from a timestamp and an offset in minutes.
+    ///
+    /// Fails if the offset exceeds ±23:59 or if the local date is not
+    /// within the years 0000 to 9999.
+    pub fn new(timestamp: Timestamp, offset: i16) -> Result<Self, DateTimeError> {
+        if offset.unsigned_abs() >= 24 * 60 {
+            return Err(DateTimeError("offset out of range"))
+        }
+        let local = timestamp.secs.checked_add(i64::from(offset) * 60);
+        if !local.is_some_and(|s| (MIN_SECS ..= MAX_SECS).contains(&s)) {
+            return Err(DateTimeError("year out of range"))
+        }
+        Ok(Self { timestamp, offset })
+    }
+
+    /// Create a UTC date and time from a timestamp.
+    pub fn utc(timestamp: Timestamp) -> Result<Self, DateTimeError> {
+        Self::new(timestamp, 0)
+    }
+
+    /// The point in time.
+    pub const fn timestamp(&self) -> Timestamp {
+        self.timestamp
+    }
+
+    /// The offset from UTC in minutes.
+    pub const fn offset(&self) -> i16 {
+        self.offset
+    }
+
+    /// Decode the text string following tag 0.
+    fn decode_text(d: &mut Decoder<'_>, p: usize) -> Result<Self, decode::Error> {
+        d.str()?.parse().map_err(|e: DateTimeError| decode::Error::message(e.0).at(p))
+    }
+}
+
+impl TryFrom<Timestamp> for DateTime {
+    type Error = DateTimeError;
+
+    fn try_from(t: Timestamp) -> Result<Self, Self::Error> {
+        Self::utc(t)
+    }
+}
+
+impl fmt::Display for DateTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let local = self.timestamp.secs + i64::from(self.offset) * 60;
+        let (y, m, d) = civil_from_days(local.div_euclid(SECS_PER_DAY));
+        let s = local.rem_euclid(SECS_PER_DAY);
+        write!(f, "{y:04}-{m:02}-{d:02}T{:02}:{:02}:{:02}", s / 3600, s / 60 % 60, s % 60)?;
+        if self.timestamp.nanos > 0 {
+            let mut n = self.timestamp.nanos;
+            let mut w = 9;
+            while n.is_multiple_of(10) {
+                n /= 10;
+                w -= 1
+            }
+            write!(f, ".{n:0w$}")?
+        }
+        if self.offset == 0 {
+            return f.write_str("Z")
+        }
+        let sign = if self.offset < 0 { '-' } else { '+' };
+        let o = self.offset.unsigned_abs();
+        write!(f, "{sign}{:02}:{:02}", o / 60, o % 60)
+    }
+}
+
+impl FromStr for DateTime {
+    type Err = DateTimeError;
+
+    /// Parse a date and time in RFC 3339 format.
+    ///
+    /// Fractional seconds beyond nanosecond precision are truncated.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut p = Parser(s.as_bytes());
+        let days = p.date()?;
+        if !matches!(p.next(), Some(b'T' | b't')) {
+            return Err(DateTimeError(INVALID_FORMAT))
+        }
+        let h = p.number(2, 23, "hour out of range")?;
+        p.expect(b':')?;
+        let m = p.number(2, 59, "minute out of range")?;
+        p.expect(b':')?;
+        let s = p.number(2, 59, "second out of range")?;
+        let mut nanos = 0;
+        if p.0.first() == Some(&b'.') {
+            p.next();
+            let mut n = 0;
+            while let Some(c) = p.0.first().filter(|c| c.is_ascii_digit()) {
+                if n < 9 {
+                    nanos = nanos * 10 + u32::from(c - b'0')
+                }
+                n += 1;
+                p.next();
+            }
+            if n == 0 {
+                return Err(DateTimeError(INVALID_FORMAT))
+            }
+            for _ in n .. 9 {
+                nanos *= 10
+            }
+        }
+        let offset = match p.next() {
+            Some(b'Z' | b'z') => 0,
+            Some(c @ (b'+' | b'-')) => {
+                let h = p.number(2, 23, "offset out of range")?;
+                p.expect(b':')?;
+                let m = p.number(2, 59, "offset out of range")?;
+                let o = (h * 60 + m) as i16;
+                if c == b'-' { -o } else { o }
+            }
+            _ => return Err(DateTimeError(INVALID_FORMAT))
+        };
+        if !p.0.is_empty() {
+            return Err(DateTimeError(INVALID_FORMAT))
+        }
+        let local = days * SECS_PER_DAY + i64::from(h * 3600 + m * 60 + s);
+        let secs  = local - i64::from(offset) * 60;
+        Self::new(Timestamp { secs, nanos }, offset)
+    }
+}
+
+impl<'b, C> Decode<'b, C> for DateTime {
+    fn decode(d: &mut Decoder<'b>, _: &mut C) -> Result<Self, decode::Error> {
+        match decode_tag(d)? {
+            Tagged::Text(p) => DateTime::decode_text(d, p),
+            Tagged::Epoch   => {
+                let p = d.position();
+                let t = Timestamp::decode_value(d)?;
+                DateTime::utc(t).map_err(|e| decode::Error::message(e.0).at(p))
+            }
+        }
+    }
+}
+
+impl<C> Encode<C> for DateTime {
+    fn encode<W: Write>(&self, e: &mut Encoder<W>, _: &mut C) -> Result<(), encode::Error<W::Error>> {
+        let mut b = Buf::new();
+        fmt::write(&mut b, format_args!("{self}")).map_err(|_| encode::Error::message("date/time too long"))?;
+        e.tag(IanaTag::DateTime)?.str(b.as_str())?.ok()
+    }
+}
+
+impl<C> CborLen<C> for DateTime {
+    fn cbor_len(&self, ctx: &mut C) -> usize {
+        let mut b = Buf::new();
+        let _ = fmt::write(&mut b, format_args!("{self}"));
+        IanaTag::DateTime.cbor_len(ctx) + b.len.cbor_len(ctx) + b.len
+    }
+}
+
+/// A calendar date (tag 1004).
+///
+/// A `Date` is encoded as tag 1004 ([`IanaTag::FullDate`]) followed by a
+/// text string in RFC 3339 "full-date" format, e.g. `"1940-10-09"`, as
+/// specified in RFC 8943. Years range from 0000 to 9999. Decoding also
+/// accepts tag 100 ([`IanaTag::EpochDays`]) followed by an integer number
+/// of days since 1970-01-01.
+///
+/// # Example
+///
+/// ```
+/// use minicbor::data::Date;
+///
+/// let d = Date::new(1940, 10, 9)?;
+/// assert_eq!(-10676, d.days());
+/// assert_eq!("1940-10-09", d.to_string());
+///
+/// let input = minicbor::to_vec(d)?;
+/// assert_eq!(d, minicbor::decode(&input)?);
+///
+/// // The same date as tag 100.
+/// assert_eq!(d, minicbor::decode(&[0xd8, 0x64, 0x39, 0x29, 0xb3])?);
+/// # Ok::<_, Box<dyn core::error::Error>>(())
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    days: i64
+}
+
+impl Date {
+    /// Create a date from year, month and day.
+    ///
+    /// Fails if the year is greater than 9999 or if month or day are invalid.
+    pub fn new(year: u16, month: u8, day: u8) -> Result<Self, DateTimeError> {
+        if year > 9999 {
+            return Err(DateTimeError("year out of range"))
+        }
+        if !(1 ..= 12).contains(&month) {
+            return Err(DateTimeError("month out of range"))
+        }
+        if day == 0 || day > days_in_month(year.into(), month.into()) {
+            return Err(DateTimeError("day out of range"))
+        }
+        Ok(Self { days: days_from_civil(year.into(), month.into(), day.into()) })
+    }
+
+    /// Create a date from the number of days since 1970-01-01.
+    ///
+    /// Fails if the date is not within the years 0000 to 9999.
+    pub fn from_days(days: i64) -> Result<Self, DateTimeError> {
+        if !(MIN_DAYS ..= MAX_DAYS).contains(&days) {
+            return Err(DateTimeError("year out of range"))
+        }
+        Ok(Self { days })
+    }
+
+    /// The number of days since 1970-01-01.
+    pub const fn days(&self) -> i64 {
+        self.days
+    }
+
+    pub fn year(&self) -> u16 {
+        civil_from_days(self.days).0 as u16
+    }
+
+    pub fn month(&self) -> u8 {
+        civil_from_days(self.days).1 as u8
+    }
+
+    pub fn day(&self) -> u8 {
+        civil_from_days(self.days).2 as u8
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (y, m, d) = civil_from_days(self.days);
+        write!(f, "{y:04}-{m:02}-{d:02}")
+    }
+}
+
+impl FromStr for Date {
+    type Err = DateTimeError;
+
+    /// Parse a date in RFC 3339 "full-date" format.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut p = Parser(s.as_bytes());
+        let days = p.date()?;
+        if !p.0.is_empty() {
+            return Err(DateTimeError(INVALID_FORMAT))
+        }
+        Ok(Self { days })
+    }
+}
+
+impl<'b, C> Decode<'b, C> for Date {
+    fn decode(d: &mut Decoder<'b>, _: &mut C) -> Result<Self, decode::Error> {
+        let p = d.position();
+        let t = d.tag()?;
+        let p2 = d.position();
+        if t == IanaTag::FullDate.tag() {
+            d.str()?.parse().map_err(|e: DateTimeError| decode::Error::message(e.0).at(p2))
+        } else if t == IanaTag::EpochDays.tag() {
+            Date::from_days(d.i64()?).map_err(|e| decode::Error::message(e.0).at(p2))
+        } else {
+            Err(decode::Error::tag_mismatch(t).with_message("expected tag 1004 or 100").at(p))
+        }
+    }
+}
+
+impl<C> Encode<C> for Date {
+    fn encode<W: Write>(&self, e: &mut Encoder<W>, _: &mut C) -> Result<(), encode::Error<W::Error>> {
+        let mut b = Buf::new();
+        fmt::write(&mut b, format_args!("{self}")).map_err(|_| encode::Error::message("date too long"))?;
+        e.tag(IanaTag::FullDate)?.str(b.as_str())?.ok()
+    }
+}
+
+impl<C> CborLen<C> for Date {
+    fn cbor_len(&self, ctx: &mut C) -> usize {
+        IanaTag::FullDate.cbor_len(ctx) + 11
+    }
+}
+
+/// Error when a date or time is invalid or out of range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DateTimeError(&'static str);
+
+impl fmt::Display for DateTimeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+impl core::error::Error for DateTimeError {}
+
+const INVALID_FORMAT: &str = "invalid RFC 3339 format";
+
+/// The tag preceding a point in time.
+enum Tagged {
+    /// Tag 0 at the given position.
+    Text(usize),
+    /// Tag 1.
+    Epoch
+}
+
+fn decode_tag(d: &mut Decoder<'_>) -> Result<Tagged, decode::Error> {
+    let p = d.position();
+    let t = d.tag()?;
+    if t == IanaTag::DateTime.tag() {
+        Ok(Tagged::Text(d.position()))
+    } else if t == IanaTag::Timestamp.tag() {
+        Ok(Tagged::Epoch)
+    } else {
+        Err(decode::Error::tag_mismatch(t).with_message("expected tag 0 or 1").at(p))
+    }
+}
+
+/// A minimal RFC 3339 parser.
+struct Parser<'a>(&'a [u8]);
+
+impl Parser<'_> {
+    fn next(&mut self) -> Option<u8> {
+        let (c, rest) = self.0.split_first()?;
+        self.0 = rest;
+        Some(*c)
+    }
+
+    fn expect(&mut self, c: u8) -> Result<(), DateTimeError> {
+        if self.next() != Some(c) {
+            return Err(DateTimeError(INVALID_FORMAT))
+        }
+        Ok(())
+    }
+
+    /// Parse exactly `n` decimal digits into a number not greater than `max`.
+    fn number(&mut self, n: usize, max: u32, msg: &'static str) -> Result<u32, DateTimeError> {
+        let mut x = 0;
+        for _ in 0 .. n {
+            match self.next() {
+                Some(c) if c.is_ascii_digit() => x = x * 10 + u32::from(c - b'0'),
+                _ => return Err(DateTimeError(INVALID_FORMAT))
+            }
+        }
+        if x > max {
+            return Err(DateTimeError(msg))
+        }
+        Ok(x)
+    }
+
+    /// Parse a "full-date" into days since 1970-01-01.
+    fn date(&mut self) -> Result<i64, DateTimeError> {
+        let y = self.number(4, 9999, "year out of range")?;
+        self.expect(b'-')?;
+        let m = self.number(2, 12, "month out of range")?;
+        self.expect(b'-')?;
+        let d = self.number(2, 31, "day out of range")?;
+        if m == 0 {
+            return Err(DateTimeError("month out of range"))
+        }
+        if d == 0 || d > u32::from(days_in_month(y.into(), m.into())) {
+            return Err(DateTimeError("day out of range"))
+        }
+        Ok(days_from_civil(y.into(), m.into(), d.into()))
+    }
+}
+
+/// A fixed-size buffer to format dates and times into.
+struct Buf {
+    bytes: [u8; 40],
+    len: usize
+}
+
+impl Buf {
+    fn new() -> Self {
+        Buf { bytes: [0; 40], len: 0 }
+    }
+
+    fn as_str(&self) -> &str {
+        // Only complete `str`s are ever written.
+        core::str::from_utf8(&self.bytes[.. self.len]).unwrap_or_default()
+    }
+}
+
+impl fmt::Write for Buf {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let b = self.bytes.get_mut(self.len .. self.len + s.len()).ok_or(fmt::Error)?;
+        b.copy_from_slice(s.as_bytes());
+        self.len += s.len();
+        Ok(())
+    }
+}
+
+fn is_leap_year(y: i64) -> bool {
+    y % 4 == 0 && (y % 100 != 0 || y % 400 == 0)
+}
+
+fn days_in_month(y: i64, m: i64) -> u8 {
+    match m {
+        2 if is_leap_year(y) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31
+    }
+}
+
+// The following two functions are based on the algorithms described in
+// <http://howardhinnant.github.io/date_algorithms.html>.
+
+/// Days since 1970-01-01 of the given (proleptic Gregorian) date.
+fn days_from_civil(y: i64, m: i64, d: i64) -> i64 {
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let doy = (153 * ((m + 9) % 12) + 2) / 5 + d - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// The (proleptic Gregorian) date of the given days since 1970-01-01.
+fn civil_from_days(z: i64) -> (i64, i64, i64) {
+    let z = z + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = doy - (153 * mp + 2) / 5 + 1;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 };
+    let y = yoe + era * 400;
+    (if m <= 2 { y + 1 } else { y }, m, d)
+}
+
+#[cfg(feature = "chrono")]
+mod chrono_impls {
+    use chrono::{Datelike, FixedOffset, NaiveDate, Offset, TimeZone, Utc};
+
+    use super::{Date, DateTime, DateTimeError, Timestamp};
+    use crate::decode::{self, Decode, Decoder};
+    use crate::encode::{self, CborLen, Encode, Encoder, Write};
+
+    /// `NaiveDate::num_days_from_ce` of 1970-01-01.
+    const UNIX_EPOCH_DAYS_FROM_CE: i64 = 719_163;
+
+    impl<Tz: TimeZone> TryFrom<chrono::DateTime<Tz>> for DateTime {
+        type Error = DateTimeError;
+
+        fn try_from(d: chrono::DateTime<Tz>) -> Result<Self, Self::Error> {
+            DateTime::try_from(&d)
+        }
+    }
+
+    impl<Tz: TimeZone> TryFrom<&chrono::DateTime<Tz>> for DateTime {
+        type Error = DateTimeError;
+
+        fn try_from(d: &chrono::DateTime<Tz>) -> Result<Self, Self::Error> {
+            let t = Timestamp::new(d.timestamp(), d.timestamp_subsec_nanos())
+                .map_err(|_| DateTimeError("leap seconds are not supported"))?;
+            let o = d.offset().fix().local_minus_utc();
+            if o % 60 != 0 {
+                return Err(DateTimeError("offset is not a whole number of minutes"))
+            }
+            DateTime::new(t, (o / 60) as i16)
+        }
+    }
+
+    impl TryFrom<DateTime> for chrono::DateTime<FixedOffset> {
+        type Error = DateTimeError;
+
+        fn try_from(d: DateTime) -> Result<Self, Self::Error> {
+            let t = d.timestamp();
+            let o = FixedOffset::east_opt(i32::from(d.offset()) * 60);
+            chrono::DateTime::from_timestamp(t.secs(), t.nanos())
+                .zip(o)
+                .map(|(t, o)| t.with_timezone(&o))
+                .ok_or(DateTimeError("date/time out of range"))
+        }
+    }
+
+    impl TryFrom<DateTime> for chrono::DateTime<Utc> {
+        type Error = DateTimeError;
+
+        fn try_from(d: DateTime) -> Result<Self, Self::Error> {
+            let t = d.timestamp();
+            chrono::DateTime::from_timestamp(t.secs(), t.nanos()).ok_or(DateTimeError("date/time out of range"))
+        }
+    }
+
+    impl TryFrom<NaiveDate> for Date {
+        type Error = DateTimeError;
+
+        fn try_from(d: NaiveDate) -> Result<Self, Self::Error> {
+            Date::from_days(i64::from(d.num_days_from_ce()) - UNIX_EPOCH_DAYS_FROM_CE)
+        }
+    }
+
+    impl TryFrom<Date> for NaiveDate {
+        type Error = DateTimeError;
+
+        fn try_from(d: Date) -> Result<Self, Self::Error> {
+            i32::try_from(d.days() + UNIX_EPOCH_DAYS_FROM_CE)
+                .ok()
+                .and_then(NaiveDate::from_num_days_from_ce_opt)
+                .ok_or(DateTimeError("date out of range"))
+        }
+    }
+
+    impl<C, Tz: TimeZone> Encode<C> for chrono::DateTime<Tz> {
+        fn encode<W: Write>(&self, e: &mut Encoder<W>, ctx: &mut C) -> Result<(), encode::Error<W::Error>> {
+            DateTime::try_from(self).map_err(|e| encode::Error::message(e.0))?.encode(e, ctx)
+        }
+    }
+
+    impl<C, Tz: TimeZone> CborLen<C> for chrono::DateTime<Tz> {
+        fn cbor_len(&self, ctx: &mut C) -> usize {
+            DateTime::try_from(self).map(|d| d.cbor_len(ctx)).unwrap_or(0)
+        }
+    }
+
+    impl<'b, C> Decode<'b, C> for chrono::DateTime<FixedOffset> {
+        fn decode(d: &mut Decoder<'b>, ctx: &mut C) -> Result<Self, decode::Error> {
+            let p = d.position();
+            let x: DateTime = d.decode_with(ctx)?;
+            x.try_into().map_err(|e: DateTimeError| decode::Error::message(e.0).at(p))
+        }
+    }
+
+    impl<'b, C> Decode<'b, C> for chrono::DateTime<Utc> {
+        fn decode(d: &mut Decoder<'b>, ctx: &mut C) -> Result<Self, decode::Error> {
+            let p = d.position();
+            let x: DateTime = d.decode_with(ctx)?;
+            x.try_into().map_err(|e: DateTimeError| decode::Error::message(e.0).at(p))
+        }
+    }
+
+    impl<C> Encode<C> for NaiveDate {
+        fn encode<W: Write>(&self, e: &mut Encoder<W>, ctx: &mut C) -> Result<(), encode::Error<W::Error>> {
+            Date::try_from(*self).map_err(|e| encode::Error::message(e.0))?.encode(e, ctx)
+        }
+    }
+
+    impl<C> CborLen<C> for NaiveDate {
+        fn cbor_len(&self, ctx: &mut C) -> usize {
+            Date::try_from(*self).map(|d| d.cbor_len(ctx)).unwrap_or(0)
+        }
+    }
+
+    impl<'b, C> Decode<'b, C> for NaiveDate {
+        fn decode(d: &mut Decoder<'b>, ctx: &mut C) -> Result<Self, decode::Error> {
+            let p = d.position();
+            let x: Date = d.decode_with(ctx)?;
+            x.try_into().map_err(|e: DateTimeError| decode::Error::message(e.0).at(p))
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+mod time_impls {
+    use time::{OffsetDateTime, UtcOffset};
+
+    use super::{Date, DateTime, DateTimeError, Timestamp};
+    use crate::decode::{self, Decode, Decoder};
+    use crate::encode::{self, CborLen, Encode, Encoder, Write};
+
+    /// Julian day of 1970-01-01.
+    const UNIX_EPOCH_JULIAN_DAY: i64 = 2_440_588;
+
+    impl TryFrom<OffsetDateTime> for DateTime {
+        type Error = DateTimeError;
+
+        fn try_from(d: OffsetDateTime) -> Result<Self, Self::Error> {
+            let t = Timestamp::new(d.unix_timestamp(), d.nanosecond())?;
+            let o = d.offset().whole_seconds();
+            if o % 60 != 0 {
+                return Err(DateTimeError("offset is not a whole number of minutes"))
+            }
+            DateTime::new(t, (o / 60) as i16)
+        }
+    }
+
+    impl TryFrom<DateTime> for OffsetDateTime {
+        type Error = DateTimeError;
+
+        fn try_from(d: DateTime) -> Result<Self, Self::Error> {
+            let t = d.timestamp();
+            let n = i128::from(t.secs()) * 1_000_000_000 + i128::from(t.nanos());
+            let o = UtcOffset::from_whole_seconds(i32::from(d.offset()) * 60)
+                .map_err(|_| DateTimeError("offset out of range"))?;
+            OffsetDateTime::from_unix_timestamp_nanos(n)
+                .ok()
+                .and_then(|t| t.checked_to_offset(o))
+                .ok_or(DateTimeError("date/time out of range"))
+        }
+    }
+
+    impl TryFrom<time::Date> for Date {
+        type Error = DateTimeError;
+
+        fn try_from(d: time::Date) -> Result<Self, Self::Error> {
+            Date::from_days(i64::from(d.to_julian_day()) - UNIX_EPOCH_JULIAN_DAY)
+        }
+    }
+
+    impl TryFrom<Date> for time::Date {
+        type Error = DateTimeError;
+
+        fn try_from(d: Date) -> Result<Self, Self::Error> {
+            i32::try_from(d.days() + UNIX_EPOCH_JULIAN_DAY)
+                .ok()
+                .and_then(|d| time::Date::from_julian_day(d).ok())
+                .ok_or(DateTimeError("date out of range"))
+        }
+    }
+
+    impl<C> Encode<C> for OffsetDateTime {
+        fn encode<W: Write>(&self, e: &mut Encoder<W>, ctx: &mut C) -> Result<(), encode::Error<W::Error>> {
+            DateTime::try_from(*self).map_err(|e| encode::Error::message(e.0))?.encode(e, ctx)
+        }
+    }
+
+    impl<C> CborLen<C> for OffsetDateTime {
+        fn cbor_len(&self, ctx: &mut C) -> usize {
+            DateTime::try_from(*self).map(|d| d.cbor_len(ctx)).unwrap_or(0)
+        }
+    }
+
+    impl<'b, C> Decode<'b, C> for OffsetDateTime {
+        fn decode(d: &mut Decoder<'b>, ctx: &mut C) -> Result<Self, decode::Error> {
+            let p = d.position();
+            let x: DateTime = d.decode_with(ctx)?;
+            x.try_into().map_err(|e: DateTimeError| decode::Error::message(e.0).at(p))
+        }
+    }
+
+    impl<C> Encode<C> for time::Date {
+        fn encode<W: Write>(&self, e: &mut Encoder<W>, ctx: &mut C) -> Result<(), encode::Error<W::Error>> {
+            Date::try_from(*self).map_err(|e| encode::Error::message(e.0))?.encode(e, ctx)
+        }
+    }
+
+    impl<C> CborLen<C> for time::Date {
+        fn cbor_len(&self, ctx: &mut C) -> usize {
+            Date::try_from(*self).map(|d| d.cbor_len(ctx)).unwrap_or(0)
+        }
+    }
+
+    impl<'b, C> Decode<'b, C> for time::Date {
+        fn decode(d: &mut Decoder<'b>, ctx: &mut C) -> Result<Self, decode::Error> {
+            let p = d.position();
+            let x: Date = d.decode_with(ctx)?;
+            x.try_into().map_err(|e: DateTimeError| decode::Error::message(e.0).at(p))
+        }
+    }
+}
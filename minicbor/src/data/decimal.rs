@@ -0,0 +1,182 @@
+use crate::data::{IanaTag, Int};
+use crate::decode::{self, Decode, Decoder};
+use crate::encode::{self, CborLen, Encode, Encoder, Write};
+
+macro_rules! fraction {
+    ($(#[$doc:meta])* $name:ident, $tag:ident, $expected:literal) => {
+        $(#[$doc])*
+        #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+        pub struct $name<M = Int> {
+            exponent: i64,
+            mantissa: M
+        }
+
+        impl<M> $name<M> {
+            pub const fn new(exponent: i64, mantissa: M) -> Self {
+                Self { exponent, mantissa }
+            }
+
+            pub const fn exponent(&self) -> i64 {
+                self.exponent
+            }
+
+            pub const fn mantissa(&self) -> &M {
+                &self.mantissa
+            }
+
+            pub fn into_parts(self) -> (i64, M) {
+                (self.exponent, self.mantissa)
+            }
+        }
+
+        impl<'b, C, M: Decode<'b, C>> Decode<'b, C> for $name<M> {
+            fn decode(d: &mut Decoder<'b>, ctx: &mut C) -> Result<Self, decode::Error> {
+                let p = d.position();
+                let t = d.tag()?;
+                if t != IanaTag::$tag.tag() {
+                    return Err(decode::Error::tag_mismatch(t).with_message($expected).at(p))
+                }
+                let p = d.position();
+                if d.array()? != Some(2) {
+                    return Err(decode::Error::message("expected array of length 2").at(p))
+                }
+                let exponent = d.i64()?;
+                let mantissa = M::decode(d, ctx)?;
+                Ok(Self { exponent, mantissa })
+            }
+        }
+
+        impl<C, M: Encode<C>> Encode<C> for $name<M> {
+            fn encode<W: Write>(&self, e: &mut Encoder<W>, ctx: &mut C) -> Result<(), encode::Error<W::Error>> {
+                e.tag(IanaTag::$tag)?
+                    .array(2)?
+                    .i64(self.exponent)?
+                    .encode_with(&self.mantissa, ctx)?
+                    .ok()
+            }
+        }
+
+        impl<C, M: CborLen<C>> CborLen<C> for $name<M> {
+            fn cbor_len(&self, ctx: &mut C) -> usize {
+                IanaTag::$tag.cbor_len(ctx) + 1 + self.exponent.cbor_len(ctx) + self.mantissa.cbor_len(ctx)
+            }
+        }
+    }
+}
+
+fraction! {
+    /// A decimal fraction (tag 4).
+    ///
+    /// The value of a decimal fraction is `mantissa * 10^exponent`. It is
+    /// encoded as tag 4 ([`IanaTag::Decimal`]) followed by an array of the
+    /// exponent and the mantissa. The mantissa type `M` is usually [`Int`]
+    /// or, to support bignum mantissas (tags 2 and 3), `i128` or a bignum
+    /// type such as `num_bigint::BigInt` (*requires feature* `"num-bigint"`).
+    ///
+    /// With feature `"rust_decimal"`, `rust_decimal::Decimal` can be
+    /// converted to and from `Decimal<i128>` and is encoded as such.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use minicbor::data::{Decimal, Int};
+    ///
+    /// // 273.15
+    /// let input = [0xc4, 0x82, 0x21, 0x19, 0x6a, 0xb3];
+    ///
+    /// let d: Decimal = minicbor::decode(&input)?;
+    /// assert_eq!(-2, d.exponent());
+    /// assert_eq!(&Int::from(27315), d.mantissa());
+    ///
+    /// assert_eq!(input.as_slice(), minicbor::to_vec(d)?);
+    /// # Ok::<_, Box<dyn core::error::Error>>(())
+    /// ```
+    Decimal, Decimal, "expected tag 4"
+}
+
+fraction! {
+    /// A bigfloat (tag 5).
+    ///
+    /// The value of a bigfloat is `mantissa * 2^exponent`. It is encoded as
+    /// tag 5 ([`IanaTag::Bigfloat`]) followed by an array of the exponent
+    /// and the mantissa. See [`Decimal`] regarding the mantissa type `M`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use minicbor::data::{Bigfloat, Int};
+    ///
+    /// // 1.5
+    /// let input = [0xc5, 0x82, 0x20, 0x03];
+    ///
+    /// let b: Bigfloat<i128> = minicbor::decode(&input)?;
+    /// assert_eq!((-1, 3), b.into_parts());
+    ///
+    /// assert_eq!(input.as_slice(), minicbor::to_vec(b)?);
+    /// # Ok::<_, Box<dyn core::error::Error>>(())
+    /// ```
+    Bigfloat, Bigfloat, "expected tag 5"
+}
+
+#[cfg(feature = "rust_decimal")]
+impl From<rust_decimal::Decimal> for Decimal<i128> {
+    fn from(d: rust_decimal::Decimal) -> Self {
+        Decimal::new(-i64::from(d.scale()), d.mantissa())
+    }
+}
+
+#[cfg(feature = "rust_decimal")]
+impl TryFrom<Decimal<i128>> for rust_decimal::Decimal {
+    type Error = rust_decimal::Error;
+
+    fn try_from(d: Decimal<i128>) -> Result<Self, Self::Error> {
+        let (mut e, mut m) = d.into_parts();
+        if m == 0 {
+            // Zero can be represented with any exponent, so only the scale is limited.
+            let scale = e.min(0).unsigned_abs().min(rust_decimal::Decimal::MAX_SCALE.into());
+            return rust_decimal::Decimal::try_from_i128_with_scale(0, scale as u32)
+        }
+        if e > 0 {
+            let overflow = || if m < 0 {
+                rust_decimal::Error::LessThanMinimumPossibleValue
+            } else {
+                rust_decimal::Error::ExceedsMaximumPossibleValue
+            };
+            let f = u32::try_from(e).ok().and_then(|e| 10_i128.checked_pow(e)).ok_or_else(overflow)?;
+            m = m.checked_mul(f).ok_or_else(overflow)?;
+            e = 0
+        }
+        // Remove trailing zeros if the scale is too large.
+        while e < -i64::from(rust_decimal::Decimal::MAX_SCALE) && m % 10 == 0 && m != 0 {
+            m /= 10;
+            e += 1
+        }
+        let scale = u32::try_from(e.unsigned_abs()).map_err(|_| rust_decimal::Error::ScaleExceedsMaximumPrecision(u32::MAX))?;
+        rust_decimal::Decimal::try_from_i128_with_scale(m, scale)
+    }
+}
+
+#[cfg(feature = "rust_decimal")]
+impl<'b, C> Decode<'b, C> for rust_decimal::Decimal {
+    fn decode(d: &mut Decoder<'b>, ctx: &mut C) -> Result<Self, decode::Error> {
+        let p = d.position();
+        let x: Decimal<i128> = d.decode_with(ctx)?;
+        rust_decimal::Decimal::try_from(x).map_err(|_| {
+            decode::Error::message("decimal fraction exceeds range of `rust_decimal::Decimal`").at(p)
+        })
+    }
+}
+
+#[cfg(feature = "rust_decimal")]
+impl<C> Encode<C> for rust_decimal::Decimal {
+    fn encode<W: Write>(&self, e: &mut Encoder<W>, ctx: &mut C) -> Result<(), encode::Error<W::Error>> {
+        Decimal::from(*self).encode(e, ctx)
+    }
+}
+
+#[cfg(feature = "rust_decimal")]
+impl<C> CborLen<C> for rust_decimal::Decimal {
+    fn cbor_len(&self, ctx: &mut C) -> usize {
+        Decimal::from(*self).cbor_len(ctx)
+    }
+}
@@ -0,0 +1,172 @@
+use core::ops::{Deref, DerefMut};
+
+#[cfg(feature = "alloc")]
+use core::marker::PhantomData;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use crate::BYTES;
+use crate::data::IanaTag;
+use crate::decode::{self, Decode, Decoder};
+use crate::encode::{self, CborLen, Encode, Encoder, Write};
+
+/// A CBOR data item embedded in a byte string (tag 24).
+///
+/// `Embedded<T>` is encoded as tag 24 ([`IanaTag::Cbor`]) followed by a
+/// byte string which contains the encoding of `T`. The length of the byte
+/// string is determined with [`CborLen`], i.e. the value is written directly
+/// to the encoder without an intermediate buffer. Only within a string
+/// reference namespace (tag 256) is the value buffered, because
+/// the byte string itself may be replaced by a reference. Decoding reads the byte
+/// string and decodes `T` from it, using the same settings and limits as
+/// the outer decoder. It is an error if `T` does not consume all bytes.
+///
+/// To defer decoding of the embedded item use `Embedded<Raw<'b>>` (see
+/// [`Raw`](crate::data::Raw)), which only checks that the byte string
+/// contains a single well-formed data item.
+///
+/// # Example
+///
+/// ```
+/// use minicbor::data::{Embedded, Raw};
+///
+/// let input = minicbor::to_vec(Embedded::new([1, 2]))?;
+/// assert_eq!(input, [0xd8, 0x18, 0x43, 0x82, 0x01, 0x02]);
+///
+/// let e: Embedded<Vec<u8>> = minicbor::decode(&input)?;
+/// assert_eq!(vec![1, 2], e.into_value());
+///
+/// let e: Embedded<Raw> = minicbor::decode(&input)?;
+/// assert_eq!(&[0x82, 0x01, 0x02], e.as_bytes());
+/// # Ok::<_, Box<dyn core::error::Error>>(())
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Embedded<T>(T);
+
+impl<T> Embedded<T> {
+    pub const fn new(val: T) -> Self {
+        Self(val)
+    }
+
+    pub const fn value(&self) -> &T {
+        &self.0
+    }
+
+    pub fn value_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+
+    pub fn into_value(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for Embedded<T> {
+    fn from(val: T) -> Self {
+        Self::new(val)
+    }
+}
+
+impl<T> Deref for Embedded<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Embedded<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<'b, C, T: Decode<'b, C>> Decode<'b, C> for Embedded<T> {
+    fn decode(d: &mut Decoder<'b>, ctx: &mut C) -> Result<Self, decode::Error> {
+        let p = d.position();
+        let t = d.tag()?;
+        if t != IanaTag::Cbor.tag() {
+            return Err(decode::Error::tag_mismatch(t).with_message("expected tag 24").at(p))
+        }
+        let b = d.bytes()?;
+        let start = d.position() - b.len();
+        let at = |e: decode::Error| match e.position() {
+            Some(q) => e.at(start + q),
+            None    => e
+        };
+        d.with_nested(b, |n| {
+            let v = T::decode(n, ctx).map_err(at)?;
+            if n.position() != b.len() {
+                return Err(decode::Error::trailing_data().at(start + n.position()))
+            }
+            Ok(Embedded(v))
+        })
+    }
+}
+
+impl<C, T: Encode<C> + CborLen<C>> Encode<C> for Embedded<T> {
+    fn encode<W: Write>(&self, e: &mut Encoder<W>, ctx: &mut C) -> Result<(), encode::Error<W::Error>> {
+        let n = self.0.cbor_len(ctx);
+        // Inside a string reference namespace the byte string takes part in
+        // it like any other, so it needs to be known up front. The embedded
+        // item itself is encoded separately, just as it is decoded.
+        #[cfg(feature = "alloc")]
+        if e.in_string_refs() {
+            let mut c = Encoder::new(Buffer { bytes: Vec::with_capacity(n), _marker: PhantomData });
+            self.0.encode(&mut c, ctx)?;
+            if c.writer().bytes.len() != n {
+                return Err(encode::Error::message("length of embedded data item differs from its `CborLen`"))
+            }
+            return e.tag(IanaTag::Cbor)?.bytes(&c.writer().bytes)?.ok()
+        }
+        e.tag(IanaTag::Cbor)?.type_len(BYTES, n as u64)?;
+        let mut c = Encoder::new(Counter { writer: e.writer_mut(), count: 0 });
+        self.0.encode(&mut c, ctx)?;
+        if c.writer().count != n {
+            return Err(encode::Error::message("length of embedded data item differs from its `CborLen`"))
+        }
+        Ok(())
+    }
+}
+
+impl<C, T: CborLen<C>> CborLen<C> for Embedded<T> {
+    fn cbor_len(&self, ctx: &mut C) -> usize {
+        let n = self.0.cbor_len(ctx);
+        IanaTag::Cbor.cbor_len(ctx) + n.cbor_len(ctx) + n
+    }
+}
+
+/// A `Write` impl which counts the number of bytes written.
+struct Counter<'a, W> {
+    writer: &'a mut W,
+    count: usize
+}
+
+impl<W: Write> Write for Counter<'_, W> {
+    type Error = W::Error;
+
+    fn write_all(&mut self, buf: &[u8]) -> Result<(), Self::Error> {
+        self.writer.write_all(buf)?;
+        self.count += buf.len();
+        Ok(())
+    }
+}
+
+/// A `Write` impl which collects bytes in memory, with the error type of
+/// another `Write` impl.
+#[cfg(feature = "alloc")]
+struct Buffer<E> {
+    bytes: Vec<u8>,
+    _marker: PhantomData<fn() -> E>
+}
+
+#[cfg(feature = "alloc")]
+impl<E> Write for Buffer<E> {
+    type Error = E;
+
+    fn write_all(&mut self, buf: &[u8]) -> Result<(), Self::Error> {
+        self.bytes.extend_from_slice(buf);
+        Ok(())
+    }
+}
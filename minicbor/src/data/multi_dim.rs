@@ -0,0 +1,385 @@
+use core::fmt;
+
+use alloc::vec::Vec;
+
+use crate::data::{IanaTag, Type, TypedArray, TypedArrayElement};
+use crate::decode::{self, Decode, Decoder};
+use crate::encode::{self, CborLen, Encode, Encoder, Write};
+
+/// A multi-dimensional array (RFC 8746, tags 40 and 1040).
+///
+/// A multi-dimensional array consists of its dimensions and a flat array
+/// of all elements. It is encoded as tag 40 ([`IanaTag::MultiDimArrayR`])
+/// if the elements are in row-major order or tag 1040
+/// ([`IanaTag::MultiDimArrayC`]) if they are in column-major order,
+/// followed by an array of the dimensions and the elements.
+///
+/// The elements type `A` can be any type representing a sequence, e.g.
+/// `Vec<T>` for a regular CBOR array or [`TypedArray`] for a typed array.
+/// When creating a `MultiDimArray` with [`MultiDimArray::new`] or decoding
+/// regular or typed arrays, the number of elements is checked against the
+/// dimensions.
+/// `MultiDimArray<Vec<T>>` supports indexing, changing the element order
+/// and conversions from and to nested `Vec`s of two or three dimensions.
+///
+/// *Requires feature* `"alloc"`.
+///
+/// # Example
+///
+/// ```
+/// use minicbor::data::{ArrayOrder, MultiDimArray};
+///
+/// let m = MultiDimArray::try_from(vec![vec![1, 2, 3], vec![4, 5, 6]])?;
+/// assert_eq!([2, 3], m.dims());
+/// assert_eq!(Some(&6), m.get(&[1, 2]));
+///
+/// let input = minicbor::to_vec(&m)?;
+/// assert_eq!(input, [0xd8, 0x28, 0x82, 0x82, 0x02, 0x03, 0x86, 1, 2, 3, 4, 5, 6]);
+///
+/// // The same matrix in column-major order.
+/// let input = [0xd9, 0x04, 0x10, 0x82, 0x82, 0x02, 0x03, 0x86, 1, 4, 2, 5, 3, 6];
+/// let c: MultiDimArray<Vec<u8>> = minicbor::decode(&input)?;
+/// assert_eq!(ArrayOrder::ColumnMajor, c.order());
+/// assert_eq!(Some(&6), c.get(&[1, 2]));
+/// assert_eq!(vec![vec![1, 2, 3], vec![4, 5, 6]], Vec::<Vec<u8>>::try_from(c)?);
+/// # Ok::<_, Box<dyn core::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MultiDimArray<A> {
+    dims: Vec<usize>,
+    order: ArrayOrder,
+    elements: A
+}
+
+/// The order of elements in a [`MultiDimArray`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ArrayOrder {
+    /// The last index varies fastest (tag 40).
+    RowMajor,
+    /// The first index varies fastest (tag 1040).
+    ColumnMajor
+}
+
+impl<A> MultiDimArray<A> {
+    /// Create a multi-dimensional array.
+    ///
+    /// Returns an error if there are no dimensions or if the number of
+    /// elements is not equal to the product of dimensions.
+    pub fn new(dims: Vec<usize>, order: ArrayOrder, elements: A) -> Result<Self, DimensionError>
+    where
+        A: ArrayElements
+    {
+        if dims.is_empty() || size(&dims) != Some(elements.count()) {
+            return Err(DimensionError(()))
+        }
+        Ok(Self { dims, order, elements })
+    }
+
+    pub fn dims(&self) -> &[usize] {
+        &self.dims
+    }
+
+    pub fn order(&self) -> ArrayOrder {
+        self.order
+    }
+
+    pub fn elements(&self) -> &A {
+        &self.elements
+    }
+
+    pub fn elements_mut(&mut self) -> &mut A {
+        &mut self.elements
+    }
+
+    pub fn into_elements(self) -> A {
+        self.elements
+    }
+
+    /// Apply a function to the elements, keeping dimensions and order.
+    pub fn map<B, F: FnOnce(A) -> B>(self, f: F) -> MultiDimArray<B> {
+        MultiDimArray { dims: self.dims, order: self.order, elements: f(self.elements) }
+    }
+
+    /// Get the position in the flat array of the element at the given index.
+    fn position(&self, index: &[usize]) -> Option<usize> {
+        if index.len() != self.dims.len() || index.iter().zip(&self.dims).any(|(i, d)| i >= d) {
+            return None
+        }
+        Some(offset(&self.dims, self.order, index))
+    }
+}
+
+impl<T> MultiDimArray<Vec<T>> {
+    /// Get the element at the given index.
+    ///
+    /// The index must have one entry for each dimension.
+    pub fn get(&self, index: &[usize]) -> Option<&T> {
+        self.elements.get(self.position(index)?)
+    }
+
+    /// Get the element at the given index.
+    ///
+    /// The index must have one entry for each dimension.
+    pub fn get_mut(&mut self, index: &[usize]) -> Option<&mut T> {
+        let p = self.position(index)?;
+        self.elements.get_mut(p)
+    }
+
+    /// Rearrange the elements into the given order.
+    ///
+    /// Returns an error if the number of elements is not equal to the
+    /// product of dimensions.
+    pub fn into_order(self, order: ArrayOrder) -> Result<Self, DimensionError> {
+        if size(&self.dims) != Some(self.elements.len()) {
+            return Err(DimensionError(()))
+        }
+        if order == self.order || self.dims.len() < 2 {
+            return Ok(Self { order, ..self })
+        }
+        let mut source: Vec<Option<T>> = self.elements.into_iter().map(Some).collect();
+        let mut elements = Vec::with_capacity(source.len());
+        let mut index = alloc::vec![0; self.dims.len()];
+        // Visit all indices in target order and move the source elements over.
+        for _ in 0 .. source.len() {
+            let p = offset(&self.dims, self.order, &index);
+            if let Some(x) = source.get_mut(p).and_then(Option::take) {
+                elements.push(x)
+            }
+            next_index(&mut index, &self.dims, order)
+        }
+        Ok(Self { dims: self.dims, order, elements })
+    }
+}
+
+/// The number of elements of an array with the given dimensions.
+fn size(dims: &[usize]) -> Option<usize> {
+    dims.iter().try_fold(1usize, |n, d| n.checked_mul(*d))
+}
+
+/// The position in the flat array of the element at the given index.
+fn offset(dims: &[usize], order: ArrayOrder, index: &[usize]) -> usize {
+    let mut p = 0;
+    match order {
+        ArrayOrder::RowMajor => for (i, d) in index.iter().zip(dims) {
+            p = p * d + i
+        }
+        ArrayOrder::ColumnMajor => for (i, d) in index.iter().zip(dims).rev() {
+            p = p * d + i
+        }
+    }
+    p
+}
+
+/// Advance the index to the next element in the given order.
+fn next_index(index: &mut [usize], dims: &[usize], order: ArrayOrder) {
+    let mut step = |k: usize| {
+        index[k] += 1;
+        if index[k] < dims[k] {
+            return true
+        }
+        index[k] = 0;
+        false
+    };
+    match order {
+        ArrayOrder::RowMajor => {
+            for k in (0 .. dims.len()).rev() {
+                if step(k) { break }
+            }
+        }
+        ArrayOrder::ColumnMajor => {
+            for k in 0 .. dims.len() {
+                if step(k) { break }
+            }
+        }
+    }
+}
+
+impl<T> TryFrom<Vec<Vec<T>>> for MultiDimArray<Vec<T>> {
+    type Error = DimensionError;
+
+    /// Create a row-major array from rows of equal length.
+    fn try_from(rows: Vec<Vec<T>>) -> Result<Self, Self::Error> {
+        let cols = rows.first().map(Vec::len).unwrap_or(0);
+        if rows.iter().any(|r| r.len() != cols) {
+            return Err(DimensionError(()))
+        }
+        let dims = alloc::vec![rows.len(), cols];
+        Self::new(dims, ArrayOrder::RowMajor, rows.into_iter().flatten().collect())
+    }
+}
+
+impl<T> TryFrom<Vec<Vec<Vec<T>>>> for MultiDimArray<Vec<T>> {
+    type Error = DimensionError;
+
+    /// Create a row-major array from nested `Vec`s of equal length.
+    fn try_from(xs: Vec<Vec<Vec<T>>>) -> Result<Self, Self::Error> {
+        let d1 = xs.first().map(Vec::len).unwrap_or(0);
+        let d2 = xs.first().and_then(|x| x.first()).map(Vec::len).unwrap_or(0);
+        if xs.iter().any(|x| x.len() != d1 || x.iter().any(|y| y.len() != d2)) {
+            return Err(DimensionError(()))
+        }
+        let dims = alloc::vec![xs.len(), d1, d2];
+        Self::new(dims, ArrayOrder::RowMajor, xs.into_iter().flatten().flatten().collect())
+    }
+}
+
+impl<T> TryFrom<MultiDimArray<Vec<T>>> for Vec<Vec<T>> {
+    type Error = DimensionError;
+
+    /// Split a two-dimensional array into rows.
+    fn try_from(a: MultiDimArray<Vec<T>>) -> Result<Self, Self::Error> {
+        let &[rows, cols] = a.dims.as_slice() else {
+            return Err(DimensionError(()))
+        };
+        let a = a.into_order(ArrayOrder::RowMajor)?;
+        Ok(chunks(a.elements, rows, cols))
+    }
+}
+
+impl<T> TryFrom<MultiDimArray<Vec<T>>> for Vec<Vec<Vec<T>>> {
+    type Error = DimensionError;
+
+    /// Split a three-dimensional array into nested `Vec`s.
+    fn try_from(a: MultiDimArray<Vec<T>>) -> Result<Self, Self::Error> {
+        let &[d0, d1, d2] = a.dims.as_slice() else {
+            return Err(DimensionError(()))
+        };
+        let a = a.into_order(ArrayOrder::RowMajor)?;
+        Ok(chunks(chunks(a.elements, d0 * d1, d2), d0, d1))
+    }
+}
+
+/// Split a `Vec` into `k` `Vec`s of length `n`.
+fn chunks<T>(xs: Vec<T>, k: usize, n: usize) -> Vec<Vec<T>> {
+    let mut xs = xs.into_iter();
+    (0 .. k).map(|_| xs.by_ref().take(n).collect()).collect()
+}
+
+impl<'b, C, A: Decode<'b, C>> Decode<'b, C> for MultiDimArray<A> {
+    fn decode(d: &mut Decoder<'b>, ctx: &mut C) -> Result<Self, decode::Error> {
+        let p = d.position();
+        let t = d.tag()?;
+        let order = if t == IanaTag::MultiDimArrayR.tag() {
+            ArrayOrder::RowMajor
+        } else if t == IanaTag::MultiDimArrayC.tag() {
+            ArrayOrder::ColumnMajor
+        } else {
+            return Err(decode::Error::tag_mismatch(t).with_message("expected tag 40 or 1040").at(p))
+        };
+        let p = d.position();
+        if d.array()? != Some(2) {
+            return Err(decode::Error::message("expected array of length 2").at(p))
+        }
+        let p = d.position();
+        let dims: Vec<usize> = d.decode_with(ctx)?;
+        let n = size(&dims);
+        if dims.is_empty() || n.is_none() {
+            return Err(decode::Error::message("invalid dimensions").at(p))
+        }
+        let p = d.position();
+        if count(d)?.is_some_and(|c| Some(c) != n) {
+            return Err(decode::Error::message("number of elements does not match dimensions").at(p))
+        }
+        let elements = A::decode(d, ctx)?;
+        Ok(Self { dims, order, elements })
+    }
+}
+
+/// Count the number of elements of the next array or typed array.
+///
+/// Returns `None` for other data items.
+fn count(d: &mut Decoder<'_>) -> Result<Option<usize>, decode::Error> {
+    let mut d = d.probe();
+    match d.datatype()? {
+        Type::Array | Type::ArrayIndef => match d.array()? {
+            Some(n) => Ok(usize::try_from(n).ok()),
+            None    => {
+                let mut n = 0;
+                while d.datatype()? != Type::Break {
+                    d.skip()?;
+                    n += 1
+                }
+                Ok(Some(n))
+            }
+        }
+        Type::Tag => {
+            let t = u64::from(d.tag()?);
+            // Typed array tags (RFC 8746, section 2.1) are of the form
+            // 0b010_f_s_ll, where `f` denotes floats and `ll` the size.
+            if !(0x40 ..= 0x57).contains(&t) || d.datatype()? != Type::Bytes {
+                return Ok(None)
+            }
+            let size = if t & 0x10 == 0 { 1 << (t & 3) } else { 2 << (t & 3) };
+            Ok(Some(d.bytes()?.len() / size))
+        }
+        _ => Ok(None)
+    }
+}
+
+impl<C, A: Encode<C>> Encode<C> for MultiDimArray<A> {
+    fn encode<W: Write>(&self, e: &mut Encoder<W>, ctx: &mut C) -> Result<(), encode::Error<W::Error>> {
+        let t = match self.order {
+            ArrayOrder::RowMajor    => IanaTag::MultiDimArrayR,
+            ArrayOrder::ColumnMajor => IanaTag::MultiDimArrayC
+        };
+        e.tag(t)?.array(2)?.encode_with(&self.dims, ctx)?.encode_with(&self.elements, ctx)?.ok()
+    }
+}
+
+impl<C, A: CborLen<C>> CborLen<C> for MultiDimArray<A> {
+    fn cbor_len(&self, ctx: &mut C) -> usize {
+        let t = match self.order {
+            ArrayOrder::RowMajor    => IanaTag::MultiDimArrayR,
+            ArrayOrder::ColumnMajor => IanaTag::MultiDimArrayC
+        };
+        t.cbor_len(ctx) + 1 + self.dims.cbor_len(ctx) + self.elements.cbor_len(ctx)
+    }
+}
+
+/// Element sequences of a [`MultiDimArray`] whose length is known.
+///
+/// This trait is sealed and can not be implemented outside of this crate.
+pub trait ArrayElements: sealed::Sealed {
+    /// The number of elements.
+    fn count(&self) -> usize;
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+impl<T> sealed::Sealed for Vec<T> {}
+
+impl<T> ArrayElements for Vec<T> {
+    fn count(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T> sealed::Sealed for &[T] {}
+
+impl<T> ArrayElements for &[T] {
+    fn count(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T: TypedArrayElement> sealed::Sealed for TypedArray<'_, T> {}
+
+impl<T: TypedArrayElement> ArrayElements for TypedArray<'_, T> {
+    fn count(&self) -> usize {
+        self.len()
+    }
+}
+
+/// Error when elements and dimensions of a [`MultiDimArray`] do not match.
+#[derive(Debug)]
+pub struct DimensionError(());
+
+impl fmt::Display for DimensionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("elements do not match dimensions")
+    }
+}
+
+impl core::error::Error for DimensionError {}
@@ -0,0 +1,226 @@
+use core::fmt;
+
+use crate::decode::{self, Decode, Decoder};
+use crate::decode::info::{Progress, Scanner};
+use crate::encode::{self, CborLen, Encode, Encoder, Write};
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// The encoded bytes of a single CBOR data item.
+///
+/// Decoding a `Raw` value skips over the next data item and retains its
+/// bytes without decoding them any further. Encoding a `Raw` value writes
+/// its bytes verbatim. This allows deferring the decoding of parts of the
+/// input (e.g. the payload of an envelope type) until needed, or forwarding
+/// them unchanged.
+///
+/// A `Raw` value always contains exactly one well-formed data item. Use
+/// [`Raw::new`] to check arbitrary bytes. At most 128 nested indefinite-length
+/// items are supported (cf. [`Scanner`]).
+///
+/// # Example
+///
+/// ```
+/// use minicbor::data::Raw;
+///
+/// // [1, ["a", "b"]]
+/// let input = [0x82, 0x01, 0x82, 0x61, 0x61, 0x61, 0x62];
+///
+/// let (version, payload): (u8, Raw) = minicbor::decode(&input)?;
+/// assert_eq!(1, version);
+/// assert_eq!(&input[2 ..], payload.as_bytes());
+/// assert_eq!(vec!["a", "b"], payload.decode::<Vec<&str>>()?);
+///
+/// assert_eq!(input.as_slice(), minicbor::to_vec((version, payload))?);
+/// # Ok::<_, Box<dyn core::error::Error>>(())
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Raw<'b> {
+    bytes: &'b [u8]
+}
+
+impl<'b> Raw<'b> {
+    /// Create a `Raw` value from the encoding of a single CBOR data item.
+    ///
+    /// Fails if the bytes are not a well-formed data item or if they are
+    /// followed by other bytes.
+    pub fn new(bytes: &'b [u8]) -> Result<Self, decode::Error> {
+        let mut d = Decoder::new(bytes);
+        let raw: Raw = d.decode()?;
+        if d.position() != bytes.len() {
+            return Err(decode::Error::trailing_data().at(d.position()))
+        }
+        Ok(raw)
+    }
+
+    /// Get the encoded bytes.
+    pub fn as_bytes(&self) -> &'b [u8] {
+        self.bytes
+    }
+
+    /// Decode the data item.
+    pub fn decode<T: Decode<'b, ()>>(&self) -> Result<T, decode::Error> {
+        Decoder::new(self.bytes).decode()
+    }
+
+    /// Decode the data item with a user provided decoding context.
+    pub fn decode_with<C, T: Decode<'b, C>>(&self, ctx: &mut C) -> Result<T, decode::Error> {
+        Decoder::new(self.bytes).decode_with(ctx)
+    }
+
+    /// Copy the bytes into a [`RawBuf`].
+    ///
+    /// *Requires feature* `"alloc"`.
+    #[cfg(feature = "alloc")]
+    pub fn to_raw_buf(&self) -> RawBuf {
+        RawBuf { bytes: self.bytes.to_vec() }
+    }
+}
+
+impl fmt::Debug for Raw<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Raw").field(&Hex(self.bytes)).finish()
+    }
+}
+
+impl AsRef<[u8]> for Raw<'_> {
+    fn as_ref(&self) -> &[u8] {
+        self.bytes
+    }
+}
+
+impl<'b, C> Decode<'b, C> for Raw<'b> {
+    fn decode(d: &mut Decoder<'b>, _: &mut C) -> Result<Self, decode::Error> {
+        let p = d.position();
+        // `Decoder::skip` is lenient w.r.t. unexpected breaks, hence the
+        // structure of the item is checked first.
+        match Scanner::<128>::new().scan(&d.input()[p ..]) {
+            Ok(Progress::Complete(_))   => {}
+            Ok(Progress::Incomplete(_)) => return Err(decode::Error::end_of_input()),
+            Err(e) => return Err(match e.position() {
+                Some(q) => e.at(p + q),
+                None    => e
+            })
+        }
+        d.skip()?;
+        Ok(Raw { bytes: &d.input()[p .. d.position()] })
+    }
+}
+
+impl<C> Encode<C> for Raw<'_> {
+    fn encode<W: Write>(&self, e: &mut Encoder<W>, _: &mut C) -> Result<(), encode::Error<W::Error>> {
+        #[cfg(feature = "alloc")]
+        e.add_strings(self.bytes)?;
+        e.writer_mut().write_all(self.bytes).map_err(encode::Error::write)
+    }
+}
+
+impl<C> CborLen<C> for Raw<'_> {
+    fn cbor_len(&self, _: &mut C) -> usize {
+        self.bytes.len()
+    }
+}
+
+/// The encoded bytes of a single CBOR data item (owned version).
+///
+/// Like [`Raw`] but holding a copy of the bytes, so that it can be kept
+/// around independently of the input.
+///
+/// *Requires feature* `"alloc"`.
+#[cfg(feature = "alloc")]
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RawBuf {
+    bytes: Vec<u8>
+}
+
+#[cfg(feature = "alloc")]
+impl RawBuf {
+    /// Create a `RawBuf` value from the encoding of a single CBOR data item.
+    ///
+    /// Fails if the bytes are not a well-formed data item or if they are
+    /// followed by other bytes.
+    pub fn new(bytes: Vec<u8>) -> Result<Self, decode::Error> {
+        Raw::new(&bytes)?;
+        Ok(RawBuf { bytes })
+    }
+
+    /// Get the encoded bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Get a borrowed [`Raw`] value.
+    pub fn as_raw(&self) -> Raw<'_> {
+        Raw { bytes: &self.bytes }
+    }
+
+    /// Decode the data item.
+    pub fn decode<'a, T: Decode<'a, ()>>(&'a self) -> Result<T, decode::Error> {
+        Decoder::new(&self.bytes).decode()
+    }
+
+    /// Decode the data item with a user provided decoding context.
+    pub fn decode_with<'a, C, T: Decode<'a, C>>(&'a self, ctx: &mut C) -> Result<T, decode::Error> {
+        Decoder::new(&self.bytes).decode_with(ctx)
+    }
+
+    /// Get back the encoded bytes.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Debug for RawBuf {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("RawBuf").field(&Hex(&self.bytes)).finish()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl AsRef<[u8]> for RawBuf {
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<Raw<'_>> for RawBuf {
+    fn from(r: Raw<'_>) -> Self {
+        r.to_raw_buf()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'b, C> Decode<'b, C> for RawBuf {
+    fn decode(d: &mut Decoder<'b>, ctx: &mut C) -> Result<Self, decode::Error> {
+        d.decode_with::<C, Raw>(ctx).map(RawBuf::from)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<C> Encode<C> for RawBuf {
+    fn encode<W: Write>(&self, e: &mut Encoder<W>, ctx: &mut C) -> Result<(), encode::Error<W::Error>> {
+        self.as_raw().encode(e, ctx)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<C> CborLen<C> for RawBuf {
+    fn cbor_len(&self, _: &mut C) -> usize {
+        self.bytes.len()
+    }
+}
+
+/// Debug output of bytes as a hexadecimal string.
+struct Hex<'a>(&'a [u8]);
+
+impl fmt::Debug for Hex<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for b in self.0 {
+            write!(f, "{b:02x}")?
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,167 @@
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::rc::Rc;
+use alloc::vec::Vec;
+use core::any::Any;
+use core::ops::Deref;
+
+use crate::data::{IanaTag, Type};
+use crate::decode::{self, Decode, Decoder};
+use crate::encode::{self, Encode, Encoder, Write};
+
+#[cfg(target_has_atomic = "ptr")]
+use alloc::sync::Arc;
+
+/// A shared value (tags 28 and 29).
+///
+/// Wraps an `Rc<T>` or `Arc<T>` such that a value which is shared by
+/// several pointers is encoded only once. The first occurrence is encoded
+/// as tag 28 followed by the value, every further occurrence of the same
+/// allocation as tag 29 followed by the index of the first one. Decoding
+/// restores the sharing, i.e. all references point to the same allocation.
+/// Untagged values are decoded as regular, unshared values.
+///
+/// The encoder and decoder keep track of shared values in a [`SharedValues`]
+/// context, hence `Shared` requires a context type `C: AsMut<SharedValues>`.
+/// When decoding, all values marked with tag 28 must be decoded as `Shared`
+/// in order for the indices to match. Please note that `Shared` does not
+/// implement [`CborLen`].
+///
+/// [`CborLen`]: crate::CborLen
+///
+/// # Example
+///
+/// ```
+/// use minicbor::data::{Shared, SharedValues};
+/// use std::rc::Rc;
+///
+/// let x = Rc::new(vec![1, 2, 3]);
+/// let xs = [Shared::new(x.clone()), Shared::new(x.clone()), Shared::new(x)];
+///
+/// let input = minicbor::to_vec_with(&xs, &mut SharedValues::new())?;
+/// // [28([1, 2, 3]), 29(0), 29(0)]
+/// assert_eq!(input, [0x83, 0xd8, 0x1c, 0x83, 0x01, 0x02, 0x03, 0xd8, 0x1d, 0x00, 0xd8, 0x1d, 0x00]);
+///
+/// let ys: Vec<Shared<Rc<Vec<u8>>>> = minicbor::decode_with(&input, &mut SharedValues::new())?;
+/// assert!(Rc::ptr_eq(&ys[0], &ys[2]));
+/// # Ok::<_, Box<dyn core::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Shared<P>(P);
+
+impl<P> Shared<P> {
+    pub const fn new(ptr: P) -> Self {
+        Self(ptr)
+    }
+
+    pub fn into_inner(self) -> P {
+        self.0
+    }
+}
+
+impl<P> From<P> for Shared<P> {
+    fn from(ptr: P) -> Self {
+        Self::new(ptr)
+    }
+}
+
+impl<P> Deref for Shared<P> {
+    type Target = P;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// The context of [`Shared`] values.
+///
+/// A fresh `SharedValues` should be used for each encoding or decoding.
+#[derive(Debug, Default)]
+pub struct SharedValues {
+    /// Indices of the values encoded so far by their address.
+    indices: BTreeMap<usize, u64>,
+    /// The shared values by index. While being decoded an entry is `None`.
+    ///
+    /// When encoding, the values are kept alive so that their addresses
+    /// remain unique.
+    values: Vec<Option<Box<dyn Any>>>
+}
+
+impl SharedValues {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the value with the given index.
+    fn get<P: Clone + 'static>(&self, i: u64) -> Result<P, &'static str> {
+        match usize::try_from(i).ok().and_then(|i| self.values.get(i)) {
+            Some(Some(x)) => x.downcast_ref::<P>().cloned().ok_or("unexpected type of shared value"),
+            Some(None)    => Err("cyclic shared value reference"),
+            None          => Err("unknown shared value reference")
+        }
+    }
+}
+
+impl AsMut<SharedValues> for SharedValues {
+    fn as_mut(&mut self) -> &mut SharedValues {
+        self
+    }
+}
+
+macro_rules! shared_impls {
+    ($p:ident) => {
+        impl<C, T> Encode<C> for Shared<$p<T>>
+        where
+            C: AsMut<SharedValues>,
+            T: Encode<C> + 'static
+        {
+            fn encode<W: Write>(&self, e: &mut Encoder<W>, ctx: &mut C) -> Result<(), encode::Error<W::Error>> {
+                let s = ctx.as_mut();
+                let k = $p::as_ptr(&self.0) as usize;
+                if let Some(i) = s.indices.get(&k) {
+                    return e.tag(IanaTag::SharedRef)?.u64(*i)?.ok()
+                }
+                s.indices.insert(k, s.values.len() as u64);
+                s.values.push(Some(Box::new(self.0.clone())));
+                e.tag(IanaTag::SharedValue)?;
+                self.0.encode(e, ctx)
+            }
+        }
+
+        impl<'b, C, T> Decode<'b, C> for Shared<$p<T>>
+        where
+            C: AsMut<SharedValues>,
+            T: Decode<'b, C> + 'static
+        {
+            fn decode(d: &mut Decoder<'b>, ctx: &mut C) -> Result<Self, decode::Error> {
+                let p = d.position();
+                if d.datatype()? == Type::Tag {
+                    let t = d.tag()?;
+                    if t == IanaTag::SharedValue.tag() {
+                        let i = ctx.as_mut().values.len();
+                        ctx.as_mut().values.push(None);
+                        let x = $p::new(T::decode(d, ctx)?);
+                        if let Some(v) = ctx.as_mut().values.get_mut(i) {
+                            *v = Some(Box::new(x.clone()))
+                        }
+                        return Ok(Shared(x))
+                    }
+                    if t == IanaTag::SharedRef.tag() {
+                        let p = d.position();
+                        let i = d.u64()?;
+                        return ctx.as_mut().get(i)
+                            .map(Shared)
+                            .map_err(|m| decode::Error::message(m).at(p))
+                    }
+                    d.set_position(p)
+                }
+                T::decode(d, ctx).map(|x| Shared($p::new(x)))
+            }
+        }
+    }
+}
+
+shared_impls!(Rc);
+
+#[cfg(target_has_atomic = "ptr")]
+shared_impls!(Arc);
@@ -0,0 +1,124 @@
+use core::ops::{Deref, DerefMut};
+
+use crate::data::IanaTag;
+use crate::decode::{self, Decode, Decoder};
+use crate::encode::{self, Encode, Encoder, Write};
+
+/// A value encoded within a string reference namespace (tag 256).
+///
+/// This implements the [stringref] extension: Inside the namespace, every
+/// byte or text string of definite length is remembered if a reference to
+/// it would be shorter than the string itself. When it occurs again, only a
+/// reference (tag 25) to its index is encoded.
+///
+/// Encoding and decoding of references is handled by [`Encoder::str`],
+/// [`Encoder::bytes`], [`Decoder::str`] and [`Decoder::bytes`], so all types
+/// which use those (e.g. `&str`, `String` or [`ByteSlice`]) take part without
+/// further changes. Types which inspect the data type before decoding a
+/// string (e.g. [`Value`]) see a reference as a regular tag.
+///
+/// Please note that `StringRefs` does not implement [`CborLen`] as the
+/// encoded length depends on the strings seen before. The encoding is also
+/// not suitable for [`to_vec_deterministic`] which may reorder map entries
+/// and thereby the order of strings.
+///
+/// [stringref]: http://cbor.schmorp.de/stringref
+/// [`ByteSlice`]: crate::bytes::ByteSlice
+/// [`Value`]: crate::data::Value
+/// [`CborLen`]: crate::CborLen
+/// [`to_vec_deterministic`]: crate::to_vec_deterministic
+///
+/// # Example
+///
+/// ```
+/// use minicbor::data::StringRefs;
+///
+/// let names = ["alice", "bob", "alice", "alice"];
+///
+/// let input = minicbor::to_vec(StringRefs::new(names))?;
+/// // 256(["alice", "bob", 25(0), 25(0)])
+/// assert_eq!(input[.. 4], [0xd9, 0x01, 0x00, 0x84]);
+/// assert_eq!(input[14 ..], [0xd8, 0x19, 0x00, 0xd8, 0x19, 0x00]);
+///
+/// let output: StringRefs<Vec<String>> = minicbor::decode(&input)?;
+/// assert_eq!(names.as_slice(), output.as_slice());
+/// # Ok::<_, Box<dyn core::error::Error>>(())
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct StringRefs<T>(T);
+
+impl<T> StringRefs<T> {
+    pub const fn new(val: T) -> Self {
+        Self(val)
+    }
+
+    pub const fn value(&self) -> &T {
+        &self.0
+    }
+
+    pub fn value_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+
+    pub fn into_value(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for StringRefs<T> {
+    fn from(val: T) -> Self {
+        Self::new(val)
+    }
+}
+
+impl<T> Deref for StringRefs<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for StringRefs<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<C, T: Encode<C>> Encode<C> for StringRefs<T> {
+    fn encode<W: Write>(&self, e: &mut Encoder<W>, ctx: &mut C) -> Result<(), encode::Error<W::Error>> {
+        e.tag(IanaTag::StringRefNamespace)?;
+        e.begin_string_refs();
+        let r = self.0.encode(e, ctx);
+        e.end_string_refs();
+        r
+    }
+}
+
+impl<'b, C, T: Decode<'b, C>> Decode<'b, C> for StringRefs<T> {
+    fn decode(d: &mut Decoder<'b>, ctx: &mut C) -> Result<Self, decode::Error> {
+        let p = d.position();
+        let t = d.tag()?;
+        if t != IanaTag::StringRefNamespace.tag() {
+            return Err(decode::Error::tag_mismatch(t).with_message("expected tag 256").at(p))
+        }
+        d.begin_string_refs();
+        let r = T::decode(d, ctx);
+        d.end_string_refs();
+        r.map(StringRefs)
+    }
+}
+
+/// The min. length of a string to be added to a string reference namespace
+/// which already contains `n` strings.
+///
+/// Shorter strings would not benefit from being referenced.
+pub(crate) fn min_string_ref_len(n: u64) -> usize {
+    match n {
+        0 ..= 23                   => 3,
+        24 ..= 0xff                => 4,
+        0x100 ..= 0xffff           => 5,
+        0x1_0000 ..= 0xffff_ffff   => 7,
+        _                          => 11
+    }
+}
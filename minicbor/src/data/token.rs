@@ -215,9 +215,9 @@ impl<'b, C> CborLen<C> for Token<'b> {
             Token::I32(val)    => val.cbor_len(ctx),
             Token::I64(val)    => val.cbor_len(ctx),
             Token::Int(val)    => val.cbor_len(ctx),
-            Token::F16(val)    => val.cbor_len(ctx),
-            Token::F32(val)    => val.cbor_len(ctx),
-            Token::F64(val)    => val.cbor_len(ctx),
+            Token::F16(_)      => 3,
+            Token::F32(_)      => 5,
+            Token::F64(_)      => 9,
             Token::Bytes(val)  => val.cbor_len(ctx),
             Token::String(val) => val.cbor_len(ctx),
             Token::Array(val)  => val.cbor_len(ctx),
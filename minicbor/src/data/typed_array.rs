@@ -0,0 +1,322 @@
+use core::fmt;
+use core::marker::PhantomData;
+
+use crate::data::{IanaTag, Tag};
+use crate::decode::{self, Decode, Decoder};
+use crate::encode::{self, CborLen, Encode, Encoder, Write};
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// A typed array of numbers (RFC 8746).
+///
+/// A typed array is encoded as a tag which identifies element type and
+/// byte order (e.g. [`IanaTag::TypedArrayU32L`] for little-endian `u32`s)
+/// followed by a byte string containing the elements. Compared to regular
+/// CBOR arrays, no per-element header is needed and the data can often be
+/// used in place.
+///
+/// A `TypedArray` is created from a slice with [`TypedArray::new`] using
+/// native byte order. Decoding a `TypedArray` borrows the byte string from
+/// the input after checking tag and length. Its elements can be accessed
+/// with [`TypedArray::iter`], [`TypedArray::get`] or, without copying, with
+/// [`TypedArray::as_slice`] if the byte order is native and the bytes are
+/// suitably aligned.
+///
+/// Supported element types are `u8`, `u16`, `u32`, `u64`, `i8`, `i16`,
+/// `i32`, `i64`, `f32`, `f64` and, with feature `"half"`, `half::f16`
+/// (cf. [`TypedArrayElement`]). Arrays of `u8` also decode from tag 68
+/// ([`IanaTag::TypedArrayU8Clamped`]) and are encoded with this tag again
+/// (cf. [`TypedArray::is_clamped`]).
+///
+/// # Example
+///
+/// ```
+/// use minicbor::data::{Endianness, TypedArray};
+///
+/// let xs = [1u16, 2, 0x300];
+///
+/// let input = minicbor::to_vec(TypedArray::new(&xs))?;
+/// let a: TypedArray<u16> = minicbor::decode(&input)?;
+/// assert_eq!(Endianness::NATIVE, a.endianness());
+/// assert!(a.iter().eq(xs));
+///
+/// // Big-endian `u16`s (tag 65)
+/// let input = [0xd8, 0x41, 0x46, 0x00, 0x01, 0x00, 0x02, 0x03, 0x00];
+/// let a: TypedArray<u16> = minicbor::decode(&input)?;
+/// assert_eq!(Endianness::Big, a.endianness());
+/// assert_eq!(vec![1, 2, 0x300], a.to_vec());
+/// # Ok::<_, Box<dyn core::error::Error>>(())
+/// ```
+#[derive(Clone, Copy)]
+pub struct TypedArray<'b, T> {
+    bytes: &'b [u8],
+    endianness: Endianness,
+    clamped: bool,
+    _marker: PhantomData<&'b [T]>
+}
+
+impl<'b, T: TypedArrayElement> TypedArray<'b, T> {
+    /// Create a typed array from a slice, using native byte order.
+    pub fn new(xs: &'b [T]) -> Self {
+        // All element types are plain numbers without padding.
+        let bytes = unsafe {
+            core::slice::from_raw_parts(xs.as_ptr() as *const u8, core::mem::size_of_val(xs))
+        };
+        TypedArray { bytes, endianness: Endianness::NATIVE, clamped: false, _marker: PhantomData }
+    }
+
+    /// Create a typed array from bytes in the given byte order.
+    ///
+    /// Returns `None` if the number of bytes is not a multiple of the element
+    /// size.
+    pub fn from_bytes(bytes: &'b [u8], endianness: Endianness) -> Option<Self> {
+        if !bytes.len().is_multiple_of(core::mem::size_of::<T>()) {
+            return None
+        }
+        Some(TypedArray { bytes, endianness, clamped: false, _marker: PhantomData })
+    }
+
+    /// The byte order of the elements.
+    pub fn endianness(&self) -> Endianness {
+        self.endianness
+    }
+
+    /// The number of elements.
+    pub fn len(&self) -> usize {
+        self.bytes.len() / core::mem::size_of::<T>()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Get the element at the given index.
+    pub fn get(&self, i: usize) -> Option<T> {
+        let n = core::mem::size_of::<T>();
+        let b = self.bytes.get(i.checked_mul(n)? ..)?.get(.. n)?;
+        Some(T::from_bytes(b, self.endianness))
+    }
+
+    /// Iterate over all elements.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = T> + 'b {
+        let e = self.endianness;
+        self.bytes.chunks_exact(core::mem::size_of::<T>()).map(move |b| T::from_bytes(b, e))
+    }
+
+    /// Get the elements as a slice without copying.
+    ///
+    /// Returns `None` if the byte order is not native or if the bytes are
+    /// not aligned for `T`.
+    pub fn as_slice(&self) -> Option<&'b [T]> {
+        if self.endianness != Endianness::NATIVE {
+            return None
+        }
+        // Every bit pattern is a valid element value.
+        match unsafe { self.bytes.align_to::<T>() } {
+            ([], xs, []) => Some(xs),
+            _            => None
+        }
+    }
+
+    /// Copy the elements into a vector.
+    ///
+    /// *Requires feature* `"alloc"`.
+    #[cfg(feature = "alloc")]
+    pub fn to_vec(&self) -> Vec<T> {
+        self.as_slice().map(|xs| xs.to_vec()).unwrap_or_else(|| self.iter().collect())
+    }
+
+    /// Get the encoded bytes of the elements.
+    pub fn as_bytes(&self) -> &'b [u8] {
+        self.bytes
+    }
+
+    /// The tag to encode this array with.
+    fn tag(&self) -> IanaTag {
+        if self.clamped {
+            IanaTag::TypedArrayU8Clamped
+        } else {
+            T::tag(self.endianness)
+        }
+    }
+}
+
+impl TypedArray<'_, u8> {
+    /// Set whether this array is clamped.
+    ///
+    /// A clamped array is encoded with tag 68 ([`IanaTag::TypedArrayU8Clamped`])
+    /// instead of tag 64 ([`IanaTag::TypedArrayU8`]).
+    pub fn with_clamped(mut self, val: bool) -> Self {
+        self.clamped = val;
+        self
+    }
+
+    /// Is this array clamped?
+    ///
+    /// Arrays decoded from tag 68 ([`IanaTag::TypedArrayU8Clamped`]) are.
+    pub fn is_clamped(&self) -> bool {
+        self.clamped
+    }
+}
+
+impl<'b, T: TypedArrayElement> From<&'b [T]> for TypedArray<'b, T> {
+    fn from(xs: &'b [T]) -> Self {
+        TypedArray::new(xs)
+    }
+}
+
+impl<T: TypedArrayElement + fmt::Debug> fmt::Debug for TypedArray<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: TypedArrayElement + PartialEq> PartialEq for TypedArray<'_, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+impl<'b, C, T: TypedArrayElement> Decode<'b, C> for TypedArray<'b, T> {
+    fn decode(d: &mut Decoder<'b>, _: &mut C) -> Result<Self, decode::Error> {
+        let p = d.position();
+        let t = d.tag()?;
+        let Some(endianness) = T::endianness(t) else {
+            return Err(decode::Error::tag_mismatch(t).with_message("unexpected typed array tag").at(p))
+        };
+        let p = d.position();
+        let bytes = d.bytes()?;
+        let Some(mut a) = TypedArray::from_bytes(bytes, endianness) else {
+            return Err(decode::Error::message("typed array length is not a multiple of the element size").at(p))
+        };
+        a.clamped = t == IanaTag::TypedArrayU8Clamped.tag();
+        Ok(a)
+    }
+}
+
+impl<C, T: TypedArrayElement> Encode<C> for TypedArray<'_, T> {
+    fn encode<W: Write>(&self, e: &mut Encoder<W>, _: &mut C) -> Result<(), encode::Error<W::Error>> {
+        e.tag(self.tag())?.bytes(self.bytes)?.ok()
+    }
+}
+
+impl<C, T: TypedArrayElement> CborLen<C> for TypedArray<'_, T> {
+    fn cbor_len(&self, ctx: &mut C) -> usize {
+        let n = self.bytes.len();
+        self.tag().cbor_len(ctx) + n.cbor_len(ctx) + n
+    }
+}
+
+/// Byte order of typed array elements.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Endianness {
+    Big,
+    Little
+}
+
+impl Endianness {
+    /// The byte order of the target platform.
+    #[cfg(target_endian = "big")]
+    pub const NATIVE: Self = Endianness::Big;
+
+    /// The byte order of the target platform.
+    #[cfg(target_endian = "little")]
+    pub const NATIVE: Self = Endianness::Little;
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// Element types of [`TypedArray`].
+///
+/// This trait is sealed and can not be implemented outside of this crate.
+pub trait TypedArrayElement: Copy + sealed::Sealed {
+    /// The tag of a typed array with the given byte order.
+    fn tag(e: Endianness) -> IanaTag;
+
+    /// The byte order of a typed array with the given tag.
+    ///
+    /// Returns `None` if the tag does not denote a typed array of this type.
+    fn endianness(t: Tag) -> Option<Endianness>;
+
+    /// Read a value from bytes in the given byte order.
+    ///
+    /// The slice length must be equal to the size of `Self`.
+    fn from_bytes(b: &[u8], e: Endianness) -> Self;
+}
+
+macro_rules! byte_element {
+    ($t:ty, $($tag:ident),+) => {
+        impl sealed::Sealed for $t {}
+
+        impl TypedArrayElement for $t {
+            fn tag(_: Endianness) -> IanaTag {
+                byte_element!(@first $($tag),+)
+            }
+
+            fn endianness(t: Tag) -> Option<Endianness> {
+                if $(t == IanaTag::$tag.tag())||+ {
+                    Some(Endianness::NATIVE)
+                } else {
+                    None
+                }
+            }
+
+            fn from_bytes(b: &[u8], _: Endianness) -> Self {
+                b[0] as $t
+            }
+        }
+    };
+    (@first $tag:ident $(, $rest:ident)*) => { IanaTag::$tag }
+}
+
+macro_rules! element {
+    ($t:ty, $be:ident, $le:ident) => {
+        impl sealed::Sealed for $t {}
+
+        impl TypedArrayElement for $t {
+            fn tag(e: Endianness) -> IanaTag {
+                match e {
+                    Endianness::Big    => IanaTag::$be,
+                    Endianness::Little => IanaTag::$le
+                }
+            }
+
+            fn endianness(t: Tag) -> Option<Endianness> {
+                if t == IanaTag::$be.tag() {
+                    Some(Endianness::Big)
+                } else if t == IanaTag::$le.tag() {
+                    Some(Endianness::Little)
+                } else {
+                    None
+                }
+            }
+
+            fn from_bytes(b: &[u8], e: Endianness) -> Self {
+                let mut a = [0; core::mem::size_of::<$t>()];
+                a.copy_from_slice(b);
+                match e {
+                    Endianness::Big    => <$t>::from_be_bytes(a),
+                    Endianness::Little => <$t>::from_le_bytes(a)
+                }
+            }
+        }
+    }
+}
+
+byte_element!(u8, TypedArrayU8, TypedArrayU8Clamped);
+byte_element!(i8, TypedArrayI8);
+
+element!(u16, TypedArrayU16B, TypedArrayU16L);
+element!(u32, TypedArrayU32B, TypedArrayU32L);
+element!(u64, TypedArrayU64B, TypedArrayU64L);
+element!(i16, TypedArrayI16B, TypedArrayI16L);
+element!(i32, TypedArrayI32B, TypedArrayI32L);
+element!(i64, TypedArrayI64B, TypedArrayI64L);
+element!(f32, TypedArrayF32B, TypedArrayF32L);
+element!(f64, TypedArrayF64B, TypedArrayF64L);
+
+#[cfg(feature = "half")]
+element!(half::f16, TypedArrayF16B, TypedArrayF16L);
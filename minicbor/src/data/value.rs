@@ -0,0 +1,901 @@
+use alloc::{boxed::Box, string::String, vec::Vec};
+use core::ops::Index;
+
+use super::{Int, Tag, Type};
+use crate::decode::{self, ArrayIterWithCtx, Decode, Decoder, MapIterWithCtx};
+use crate::encode::{self, CborLen, Encode, Encoder, Write};
+
+/// An owned, dynamically typed CBOR data item.
+///
+/// A `Value` can represent every well-formed CBOR data item and is useful
+/// when the structure of the input is not known in advance. Decoding a
+/// `Value` and encoding it again produces an equivalent data item, i.e. one
+/// that decodes to the same `Value`, but the following details of the
+/// original encoding are not retained:
+///
+/// - Indefinite byte and text strings are concatenated into a single one.
+/// - Indefinite arrays and maps are turned into definite ones.
+/// - Integers and lengths are always encoded in their shortest form.
+///
+/// Floating point values keep their width, so e.g. an `f32` is encoded
+/// as an `f32` again. Map entries are kept in the order in which they were
+/// decoded. Since map keys can be arbitrary values, lookup by key (see
+/// [`Value::get`]) requires a linear search.
+///
+/// Indexing a `Value` with a `usize` or `&str` returns the corresponding
+/// array element or map value or [`Value::Null`] if the value is not an
+/// array or map or if no such element exists. Use [`Value::get`] or one of
+/// the `as_*` methods for checked access.
+///
+/// # Example
+///
+/// ```
+/// use minicbor::data::Value;
+///
+/// let input = minicbor::to_vec((1, "two", [3.0]))?;
+/// let value: Value = minicbor::decode(&input)?;
+///
+/// assert_eq!(Some(1), value[0].as_u64());
+/// assert_eq!(Some("two"), value[1].as_str());
+/// assert_eq!(Some(3.0), value[2][0].as_f64());
+/// assert!(value[3].is_null());
+///
+/// # Ok::<_, Box<dyn core::error::Error>>(())
+/// ```
+///
+/// *Requires feature* `"alloc"`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Undefined,
+    Bool(bool),
+    /// A simple value.
+    ///
+    /// Values 20 to 23 denote `false`, `true`, `null` and `undefined` and
+    /// 24 to 31 are not well-formed, hence encoding any of them fails.
+    Simple(u8),
+    Int(Int),
+    /// A half float (`f16`).
+    ///
+    /// Decoding and encoding this variant requires feature `"half"`.
+    F16(f32),
+    F32(f32),
+    F64(f64),
+    Bytes(Vec<u8>),
+    String(String),
+    Array(Vec<Value>),
+    Map(Vec<(Value, Value)>),
+    Tag(Tag, Box<Value>)
+}
+
+/// The value returned when indexing fails.
+static NULL: Value = Value::Null;
+
+impl Value {
+    pub fn is_null(&self) -> bool {
+        matches!(self, Value::Null)
+    }
+
+    pub fn is_undefined(&self) -> bool {
+        matches!(self, Value::Undefined)
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        if let Value::Bool(b) = self { Some(*b) } else { None }
+    }
+
+    pub fn as_simple(&self) -> Option<u8> {
+        if let Value::Simple(n) = self { Some(*n) } else { None }
+    }
+
+    pub fn as_int(&self) -> Option<Int> {
+        if let Value::Int(n) = self { Some(*n) } else { None }
+    }
+
+    /// Get this value as a `u64` if it is an integer within range.
+    pub fn as_u64(&self) -> Option<u64> {
+        self.as_int().and_then(|n| u64::try_from(n).ok())
+    }
+
+    /// Get this value as an `i64` if it is an integer within range.
+    pub fn as_i64(&self) -> Option<i64> {
+        self.as_int().and_then(|n| i64::try_from(n).ok())
+    }
+
+    /// Get this value as an `f64` if it is a floating point value of any width.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::F16(x) => Some(f64::from(*x)),
+            Value::F32(x) => Some(f64::from(*x)),
+            Value::F64(x) => Some(*x),
+            _             => None
+        }
+    }
+
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        if let Value::Bytes(b) = self { Some(b) } else { None }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        if let Value::String(s) = self { Some(s) } else { None }
+    }
+
+    pub fn as_array(&self) -> Option<&[Value]> {
+        if let Value::Array(a) = self { Some(a) } else { None }
+    }
+
+    pub fn as_map(&self) -> Option<&[(Value, Value)]> {
+        if let Value::Map(m) = self { Some(m) } else { None }
+    }
+
+    /// Get the tag and the tagged value if this value is tagged.
+    pub fn as_tag(&self) -> Option<(Tag, &Value)> {
+        if let Value::Tag(t, v) = self { Some((*t, v)) } else { None }
+    }
+
+    /// Look up the value of the first map entry whose key equals `key`.
+    ///
+    /// Returns `None` if this value is not a map or no such entry exists.
+    ///
+    /// ```
+    /// use minicbor::data::Value;
+    ///
+    /// let v = Value::Map(vec![(Value::from(1), Value::from("one")), (Value::from("x"), Value::from(2))]);
+    ///
+    /// assert_eq!(Some(&Value::from("one")), v.get(&1));
+    /// assert_eq!(Some(&Value::from(2)), v.get("x"));
+    /// assert_eq!(None, v.get(&2));
+    /// ```
+    pub fn get<Q>(&self, key: &Q) -> Option<&Value>
+    where
+        Q: ?Sized,
+        Value: PartialEq<Q>
+    {
+        self.as_map()?.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+    }
+
+    /// Get the array element at the given index.
+    ///
+    /// Returns `None` if this value is not an array or the index is out of bounds.
+    pub fn get_index(&self, i: usize) -> Option<&Value> {
+        self.as_array()?.get(i)
+    }
+}
+
+impl Index<usize> for Value {
+    type Output = Value;
+
+    fn index(&self, i: usize) -> &Self::Output {
+        self.get_index(i).unwrap_or(&NULL)
+    }
+}
+
+impl Index<&str> for Value {
+    type Output = Value;
+
+    fn index(&self, k: &str) -> &Self::Output {
+        self.get(k).unwrap_or(&NULL)
+    }
+}
+
+impl PartialEq<str> for Value {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == Some(other)
+    }
+}
+
+impl PartialEq<&str> for Value {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == Some(*other)
+    }
+}
+
+impl PartialEq<[u8]> for Value {
+    fn eq(&self, other: &[u8]) -> bool {
+        self.as_bytes() == Some(other)
+    }
+}
+
+impl PartialEq<bool> for Value {
+    fn eq(&self, other: &bool) -> bool {
+        self.as_bool() == Some(*other)
+    }
+}
+
+impl PartialEq<Int> for Value {
+    fn eq(&self, other: &Int) -> bool {
+        self.as_int() == Some(*other)
+    }
+}
+
+macro_rules! value_int {
+    ($v:ty; $($t:ty)*) => {
+        $(
+            impl From<$t> for $v {
+                fn from(n: $t) -> Self {
+                    Self::Int(Int::from(n))
+                }
+            }
+
+            impl PartialEq<$t> for $v {
+                fn eq(&self, other: &$t) -> bool {
+                    self.as_int() == Some(Int::from(*other))
+                }
+            }
+        )*
+    }
+}
+
+value_int!(Value; u8 u16 u32 u64 i8 i16 i32 i64);
+
+impl From<Int> for Value {
+    fn from(n: Int) -> Self {
+        Value::Int(n)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(b: bool) -> Self {
+        Value::Bool(b)
+    }
+}
+
+impl From<f32> for Value {
+    fn from(x: f32) -> Self {
+        Value::F32(x)
+    }
+}
+
+impl From<f64> for Value {
+    fn from(x: f64) -> Self {
+        Value::F64(x)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(s: &str) -> Self {
+        Value::String(s.into())
+    }
+}
+
+impl From<String> for Value {
+    fn from(s: String) -> Self {
+        Value::String(s)
+    }
+}
+
+impl From<Vec<Value>> for Value {
+    fn from(a: Vec<Value>) -> Self {
+        Value::Array(a)
+    }
+}
+
+impl<T: Into<Value>> From<Option<T>> for Value {
+    fn from(x: Option<T>) -> Self {
+        x.map(Into::into).unwrap_or(Value::Null)
+    }
+}
+
+impl<'b, C> Decode<'b, C> for Value {
+    fn decode(d: &mut Decoder<'b>, ctx: &mut C) -> Result<Self, decode::Error> {
+        let p = d.position();
+        match d.datatype()? {
+            Type::Bool      => d.bool().map(Value::Bool),
+            Type::Null      => d.null().map(|_| Value::Null),
+            Type::Undefined => d.undefined().map(|_| Value::Undefined),
+            Type::Simple    => d.simple().map(Value::Simple),
+            Type::U8
+            | Type::U16
+            | Type::U32
+            | Type::U64
+            | Type::I8
+            | Type::I16
+            | Type::I32
+            | Type::I64
+            | Type::Int     => d.int().map(Value::Int),
+            #[cfg(feature = "half")]
+            Type::F16       => d.f16().map(Value::F16),
+            #[cfg(not(feature = "half"))]
+            Type::F16       => Err(decode::Error::type_mismatch(Type::F16)
+                .at(p)
+                .with_message("decoding f16 values requires feature \"half\"")),
+            Type::F32       => d.f32().map(Value::F32),
+            Type::F64       => d.f64().map(Value::F64),
+            Type::Bytes | Type::BytesIndef => {
+                let mut v = Vec::new();
+                for b in d.bytes_iter()? {
+                    v.extend_from_slice(b?)
+                }
+                Ok(Value::Bytes(v))
+            }
+            Type::String | Type::StringIndef => {
+                let mut s = String::new();
+                for x in d.str_iter()? {
+                    s.push_str(x?)
+                }
+                Ok(Value::String(s))
+            }
+            Type::Array | Type::ArrayIndef => {
+                d.entered(|d| {
+                    let iter: ArrayIterWithCtx<C, Value> = d.array_iter_with(ctx)?;
+                    iter.collect::<Result<_, _>>().map(Value::Array)
+                })
+            }
+            Type::Map | Type::MapIndef => {
+                d.entered(|d| {
+                    let iter: MapIterWithCtx<C, Value, Value> = d.map_iter_with(ctx)?;
+                    iter.collect::<Result<_, _>>().map(Value::Map)
+                })
+            }
+            Type::Tag => {
+                let t = d.tag()?;
+                d.enter().map_err(|e| e.at(p))?;
+                let v = Value::decode(d, ctx);
+                d.leave();
+                Ok(Value::Tag(t, Box::new(v?)))
+            }
+            Type::Break => Err(decode::Error::type_mismatch(Type::Break)
+                .at(p)
+                .with_message("unexpected break")),
+            t @ Type::Unknown(_) => Err(decode::Error::type_mismatch(t)
+                .at(p)
+                .with_message("unknown cbor type"))
+        }
+    }
+}
+
+impl<C> Encode<C> for Value {
+    fn encode<W: Write>(&self, e: &mut Encoder<W>, ctx: &mut C) -> Result<(), encode::Error<W::Error>> {
+        match self {
+            Value::Null       => e.null()?.ok(),
+            Value::Undefined  => e.undefined()?.ok(),
+            Value::Bool(b)    => e.bool(*b)?.ok(),
+            Value::Simple(0x14 ..= 0x1f) => Err(encode::Error::message("simple values 20 to 31 can not be encoded as `Simple`")),
+            Value::Simple(n)  => e.simple(*n)?.ok(),
+            Value::Int(n)     => e.int(*n)?.ok(),
+            #[cfg(feature = "half")]
+            Value::F16(x)     => e.f16(*x)?.ok(),
+            #[cfg(not(feature = "half"))]
+            Value::F16(_)     => Err(encode::Error::message("encoding f16 values requires feature \"half\"")),
+            Value::F32(x)     => e.f32(*x)?.ok(),
+            Value::F64(x)     => e.f64(*x)?.ok(),
+            Value::Bytes(b)   => e.bytes(b)?.ok(),
+            Value::String(s)  => e.str(s)?.ok(),
+            Value::Array(a)   => {
+                e.array(a.len() as u64)?;
+                for x in a {
+                    x.encode(e, ctx)?
+                }
+                Ok(())
+            }
+            Value::Map(m) => {
+                e.map(m.len() as u64)?;
+                for (k, v) in m {
+                    k.encode(e, ctx)?;
+                    v.encode(e, ctx)?
+                }
+                Ok(())
+            }
+            Value::Tag(t, v) => {
+                e.tag(*t)?;
+                v.encode(e, ctx)
+            }
+        }
+    }
+}
+
+impl<C> CborLen<C> for Value {
+    fn cbor_len(&self, ctx: &mut C) -> usize {
+        match self {
+            Value::Null       => 1,
+            Value::Undefined  => 1,
+            Value::Bool(_)    => 1,
+            Value::Simple(n)  => if *n < 0x18 { 1 } else { 2 },
+            Value::Int(n)     => n.cbor_len(ctx),
+            Value::F16(_)     => 3,
+            Value::F32(_)     => 5,
+            Value::F64(_)     => 9,
+            Value::Bytes(b)   => b.len().cbor_len(ctx) + b.len(),
+            Value::String(s)  => s.cbor_len(ctx),
+            Value::Array(a)   => a.cbor_len(ctx),
+            Value::Map(m)     => {
+                m.len().cbor_len(ctx) + m.iter()
+                    .map(|(k, v)| k.cbor_len(ctx) + v.cbor_len(ctx))
+                    .sum::<usize>()
+            }
+            Value::Tag(t, v) => t.cbor_len(ctx) + v.cbor_len(ctx)
+        }
+    }
+}
+
+/// A dynamically typed CBOR data item borrowing from the decoding input.
+///
+/// In contrast to [`Value`], text and byte strings are not copied but refer
+/// to the input bytes, just like [`Decoder::str`] and [`Decoder::bytes`] do.
+/// Indefinite strings are represented as a list of borrowed chunks.
+///
+/// `ValueRef` retains whether strings, arrays and maps have been encoded
+/// with definite or indefinite lengths. Encoding a decoded `ValueRef` thus
+/// reproduces the original input byte for byte, provided all integers and
+/// lengths in the input use their shortest form (which is always the case
+/// for data produced by [`Encoder`]).
+///
+/// Indexing works as with [`Value`], i.e. indexing with a `usize` or `&str`
+/// returns the array element or map value or [`ValueRef::Null`] if absent.
+///
+/// # Example
+///
+/// ```
+/// use minicbor::data::ValueRef;
+///
+/// let input = [0xbf, 0x61, 0x61, 0x9f, 0x01, 0xff, 0xff]; // {_ "a": [_ 1]}
+/// let value: ValueRef = minicbor::decode(&input)?;
+///
+/// assert_eq!(Some(1), value["a"][0].as_u64());
+/// assert_eq!(&input[..], minicbor::to_vec(&value)?);
+///
+/// # Ok::<_, Box<dyn core::error::Error>>(())
+/// ```
+///
+/// *Requires feature* `"alloc"`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValueRef<'b> {
+    Null,
+    Undefined,
+    Bool(bool),
+    /// A simple value.
+    ///
+    /// Values 20 to 23 denote `false`, `true`, `null` and `undefined` and
+    /// 24 to 31 are not well-formed, hence encoding any of them fails.
+    Simple(u8),
+    Int(Int),
+    /// A half float (`f16`).
+    ///
+    /// Decoding and encoding this variant requires feature `"half"`.
+    F16(f32),
+    F32(f32),
+    F64(f64),
+    Bytes(&'b [u8]),
+    /// An indefinite byte string, given as the list of its chunks.
+    BytesIndef(Vec<&'b [u8]>),
+    String(&'b str),
+    /// An indefinite text string, given as the list of its chunks.
+    StringIndef(Vec<&'b str>),
+    Array(Vec<ValueRef<'b>>),
+    ArrayIndef(Vec<ValueRef<'b>>),
+    Map(Vec<(ValueRef<'b>, ValueRef<'b>)>),
+    MapIndef(Vec<(ValueRef<'b>, ValueRef<'b>)>),
+    Tag(Tag, Box<ValueRef<'b>>)
+}
+
+/// The value returned when indexing fails.
+static NULL_REF: ValueRef<'static> = ValueRef::Null;
+
+impl<'b> ValueRef<'b> {
+    pub fn is_null(&self) -> bool {
+        matches!(self, ValueRef::Null)
+    }
+
+    pub fn is_undefined(&self) -> bool {
+        matches!(self, ValueRef::Undefined)
+    }
+
+    /// Is this an indefinite string, array or map?
+    pub fn is_indefinite(&self) -> bool {
+        matches! { self,
+            ValueRef::BytesIndef(_)
+            | ValueRef::StringIndef(_)
+            | ValueRef::ArrayIndef(_)
+            | ValueRef::MapIndef(_)
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        if let ValueRef::Bool(b) = self { Some(*b) } else { None }
+    }
+
+    pub fn as_simple(&self) -> Option<u8> {
+        if let ValueRef::Simple(n) = self { Some(*n) } else { None }
+    }
+
+    pub fn as_int(&self) -> Option<Int> {
+        if let ValueRef::Int(n) = self { Some(*n) } else { None }
+    }
+
+    /// Get this value as a `u64` if it is an integer within range.
+    pub fn as_u64(&self) -> Option<u64> {
+        self.as_int().and_then(|n| u64::try_from(n).ok())
+    }
+
+    /// Get this value as an `i64` if it is an integer within range.
+    pub fn as_i64(&self) -> Option<i64> {
+        self.as_int().and_then(|n| i64::try_from(n).ok())
+    }
+
+    /// Get this value as an `f64` if it is a floating point value of any width.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            ValueRef::F16(x) => Some(f64::from(*x)),
+            ValueRef::F32(x) => Some(f64::from(*x)),
+            ValueRef::F64(x) => Some(*x),
+            _                => None
+        }
+    }
+
+    /// Get the byte slice of a definite byte string.
+    pub fn as_bytes(&self) -> Option<&'b [u8]> {
+        if let ValueRef::Bytes(b) = self { Some(b) } else { None }
+    }
+
+    /// Get the string slice of a definite text string.
+    pub fn as_str(&self) -> Option<&'b str> {
+        if let ValueRef::String(s) = self { Some(s) } else { None }
+    }
+
+    /// Get the elements of a definite or indefinite array.
+    pub fn as_array(&self) -> Option<&[ValueRef<'b>]> {
+        match self {
+            ValueRef::Array(a) | ValueRef::ArrayIndef(a) => Some(a),
+            _ => None
+        }
+    }
+
+    /// Get the entries of a definite or indefinite map.
+    pub fn as_map(&self) -> Option<&[(ValueRef<'b>, ValueRef<'b>)]> {
+        match self {
+            ValueRef::Map(m) | ValueRef::MapIndef(m) => Some(m),
+            _ => None
+        }
+    }
+
+    /// Get the tag and the tagged value if this value is tagged.
+    pub fn as_tag(&self) -> Option<(Tag, &ValueRef<'b>)> {
+        if let ValueRef::Tag(t, v) = self { Some((*t, v)) } else { None }
+    }
+
+    /// Look up the value of the first map entry whose key equals `key`.
+    ///
+    /// Returns `None` if this value is not a map or no such entry exists.
+    pub fn get<Q>(&self, key: &Q) -> Option<&ValueRef<'b>>
+    where
+        Q: ?Sized,
+        ValueRef<'b>: PartialEq<Q>
+    {
+        self.as_map()?.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+    }
+
+    /// Get the array element at the given index.
+    ///
+    /// Returns `None` if this value is not an array or the index is out of bounds.
+    pub fn get_index(&self, i: usize) -> Option<&ValueRef<'b>> {
+        self.as_array()?.get(i)
+    }
+
+    /// Create an owned [`Value`] from this value.
+    ///
+    /// The chunks of indefinite strings are concatenated and indefinite
+    /// arrays and maps become definite ones.
+    pub fn to_value(&self) -> Value {
+        match self {
+            ValueRef::Null          => Value::Null,
+            ValueRef::Undefined     => Value::Undefined,
+            ValueRef::Bool(b)       => Value::Bool(*b),
+            ValueRef::Simple(n)     => Value::Simple(*n),
+            ValueRef::Int(n)        => Value::Int(*n),
+            ValueRef::F16(x)        => Value::F16(*x),
+            ValueRef::F32(x)        => Value::F32(*x),
+            ValueRef::F64(x)        => Value::F64(*x),
+            ValueRef::Bytes(b)      => Value::Bytes(b.to_vec()),
+            ValueRef::BytesIndef(b) => Value::Bytes(b.concat()),
+            ValueRef::String(s)     => Value::String(String::from(*s)),
+            ValueRef::StringIndef(s) => Value::String(s.concat()),
+            ValueRef::Array(a) | ValueRef::ArrayIndef(a) => {
+                Value::Array(a.iter().map(ValueRef::to_value).collect())
+            }
+            ValueRef::Map(m) | ValueRef::MapIndef(m) => {
+                Value::Map(m.iter().map(|(k, v)| (k.to_value(), v.to_value())).collect())
+            }
+            ValueRef::Tag(t, v) => Value::Tag(*t, Box::new(v.to_value()))
+        }
+    }
+}
+
+impl<'b> Index<usize> for ValueRef<'b> {
+    type Output = ValueRef<'b>;
+
+    fn index(&self, i: usize) -> &Self::Output {
+        self.get_index(i).unwrap_or(&NULL_REF)
+    }
+}
+
+impl<'b> Index<&str> for ValueRef<'b> {
+    type Output = ValueRef<'b>;
+
+    fn index(&self, k: &str) -> &Self::Output {
+        self.get(k).unwrap_or(&NULL_REF)
+    }
+}
+
+/// Compare the concatenation of `chunks` with `other`.
+fn chunks_eq<'a, I>(chunks: I, mut other: &[u8]) -> bool
+where
+    I: IntoIterator<Item = &'a [u8]>
+{
+    for c in chunks {
+        if let Some(rest) = other.strip_prefix(c) {
+            other = rest
+        } else {
+            return false
+        }
+    }
+    other.is_empty()
+}
+
+impl PartialEq<str> for ValueRef<'_> {
+    fn eq(&self, other: &str) -> bool {
+        match self {
+            ValueRef::String(s)      => *s == other,
+            ValueRef::StringIndef(s) => chunks_eq(s.iter().map(|c| c.as_bytes()), other.as_bytes()),
+            _                        => false
+        }
+    }
+}
+
+impl PartialEq<&str> for ValueRef<'_> {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
+
+impl PartialEq<[u8]> for ValueRef<'_> {
+    fn eq(&self, other: &[u8]) -> bool {
+        match self {
+            ValueRef::Bytes(b)      => *b == other,
+            ValueRef::BytesIndef(b) => chunks_eq(b.iter().copied(), other),
+            _                       => false
+        }
+    }
+}
+
+impl PartialEq<bool> for ValueRef<'_> {
+    fn eq(&self, other: &bool) -> bool {
+        self.as_bool() == Some(*other)
+    }
+}
+
+impl PartialEq<Int> for ValueRef<'_> {
+    fn eq(&self, other: &Int) -> bool {
+        self.as_int() == Some(*other)
+    }
+}
+
+value_int!(ValueRef<'_>; u8 u16 u32 u64 i8 i16 i32 i64);
+
+impl From<Int> for ValueRef<'_> {
+    fn from(n: Int) -> Self {
+        ValueRef::Int(n)
+    }
+}
+
+impl From<bool> for ValueRef<'_> {
+    fn from(b: bool) -> Self {
+        ValueRef::Bool(b)
+    }
+}
+
+impl From<f32> for ValueRef<'_> {
+    fn from(x: f32) -> Self {
+        ValueRef::F32(x)
+    }
+}
+
+impl From<f64> for ValueRef<'_> {
+    fn from(x: f64) -> Self {
+        ValueRef::F64(x)
+    }
+}
+
+impl<'b> From<&'b str> for ValueRef<'b> {
+    fn from(s: &'b str) -> Self {
+        ValueRef::String(s)
+    }
+}
+
+impl<'b> From<Vec<ValueRef<'b>>> for ValueRef<'b> {
+    fn from(a: Vec<ValueRef<'b>>) -> Self {
+        ValueRef::Array(a)
+    }
+}
+
+impl From<ValueRef<'_>> for Value {
+    fn from(v: ValueRef<'_>) -> Self {
+        v.to_value()
+    }
+}
+
+impl From<&ValueRef<'_>> for Value {
+    fn from(v: &ValueRef<'_>) -> Self {
+        v.to_value()
+    }
+}
+
+impl<'b, C> Decode<'b, C> for ValueRef<'b> {
+    fn decode(d: &mut Decoder<'b>, ctx: &mut C) -> Result<Self, decode::Error> {
+        let p = d.position();
+        match d.datatype()? {
+            Type::Bool      => d.bool().map(ValueRef::Bool),
+            Type::Null      => d.null().map(|_| ValueRef::Null),
+            Type::Undefined => d.undefined().map(|_| ValueRef::Undefined),
+            Type::Simple    => d.simple().map(ValueRef::Simple),
+            Type::U8
+            | Type::U16
+            | Type::U32
+            | Type::U64
+            | Type::I8
+            | Type::I16
+            | Type::I32
+            | Type::I64
+            | Type::Int     => d.int().map(ValueRef::Int),
+            #[cfg(feature = "half")]
+            Type::F16       => d.f16().map(ValueRef::F16),
+            #[cfg(not(feature = "half"))]
+            Type::F16       => Err(decode::Error::type_mismatch(Type::F16)
+                .at(p)
+                .with_message("decoding f16 values requires feature \"half\"")),
+            Type::F32       => d.f32().map(ValueRef::F32),
+            Type::F64       => d.f64().map(ValueRef::F64),
+            Type::Bytes     => d.bytes().map(ValueRef::Bytes),
+            Type::String    => d.str().map(ValueRef::String),
+            Type::BytesIndef => {
+                d.bytes_iter()?.collect::<Result<_, _>>().map(ValueRef::BytesIndef)
+            }
+            Type::StringIndef => {
+                d.str_iter()?.collect::<Result<_, _>>().map(ValueRef::StringIndef)
+            }
+            Type::Array => {
+                d.entered(|d| {
+                    let iter: ArrayIterWithCtx<C, ValueRef<'b>> = d.array_iter_with(ctx)?;
+                    iter.collect::<Result<_, _>>().map(ValueRef::Array)
+                })
+            }
+            Type::ArrayIndef => {
+                d.entered(|d| {
+                    let iter: ArrayIterWithCtx<C, ValueRef<'b>> = d.array_iter_with(ctx)?;
+                    iter.collect::<Result<_, _>>().map(ValueRef::ArrayIndef)
+                })
+            }
+            Type::Map => {
+                d.entered(|d| {
+                    let iter: MapIterWithCtx<C, ValueRef<'b>, ValueRef<'b>> = d.map_iter_with(ctx)?;
+                    iter.collect::<Result<_, _>>().map(ValueRef::Map)
+                })
+            }
+            Type::MapIndef => {
+                d.entered(|d| {
+                    let iter: MapIterWithCtx<C, ValueRef<'b>, ValueRef<'b>> = d.map_iter_with(ctx)?;
+                    iter.collect::<Result<_, _>>().map(ValueRef::MapIndef)
+                })
+            }
+            Type::Tag => {
+                let t = d.tag()?;
+                d.enter().map_err(|e| e.at(p))?;
+                let v = ValueRef::decode(d, ctx);
+                d.leave();
+                Ok(ValueRef::Tag(t, Box::new(v?)))
+            }
+            Type::Break => Err(decode::Error::type_mismatch(Type::Break)
+                .at(p)
+                .with_message("unexpected break")),
+            t @ Type::Unknown(_) => Err(decode::Error::type_mismatch(t)
+                .at(p)
+                .with_message("unknown cbor type"))
+        }
+    }
+}
+
+impl<C> Encode<C> for ValueRef<'_> {
+    fn encode<W: Write>(&self, e: &mut Encoder<W>, ctx: &mut C) -> Result<(), encode::Error<W::Error>> {
+        match self {
+            ValueRef::Null       => e.null()?.ok(),
+            ValueRef::Undefined  => e.undefined()?.ok(),
+            ValueRef::Bool(b)    => e.bool(*b)?.ok(),
+            ValueRef::Simple(0x14 ..= 0x1f) => Err(encode::Error::message("simple values 20 to 31 can not be encoded as `Simple`")),
+            ValueRef::Simple(n)  => e.simple(*n)?.ok(),
+            ValueRef::Int(n)     => e.int(*n)?.ok(),
+            #[cfg(feature = "half")]
+            ValueRef::F16(x)     => e.f16(*x)?.ok(),
+            #[cfg(not(feature = "half"))]
+            ValueRef::F16(_)     => Err(encode::Error::message("encoding f16 values requires feature \"half\"")),
+            ValueRef::F32(x)     => e.f32(*x)?.ok(),
+            ValueRef::F64(x)     => e.f64(*x)?.ok(),
+            ValueRef::Bytes(b)   => e.bytes(b)?.ok(),
+            ValueRef::String(s)  => e.str(s)?.ok(),
+            ValueRef::BytesIndef(b) => {
+                e.begin_bytes()?;
+                for x in b {
+                    e.bytes(x)?;
+                }
+                e.end()?.ok()
+            }
+            ValueRef::StringIndef(s) => {
+                e.begin_str()?;
+                for x in s {
+                    e.str(x)?;
+                }
+                e.end()?.ok()
+            }
+            ValueRef::Array(a) => {
+                e.array(a.len() as u64)?;
+                for x in a {
+                    x.encode(e, ctx)?
+                }
+                Ok(())
+            }
+            ValueRef::ArrayIndef(a) => {
+                e.begin_array()?;
+                for x in a {
+                    x.encode(e, ctx)?
+                }
+                e.end()?.ok()
+            }
+            ValueRef::Map(m) => {
+                e.map(m.len() as u64)?;
+                for (k, v) in m {
+                    k.encode(e, ctx)?;
+                    v.encode(e, ctx)?
+                }
+                Ok(())
+            }
+            ValueRef::MapIndef(m) => {
+                e.begin_map()?;
+                for (k, v) in m {
+                    k.encode(e, ctx)?;
+                    v.encode(e, ctx)?
+                }
+                e.end()?.ok()
+            }
+            ValueRef::Tag(t, v) => {
+                e.tag(*t)?;
+                v.encode(e, ctx)
+            }
+        }
+    }
+}
+
+impl<C> CborLen<C> for ValueRef<'_> {
+    fn cbor_len(&self, ctx: &mut C) -> usize {
+        match self {
+            ValueRef::Null       => 1,
+            ValueRef::Undefined  => 1,
+            ValueRef::Bool(_)    => 1,
+            ValueRef::Simple(n)  => if *n < 0x18 { 1 } else { 2 },
+            ValueRef::Int(n)     => n.cbor_len(ctx),
+            ValueRef::F16(_)     => 3,
+            ValueRef::F32(_)     => 5,
+            ValueRef::F64(_)     => 9,
+            ValueRef::Bytes(b)   => b.len().cbor_len(ctx) + b.len(),
+            ValueRef::String(s)  => s.cbor_len(ctx),
+            ValueRef::BytesIndef(b) => {
+                2 + b.iter().map(|x| x.len().cbor_len(ctx) + x.len()).sum::<usize>()
+            }
+            ValueRef::StringIndef(s) => {
+                2 + s.iter().map(|x| x.cbor_len(ctx)).sum::<usize>()
+            }
+            ValueRef::Array(a) => a.cbor_len(ctx),
+            ValueRef::ArrayIndef(a) => {
+                2 + a.iter().map(|x| x.cbor_len(ctx)).sum::<usize>()
+            }
+            ValueRef::Map(m) => {
+                m.len().cbor_len(ctx) + m.iter()
+                    .map(|(k, v)| k.cbor_len(ctx) + v.cbor_len(ctx))
+                    .sum::<usize>()
+            }
+            ValueRef::MapIndef(m) => {
+                2 + m.iter()
+                    .map(|(k, v)| k.cbor_len(ctx) + v.cbor_len(ctx))
+                    .sum::<usize>()
+            }
+            ValueRef::Tag(t, v) => t.cbor_len(ctx) + v.cbor_len(ctx)
+        }
+    }
+}
@@ -6,13 +6,27 @@ use core::mem::MaybeUninit;
 
 mod decoder;
 mod error;
+mod limits;
+mod path;
 pub mod info;
 
 use crate::data::{Int, Tag, Tagged};
 
 pub use decoder::{Decoder, Probe};
 pub use decoder::{ArrayIter, ArrayIterWithCtx, BytesIter, MapIter, MapIterWithCtx, StrIter};
+pub use decoder::{SequenceIter, SequenceIterWithCtx};
 pub use error::Error;
+pub use limits::Limits;
+pub use path::Segment;
+
+#[cfg(feature = "alloc")]
+pub use error::Frame;
+
+#[cfg(feature = "std")]
+mod read;
+
+#[cfg(feature = "std")]
+pub use read::{ReadArrayIter, ReadDecoder, ReadMapIter};
 
 #[cfg(feature = "half")]
 mod tokenizer;
@@ -20,6 +34,9 @@ mod tokenizer;
 #[cfg(feature = "half")]
 pub use tokenizer::Tokenizer;
 
+#[cfg(all(feature = "alloc", feature = "half"))]
+pub use tokenizer::{Annotated, Spanned, SpannedToken};
+
 #[cfg(feature = "half")]
 #[deprecated(since = "0.23.0", note = "import `Token` from `minicbor::data` instead")]
 pub type Token<'b> = crate::data::Token<'b>;
@@ -65,6 +82,20 @@ impl<'b, C, T: Decode<'b, C>> Decode<'b, C> for alloc::boxed::Box<T> {
     }
 }
 
+#[cfg(feature = "alloc")]
+impl<'b, C, T: Decode<'b, C>> Decode<'b, C> for alloc::rc::Rc<T> {
+    fn decode(d: &mut Decoder<'b>, ctx: &mut C) -> Result<Self, Error> {
+        T::decode(d, ctx).map(alloc::rc::Rc::new)
+    }
+}
+
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+impl<'b, C, T: Decode<'b, C>> Decode<'b, C> for alloc::sync::Arc<T> {
+    fn decode(d: &mut Decoder<'b>, ctx: &mut C) -> Result<Self, Error> {
+        T::decode(d, ctx).map(alloc::sync::Arc::new)
+    }
+}
+
 impl<'a, 'b: 'a, C> Decode<'b, C> for &'a str {
     fn decode(d: &mut Decoder<'b>, _: &mut C) -> Result<Self, Error> {
         d.str()
@@ -112,6 +143,20 @@ impl<'b, C> Decode<'b, C> for alloc::boxed::Box<str> {
     }
 }
 
+#[cfg(feature = "alloc")]
+impl<'b, C> Decode<'b, C> for alloc::rc::Rc<str> {
+    fn decode(d: &mut Decoder<'b>, _: &mut C) -> Result<Self, Error> {
+        d.str().map(Into::into)
+    }
+}
+
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+impl<'b, C> Decode<'b, C> for alloc::sync::Arc<str> {
+    fn decode(d: &mut Decoder<'b>, _: &mut C) -> Result<Self, Error> {
+        d.str().map(Into::into)
+    }
+}
+
 impl<'b, C, T: Decode<'b, C>> Decode<'b, C> for Option<T> {
     fn decode(d: &mut Decoder<'b>, ctx: &mut C) -> Result<Self, Error> {
         if crate::data::Type::Null == d.datatype()? {
@@ -151,12 +196,14 @@ where
     T: Decode<'b, C> + Ord
 {
     fn decode(d: &mut Decoder<'b>, ctx: &mut C) -> Result<Self, Error> {
-        let iter: ArrayIterWithCtx<C, T> = d.array_iter_with(ctx)?;
-        let mut v = alloc::collections::BinaryHeap::new();
-        for x in iter {
-            v.push(x?)
-        }
-        Ok(v)
+        d.entered(|d| {
+            let iter: ArrayIterWithCtx<C, T> = d.array_iter_with(ctx)?;
+            let mut v = alloc::collections::BinaryHeap::new();
+            for x in iter {
+                v.push(x?)
+            }
+            Ok(v)
+        })
     }
 }
 
@@ -167,12 +214,14 @@ where
     S: std::hash::BuildHasher + std::default::Default
 {
     fn decode(d: &mut Decoder<'b>, ctx: &mut C) -> Result<Self, Error> {
-        let iter: ArrayIterWithCtx<C, T> = d.array_iter_with(ctx)?;
-        let mut v = std::collections::HashSet::default();
-        for x in iter {
-            v.insert(x?);
-        }
-        Ok(v)
+        d.entered(|d| {
+            let iter: ArrayIterWithCtx<C, T> = d.array_iter_with(ctx)?;
+            let mut v = std::collections::HashSet::default();
+            for x in iter {
+                v.insert(x?);
+            }
+            Ok(v)
+        })
     }
 }
 
@@ -182,12 +231,14 @@ where
     T: Decode<'b, C> + Ord
 {
     fn decode(d: &mut Decoder<'b>, ctx: &mut C) -> Result<Self, Error> {
-        let iter: ArrayIterWithCtx<C, T> = d.array_iter_with(ctx)?;
-        let mut v = alloc::collections::BTreeSet::new();
-        for x in iter {
-            v.insert(x?);
-        }
-        Ok(v)
+        d.entered(|d| {
+            let iter: ArrayIterWithCtx<C, T> = d.array_iter_with(ctx)?;
+            let mut v = alloc::collections::BTreeSet::new();
+            for x in iter {
+                v.insert(x?);
+            }
+            Ok(v)
+        })
     }
 }
 
@@ -200,11 +251,7 @@ where
 {
     fn decode(d: &mut Decoder<'b>, ctx: &mut C) -> Result<Self, Error> {
         let mut m = std::collections::HashMap::default();
-        let iter: MapIterWithCtx<C, K, V> = d.map_iter_with(ctx)?;
-        for x in iter {
-            let (k, v) = x?;
-            m.insert(k, v);
-        }
+        decode_map(d, ctx, |k, v| m.insert(k, v).is_some())?;
         Ok(m)
     }
 }
@@ -217,15 +264,37 @@ where
 {
     fn decode(d: &mut Decoder<'b>, ctx: &mut C) -> Result<Self, Error> {
         let mut m = alloc::collections::BTreeMap::new();
-        let iter: MapIterWithCtx<C, K, V> = d.map_iter_with(ctx)?;
-        for x in iter {
-            let (k, v) = x?;
-            m.insert(k, v);
-        }
+        decode_map(d, ctx, |k, v| m.insert(k, v).is_some())?;
         Ok(m)
     }
 }
 
+/// Decode map entries and pass them to `insert`.
+///
+/// `insert` returns `true` if the map already contained the key.
+#[cfg(feature = "alloc")]
+fn decode_map<'b, C, K, V, F>(d: &mut Decoder<'b>, ctx: &mut C, mut insert: F) -> Result<(), Error>
+where
+    K: Decode<'b, C>,
+    V: Decode<'b, C>,
+    F: FnMut(K, V) -> bool
+{
+    let unique = d.rejects_duplicate_keys();
+    d.entered(|d| {
+        let mut iter: MapIterWithCtx<C, K, V> = d.map_iter_with(ctx)?;
+        loop {
+            let p = iter.decoder().position();
+            let Some(x) = iter.next() else {
+                return Ok(())
+            };
+            let (k, v) = x?;
+            if insert(k, v) && unique {
+                return Err(iter.decoder().duplicate_map_key(p))
+            }
+        }
+    })
+}
+
 impl<'b, C, T> Decode<'b, C> for core::marker::PhantomData<T> {
     fn decode(d: &mut Decoder<'b>, _: &mut C) -> Result<Self, Error> {
         let p = d.position();
@@ -319,7 +388,7 @@ macro_rules! decode_basic {
     }
 }
 
-decode_basic!(u8 i8 u16 i16 u32 i32 u64 i64 bool f32 f64 char);
+decode_basic!(u8 i8 u16 i16 u32 i32 u64 i64 u128 i128 bool f32 f64 char);
 
 macro_rules! decode_nonzero {
     ($($t:ty, $msg:expr)*) => {
@@ -398,12 +467,14 @@ macro_rules! decode_sequential {
         $(
             impl<'b, C, T: Decode<'b, C>> Decode<'b, C> for $t {
                 fn decode(d: &mut Decoder<'b>, ctx: &mut C) -> Result<Self, Error> {
-                    let iter: ArrayIterWithCtx<C, T> = d.array_iter_with(ctx)?;
-                    let mut v = <$t>::new();
-                    for x in iter {
-                        v.$push(x?)
-                    }
-                    Ok(v)
+                    d.entered(|d| {
+                        let iter: ArrayIterWithCtx<C, T> = d.array_iter_with(ctx)?;
+                        let mut v = <$t>::new();
+                        for x in iter {
+                            v.$push(x?)
+                        }
+                        Ok(v)
+                    })
                 }
             }
         )*
@@ -471,23 +542,25 @@ impl <T, const N: usize> core::ops::Drop for ArrayVec<T, N> {
 impl<'b, C, T: Decode<'b, C>, const N: usize> Decode<'b, C> for [T; N] {
     fn decode(d: &mut Decoder<'b>, ctx: &mut C) -> Result<Self, Error> {
         let p = d.position();
-        let iter: ArrayIterWithCtx<C, T> = d.array_iter_with(ctx)?;
-        let mut a = ArrayVec::<T, N>::new();
-        for x in iter {
-            a.push(x?).map_err(|_| {
+        d.entered(|d| {
+            let iter: ArrayIterWithCtx<C, T> = d.array_iter_with(ctx)?;
+            let mut a = ArrayVec::<T, N>::new();
+            for x in iter {
+                a.push(x?).map_err(|_| {
+                    #[cfg(feature = "alloc")]
+                    let msg = &alloc::format!("array has more than {N} elements");
+                    #[cfg(not(feature = "alloc"))]
+                    let msg = "array has too many elements";
+                    Error::message(msg).at(p)
+                })?;
+            }
+            a.into_array().map_err(|_| {
                 #[cfg(feature = "alloc")]
-                let msg = &alloc::format!("array has more than {N} elements");
+                let msg = &alloc::format!("array has less than {N} elements");
                 #[cfg(not(feature = "alloc"))]
-                let msg = "array has too many elements";
+                let msg = "array has too few elements";
                 Error::message(msg).at(p)
-            })?;
-        }
-        a.into_array().map_err(|_| {
-            #[cfg(feature = "alloc")]
-            let msg = &alloc::format!("array has less than {N} elements");
-            #[cfg(not(feature = "alloc"))]
-            let msg = "array has too few elements";
-            Error::message(msg).at(p)
+            })
         })
     }
 }
@@ -1,21 +1,66 @@
 #![allow(clippy::unusual_byte_groupings)]
 
 use crate::{ARRAY, BREAK, BYTES, MAP, SIMPLE, TAGGED, TEXT, SIGNED, UNSIGNED};
-use crate::data::{Int, Tag, Type};
-use crate::decode::{Decode, Error};
+use crate::data::{IanaTag, Int, Tag, Type};
+use crate::decode::{Decode, Error, Limits, Segment};
 use core::{marker, str};
+use core::ops::Range;
 
-/// A non-allocating CBOR decoder.
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+use alloc::sync::Arc as Rc;
+
+#[cfg(all(feature = "alloc", not(target_has_atomic = "ptr")))]
+use alloc::rc::Rc;
+
+/// Flag set in strict mode.
+const STRICT: u8 = 1;
+
+/// Flag set if maps with duplicate keys are rejected.
+const UNIQUE_KEYS: u8 = 2;
+
+/// Flag set if any limit differs from the default.
+const LIMITS: u8 = 4;
+
+/// The string tables of all active string reference namespaces.
+///
+/// They are reference counted so that cloning a decoder stays cheap and
+/// are only copied if a clone adds to them.
+#[cfg(feature = "alloc")]
+type StringTables<'b> = Option<Rc<alloc::vec::Vec<alloc::vec::Vec<(bool, &'b [u8])>>>>;
+
+/// A CBOR decoder.
+///
+/// Decoding borrows from the input and does not allocate, except with
+/// feature `"alloc"` for the string tables of string reference namespaces
+/// (tag 256) and the bookkeeping of [`Decoder::skip`] and of the map key
+/// checks in [strict mode](Decoder::set_strict).
 #[derive(Debug, Clone)]
 pub struct Decoder<'b> {
     buf: &'b [u8],
-    pos: usize
+    pos: usize,
+    flags: u8,
+    limits: Limits,
+    depth: u32,
+    alloc: u64,
+    keys_checked: Range<usize>,
+    #[cfg(feature = "alloc")]
+    strings: StringTables<'b>
 }
 
 impl<'b> Decoder<'b> {
     /// Construct a `Decoder` for the given byte slice.
     pub fn new(bytes: &'b [u8]) -> Self {
-        Decoder { buf: bytes, pos: 0 }
+        Decoder {
+            buf: bytes,
+            pos: 0,
+            flags: 0,
+            limits: Limits::new(),
+            depth: 0,
+            alloc: 0,
+            keys_checked: 0 .. 0,
+            #[cfg(feature = "alloc")]
+            strings: None
+        }
     }
 
     /// Decode any type that implements [`Decode`].
@@ -43,6 +88,204 @@ impl<'b> Decoder<'b> {
         self.buf
     }
 
+    /// Enable or disable strict decoding (disabled by default).
+    ///
+    /// A strict decoder only accepts input in core deterministic encoding
+    /// as specified in [RFC 8949, section 4.2.1][1] and rejects
+    ///
+    /// - integers, lengths and tags which are not encoded in their shortest
+    ///   form ([`Error::is_non_minimal_int`]),
+    /// - floating point values which could be encoded in a shorter form
+    ///   without loss of precision ([`Error::is_non_shortest_float`]),
+    /// - indefinite-length bytes, strings, arrays and maps
+    ///   ([`Error::is_indefinite_length`]),
+    /// - maps whose keys are not sorted by the bytewise lexicographic order
+    ///   of their encoding ([`Error::is_unsorted_map_keys`]),
+    /// - maps containing the same key more than once
+    ///   ([`Error::is_duplicate_map_key`]) and
+    /// - simple values other than `false`, `true`, `null` and `undefined`
+    ///   ([`Error::is_unknown_simple`]).
+    ///
+    /// In addition, bignums decoded with [`Decoder::u128`] or [`Decoder::i128`]
+    /// must be in preferred serialization ([`Error::is_non_minimal_int`]).
+    ///
+    /// All errors report the position of the offending data item.
+    ///
+    /// **NB**: To check the order of map keys, [`Decoder::map`] scans all map
+    /// entries upfront, i.e. every map is processed twice in strict mode. The
+    /// keys of maps nested inside are checked during the same scan, so every
+    /// byte is scanned at most once. Without feature `"alloc"` each nested map
+    /// is scanned separately when it is decoded.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use minicbor::Decoder;
+    ///
+    /// let input = [0x18, 0x01]; // 1 encoded in two bytes
+    /// assert_eq!(1, Decoder::new(&input).u8()?);
+    ///
+    /// let mut d = Decoder::new(&input);
+    /// d.set_strict(true);
+    /// let e = d.u8().unwrap_err();
+    /// assert!(e.is_non_minimal_int());
+    /// assert_eq!(Some(0), e.position());
+    /// # Ok::<_, Box<dyn core::error::Error>>(())
+    /// ```
+    ///
+    /// [1]: https://www.rfc-editor.org/rfc/rfc8949.html#section-4.2.1
+    pub fn set_strict(&mut self, val: bool) {
+        self.set_flag(STRICT, val)
+    }
+
+    /// Is this decoder in strict mode?
+    ///
+    /// See [`Decoder::set_strict`] for details.
+    pub fn is_strict(&self) -> bool {
+        self.flags & STRICT != 0
+    }
+
+    /// Reject maps with duplicate keys (disabled by default).
+    ///
+    /// By default, when decoding a map type such as `BTreeMap` or `HashMap`,
+    /// a later entry silently replaces an earlier one with the same key.
+    /// Since this allows the same input to be understood differently by
+    /// different decoders, it may be preferable to reject such input with
+    /// an error for which [`Error::is_duplicate_map_key`] returns `true`.
+    ///
+    /// The error position refers to the second occurence of the key and
+    /// with features `"alloc"` and `"half"` the error message contains the
+    /// key in diagnostic notation.
+    ///
+    /// This setting is honoured by the `Decode` impls of `BTreeMap` and
+    /// `HashMap` and by `Decode` impls derived with `minicbor-derive` for
+    /// types using map encoding. Derived impls can also opt into this check
+    /// unconditionally with the `#[cbor(reject_duplicate_keys)]` attribute.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::collections::BTreeMap;
+    /// use minicbor::Decoder;
+    ///
+    /// let input = [0xa2, 0x01, 0x02, 0x01, 0x03]; // {1: 2, 1: 3}
+    ///
+    /// let m: BTreeMap<u8, u8> = Decoder::new(&input).decode()?;
+    /// assert_eq!(Some(&3), m.get(&1));
+    ///
+    /// let mut d = Decoder::new(&input);
+    /// d.set_reject_duplicate_keys(true);
+    /// let e = d.decode::<BTreeMap<u8, u8>>().unwrap_err();
+    /// assert!(e.is_duplicate_map_key());
+    /// assert_eq!(Some(3), e.position());
+    /// # Ok::<_, Box<dyn core::error::Error>>(())
+    /// ```
+    pub fn set_reject_duplicate_keys(&mut self, val: bool) {
+        self.set_flag(UNIQUE_KEYS, val)
+    }
+
+    /// Does this decoder reject maps with duplicate keys?
+    ///
+    /// See [`Decoder::set_reject_duplicate_keys`] for details.
+    pub fn rejects_duplicate_keys(&self) -> bool {
+        self.flags & UNIQUE_KEYS != 0
+    }
+
+    /// Set the resource limits of this decoder.
+    ///
+    /// See [`Limits`] for details.
+    pub fn set_limits(&mut self, limits: Limits) {
+        self.limits = limits;
+        self.set_flag(LIMITS, limits != Limits::new())
+    }
+
+    /// Set or clear the given flag.
+    fn set_flag(&mut self, flag: u8, val: bool) {
+        if val {
+            self.flags |= flag
+        } else {
+            self.flags &= !flag
+        }
+    }
+
+    /// Get the resource limits of this decoder.
+    pub fn limits(&self) -> Limits {
+        self.limits
+    }
+
+    /// Get the current nesting depth.
+    pub fn depth(&self) -> u32 {
+        self.depth
+    }
+
+    /// Increment the nesting depth.
+    ///
+    /// Fails if the max. depth of the [`Limits`] would be exceeded. If
+    /// successful, a matching [`Decoder::leave`] must follow eventually.
+    ///
+    /// The `Decode` impls of collections, [`Decoder::skip`] and `Decode`
+    /// impls derived with `minicbor-derive` do this already. The iterators
+    /// returned by [`Decoder::array_iter`], [`Decoder::map_iter`] etc. do not.
+    /// Other `Decode` impls of recursive types should call `enter` and `leave`
+    /// to guard against excessive nesting, e.g.
+    ///
+    /// ```
+    /// use minicbor::{Decode, Decoder};
+    /// use minicbor::decode::{Error, Limits};
+    ///
+    /// #[derive(Debug)]
+    /// struct List(Option<Box<List>>);
+    ///
+    /// impl<'b, C> Decode<'b, C> for List {
+    ///     fn decode(d: &mut Decoder<'b>, ctx: &mut C) -> Result<Self, Error> {
+    ///         d.enter()?;
+    ///         let x = match d.array() {
+    ///             Ok(Some(1)) => List::decode(d, ctx).map(|x| Some(Box::new(x))),
+    ///             Ok(_)       => Ok(None),
+    ///             Err(e)      => Err(e)
+    ///         };
+    ///         d.leave();
+    ///         Ok(List(x?))
+    ///     }
+    /// }
+    ///
+    /// let input = [0x81, 0x81, 0x80]; // [[[]]]
+    ///
+    /// let mut d = Decoder::new(&input);
+    /// assert!(d.decode::<List>().is_ok());
+    ///
+    /// let mut d = Decoder::new(&input);
+    /// d.set_limits(Limits::new().with_max_depth(2));
+    /// let e = d.decode::<List>().unwrap_err();
+    /// assert!(e.is_limit_exceeded());
+    /// assert_eq!(Some(2), e.position());
+    /// ```
+    pub fn enter(&mut self) -> Result<(), Error> {
+        if self.depth >= self.limits.depth {
+            return Err(Error::limit_exceeded("nesting depth", self.limits.depth.into()).at(self.pos))
+        }
+        self.depth += 1;
+        Ok(())
+    }
+
+    /// Decrement the nesting depth.
+    ///
+    /// See [`Decoder::enter`] for details.
+    pub fn leave(&mut self) {
+        self.depth = self.depth.saturating_sub(1)
+    }
+
+    /// Apply `f` to this decoder with an incremented nesting depth.
+    pub(crate) fn entered<T, F>(&mut self, f: F) -> Result<T, Error>
+    where
+        F: FnOnce(&mut Self) -> Result<T, Error>
+    {
+        self.enter()?;
+        let r = f(self);
+        self.leave();
+        r
+    }
+
     /// Get a decoding probe to look ahead what is coming next.
     ///
     /// This will not affect the decoding state of `self` and after the
@@ -58,6 +301,7 @@ impl<'b> Decoder<'b> {
     /// Decode a `bool` value.
     pub fn bool(&mut self) -> Result<bool, Error> {
         let p = self.pos;
+        self.check_head()?;
         match self.read()? {
             0xf4 => Ok(false),
             0xf5 => Ok(true),
@@ -68,6 +312,7 @@ impl<'b> Decoder<'b> {
     /// Decode a `u8` value.
     pub fn u8(&mut self) -> Result<u8, Error> {
         let p = self.pos;
+        self.check_head()?;
         match self.read()? {
             n @ 0 ..= 0x17 => Ok(n),
             0x18           => self.read(),
@@ -81,6 +326,7 @@ impl<'b> Decoder<'b> {
     /// Decode a `u16` value.
     pub fn u16(&mut self) -> Result<u16, Error> {
         let p = self.pos;
+        self.check_head()?;
         match self.read()? {
             n @ 0 ..= 0x17 => Ok(u16::from(n)),
             0x18           => self.read().map(u16::from),
@@ -94,6 +340,7 @@ impl<'b> Decoder<'b> {
     /// Decode a `u32` value.
     pub fn u32(&mut self) -> Result<u32, Error> {
         let p = self.pos;
+        self.check_head()?;
         match self.read()? {
             n @ 0 ..= 0x17 => Ok(u32::from(n)),
             0x18           => self.read().map(u32::from),
@@ -107,6 +354,7 @@ impl<'b> Decoder<'b> {
     /// Decode a `u64` value.
     pub fn u64(&mut self) -> Result<u64, Error> {
         let p = self.pos;
+        self.check_head()?;
         let n = self.read()?;
         self.unsigned(n, p)
     }
@@ -114,6 +362,7 @@ impl<'b> Decoder<'b> {
     /// Decode an `i8` value.
     pub fn i8(&mut self) -> Result<i8, Error> {
         let p = self.pos;
+        self.check_head()?;
         match self.read()? {
             n @ 0x00 ..= 0x17 => Ok(n as i8),
             0x18              => self.read().and_then(|n| try_as(n, "when converting u8 to i8", p)),
@@ -132,6 +381,7 @@ impl<'b> Decoder<'b> {
     /// Decode an `i16` value.
     pub fn i16(&mut self) -> Result<i16, Error> {
         let p = self.pos;
+        self.check_head()?;
         match self.read()? {
             n @ 0x00 ..= 0x17 => Ok(i16::from(n)),
             0x18              => self.read().map(i16::from),
@@ -150,6 +400,7 @@ impl<'b> Decoder<'b> {
     /// Decode an `i32` value.
     pub fn i32(&mut self) -> Result<i32, Error> {
         let p = self.pos;
+        self.check_head()?;
         match self.read()? {
             n @ 0x00 ..= 0x17 => Ok(i32::from(n)),
             0x18              => self.read().map(i32::from),
@@ -168,6 +419,7 @@ impl<'b> Decoder<'b> {
     /// Decode an `i64` value.
     pub fn i64(&mut self) -> Result<i64, Error> {
         let p = self.pos;
+        self.check_head()?;
         match self.read()? {
             n @ 0x00 ..= 0x17 => Ok(i64::from(n)),
             0x18              => self.read().map(i64::from),
@@ -183,11 +435,42 @@ impl<'b> Decoder<'b> {
         }
     }
 
+    /// Decode a `u128` value.
+    ///
+    /// Regular integers and positive bignums (tag 2) are accepted. Leading
+    /// zero bytes of bignums are ignored, except in strict mode, where
+    /// bignums must not have any and must not fit into a `u64`.
+    pub fn u128(&mut self) -> Result<u128, Error> {
+        let p = self.pos;
+        if self.current().map(type_of)? != TAGGED {
+            return self.u64().map(u128::from).map_err(|e| if e.is_type_mismatch() { e.with_message("expected u128") } else { e })
+        }
+        match self.bignum(p)? {
+            (false, n) => Ok(n),
+            (true,  _) => Err(Error::tag_mismatch(IanaTag::NegBignum.tag()).with_message("expected tag 2").at(p))
+        }
+    }
+
+    /// Decode an `i128` value.
+    ///
+    /// Regular integers and positive (tag 2) or negative (tag 3) bignums are
+    /// accepted. See [`Decoder::u128`] regarding leading zero bytes.
+    pub fn i128(&mut self) -> Result<i128, Error> {
+        let p = self.pos;
+        if self.current().map(type_of)? != TAGGED {
+            return self.int().map(i128::from).map_err(|e| if e.is_type_mismatch() { e.with_message("expected i128") } else { e })
+        }
+        let (neg, n) = self.bignum(p)?;
+        let n = i128::try_from(n).map_err(|_| Error::bignum_overflow().with_message("when converting to i128").at(p))?;
+        Ok(if neg { -1 - n } else { n })
+    }
+
     /// Decode a CBOR integer.
     ///
     /// See [`Int`] for details regarding the value range of CBOR integers.
     pub fn int(&mut self) -> Result<Int, Error> {
         let p = self.pos;
+        self.check_head()?;
         match self.read()? {
             n @ 0x00 ..= 0x17 => Ok(Int::pos(n)),
             0x18              => self.read().map(Int::pos),
@@ -209,6 +492,7 @@ impl<'b> Decoder<'b> {
     #[cfg(feature = "half")]
     pub fn f16(&mut self) -> Result<f32, Error> {
         let p = self.pos;
+        self.check_head()?;
         let b = self.read()?;
         if 0xf9 != b {
             return Err(Error::type_mismatch(self.type_of(b)?).at(p).with_message("expected f16"))
@@ -216,9 +500,24 @@ impl<'b> Decoder<'b> {
         Ok(half::f16::from_bits(u16::from_be_bytes(self.read_array()?)).to_f32())
     }
 
+    /// Decode the bits of a half float (`f16`).
+    ///
+    /// Used by [`crate::float`] when the feature `half` is not present.
+    #[cfg(not(feature = "half"))]
+    pub(crate) fn f16_bits(&mut self) -> Result<u16, Error> {
+        let p = self.pos;
+        self.check_head()?;
+        let b = self.read()?;
+        if 0xf9 != b {
+            return Err(Error::type_mismatch(self.type_of(b)?).at(p).with_message("expected f16"))
+        }
+        Ok(u16::from_be_bytes(self.read_array()?))
+    }
+
     /// Decode an `f32` value.
     pub fn f32(&mut self) -> Result<f32, Error> {
         let p = self.pos;
+        self.check_head()?;
         match self.current()? {
             #[cfg(feature = "half")]
             0xf9 => self.f16(),
@@ -233,6 +532,7 @@ impl<'b> Decoder<'b> {
     /// Decode an `f64` value.
     pub fn f64(&mut self) -> Result<f64, Error> {
         let p = self.pos;
+        self.check_head()?;
         match self.current()? {
             #[cfg(feature = "half")]
             0xf9 => self.f16().map(f64::from),
@@ -256,16 +556,37 @@ impl<'b> Decoder<'b> {
     ///
     /// This only decodes byte slices of definite lengths.
     /// See [`Decoder::bytes_iter`] for indefinite byte slice support.
+    ///
+    /// Within a string reference namespace (cf. [`StringRefs`]), tag 25
+    /// references to earlier byte slices are resolved.
+    ///
+    /// [`StringRefs`]: crate::data::StringRefs
     pub fn bytes(&mut self) -> Result<&'b [u8], Error> {
+        #[cfg(feature = "alloc")]
+        if self.strings.is_some() {
+            if let Some(b) = self.string_ref(false)? {
+                return Ok(b)
+            }
+            let b = self.definite_bytes()?;
+            self.add_string(false, b);
+            return Ok(b)
+        }
+        self.definite_bytes()
+    }
+
+    /// Decode a byte slice of definite length.
+    fn definite_bytes(&mut self) -> Result<&'b [u8], Error> {
         let p = self.pos;
+        self.check_head()?;
         let b = self.read()?;
         if BYTES != type_of(b) || info_of(b) == 31 {
             return Err(Error::type_mismatch(self.type_of(b)?)
                 .with_message("expected bytes (definite length)")
                 .at(p))
         }
-        let n = u64_to_usize(self.unsigned(info_of(b), p)?, p)?;
-        self.read_slice(n)
+        let n = self.unsigned(info_of(b), p)?;
+        self.check_len(n, p)?;
+        self.read_slice(u64_to_usize(n, p)?)
     }
 
     /// Iterate over byte slices.
@@ -275,6 +596,7 @@ impl<'b> Decoder<'b> {
     /// only yield one item.
     pub fn bytes_iter(&mut self) -> Result<BytesIter<'_, 'b>, Error> {
         let p = self.pos;
+        self.check_head()?;
         let b = self.read()?;
         if BYTES != type_of(b) {
             return Err(Error::type_mismatch(self.type_of(b)?)
@@ -282,10 +604,12 @@ impl<'b> Decoder<'b> {
                 .at(p))
         }
         match info_of(b) {
-            31 => Ok(BytesIter { decoder: self, len: None }),
+            31 => Ok(BytesIter { decoder: self, len: None, total: 0, pos: p }),
             n  => {
-                let len = u64_to_usize(self.unsigned(n, p)?, p)?;
-                Ok(BytesIter { decoder: self, len: Some(len) })
+                let n = self.unsigned(n, p)?;
+                self.check_len(n, p)?;
+                let len = u64_to_usize(n, p)?;
+                Ok(BytesIter { decoder: self, len: Some(len), total: 0, pos: p })
             }
         }
     }
@@ -294,16 +618,38 @@ impl<'b> Decoder<'b> {
     ///
     /// This only decodes string slices of definite lengths.
     /// See [`Decoder::str_iter`] for indefinite string slice support.
+    ///
+    /// Within a string reference namespace (cf. [`StringRefs`]), tag 25
+    /// references to earlier string slices are resolved.
+    ///
+    /// [`StringRefs`]: crate::data::StringRefs
     pub fn str(&mut self) -> Result<&'b str, Error> {
+        #[cfg(feature = "alloc")]
+        if self.strings.is_some() {
+            if let Some(b) = self.string_ref(true)? {
+                let p = self.pos;
+                return str::from_utf8(b).map_err(|e| Error::utf8(e).at(p))
+            }
+            let s = self.definite_str()?;
+            self.add_string(true, s.as_bytes());
+            return Ok(s)
+        }
+        self.definite_str()
+    }
+
+    /// Decode a string slice of definite length.
+    fn definite_str(&mut self) -> Result<&'b str, Error> {
         let p = self.pos;
+        self.check_head()?;
         let b = self.read()?;
         if TEXT != type_of(b) || info_of(b) == 31 {
             return Err(Error::type_mismatch(self.type_of(b)?)
                 .with_message("expected text (definite length)")
                 .at(p))
         }
-        let n = u64_to_usize(self.unsigned(info_of(b), p)?, p)?;
-        let d = self.read_slice(n)?;
+        let n = self.unsigned(info_of(b), p)?;
+        self.check_len(n, p)?;
+        let d = self.read_slice(u64_to_usize(n, p)?)?;
         str::from_utf8(d).map_err(|e| Error::utf8(e).at(p))
     }
 
@@ -314,6 +660,7 @@ impl<'b> Decoder<'b> {
     /// will only yield one item.
     pub fn str_iter(&mut self) -> Result<StrIter<'_, 'b>, Error> {
         let p = self.pos;
+        self.check_head()?;
         let b = self.read()?;
         if TEXT != type_of(b) {
             return Err(Error::type_mismatch(self.type_of(b)?)
@@ -321,10 +668,12 @@ impl<'b> Decoder<'b> {
                 .at(p))
         }
         match info_of(b) {
-            31 => Ok(StrIter { decoder: self, len: None, pos: p }),
+            31 => Ok(StrIter { decoder: self, len: None, total: 0, pos: p }),
             n  => {
-                let len = u64_to_usize(self.unsigned(n, p)?, p)?;
-                Ok(StrIter { decoder: self, len: Some(len), pos: p })
+                let n = self.unsigned(n, p)?;
+                self.check_len(n, p)?;
+                let len = u64_to_usize(n, p)?;
+                Ok(StrIter { decoder: self, len: Some(len), total: 0, pos: p })
             }
         }
     }
@@ -336,6 +685,7 @@ impl<'b> Decoder<'b> {
     /// indefinite arrays a `None` is returned.
     pub fn array(&mut self) -> Result<Option<u64>, Error> {
         let p = self.pos;
+        self.check_head()?;
         let b = self.read()?;
         if ARRAY != type_of(b) {
             return Err(Error::type_mismatch(self.type_of(b)?)
@@ -344,7 +694,11 @@ impl<'b> Decoder<'b> {
         }
         match info_of(b) {
             31 => Ok(None),
-            n  => Ok(Some(self.unsigned(n, p)?))
+            n  => {
+                let len = self.unsigned(n, p)?;
+                self.check_items(len, p)?;
+                Ok(Some(len))
+            }
         }
     }
 
@@ -358,7 +712,7 @@ impl<'b> Decoder<'b> {
         T: Decode<'b, ()>
     {
         let len = self.array()?;
-        Ok(ArrayIter { decoder: self, len, _mark: marker::PhantomData })
+        Ok(ArrayIter { decoder: self, len, count: 0, _mark: marker::PhantomData })
     }
 
     /// Iterate over all array elements.
@@ -371,7 +725,7 @@ impl<'b> Decoder<'b> {
         T: Decode<'b, C>
     {
         let len = self.array()?;
-        Ok(ArrayIterWithCtx { decoder: self, ctx, len, _mark: marker::PhantomData })
+        Ok(ArrayIterWithCtx { decoder: self, ctx, len, count: 0, _mark: marker::PhantomData })
     }
 
     /// Begin decoding a map.
@@ -381,6 +735,7 @@ impl<'b> Decoder<'b> {
     /// as a `Some`, for indefinite maps a `None` is returned.
     pub fn map(&mut self) -> Result<Option<u64>, Error> {
         let p = self.pos;
+        self.check_head()?;
         let b = self.read()?;
         if MAP != type_of(b) {
             return Err(Error::type_mismatch(self.type_of(b)?)
@@ -389,7 +744,14 @@ impl<'b> Decoder<'b> {
         }
         match info_of(b) {
             31 => Ok(None),
-            n  => Ok(Some(self.unsigned(n, p)?))
+            n  => {
+                let len = self.unsigned(n, p)?;
+                self.check_items(len, p)?;
+                if self.is_strict() && !self.keys_checked.contains(&self.pos) {
+                    self.check_map_keys(len)?
+                }
+                Ok(Some(len))
+            }
         }
     }
 
@@ -404,7 +766,7 @@ impl<'b> Decoder<'b> {
         V: Decode<'b, ()>
     {
         let len = self.map()?;
-        Ok(MapIter { decoder: self, len, _mark: marker::PhantomData })
+        Ok(MapIter { decoder: self, len, count: 0, _mark: marker::PhantomData })
     }
 
     /// Iterate over all map entries.
@@ -418,12 +780,57 @@ impl<'b> Decoder<'b> {
         V: Decode<'b, C>
     {
         let len = self.map()?;
-        Ok(MapIterWithCtx { decoder: self, ctx, len, _mark: marker::PhantomData })
+        Ok(MapIterWithCtx { decoder: self, ctx, len, count: 0, _mark: marker::PhantomData })
+    }
+
+    /// Iterate over a sequence of top-level data items ([RFC 8742][1]).
+    ///
+    /// Starting from the current position, the iterator decodes one item
+    /// after another until the end of the input is reached. Decoding errors
+    /// are returned and end the iteration. In particular, if the last item is
+    /// incomplete an error for which [`Error::is_end_of_input`] returns `true`
+    /// is produced.
+    ///
+    /// Like [`Decoder::array_iter`], *only homogenous sequences are supported
+    /// by this method*.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use minicbor::Decoder;
+    ///
+    /// let input = [0x01, 0x82, 0x02, 0x03, 0x80]; // 1, [2, 3], []
+    /// let mut d = Decoder::new(&input);
+    /// assert_eq!(1, d.u8()?);
+    /// let v = d.sequence::<Vec<u8>>().collect::<Result<Vec<_>, _>>()?;
+    /// assert_eq!(v, [vec![2, 3], vec![]]);
+    /// # Ok::<_, Box<dyn core::error::Error>>(())
+    /// ```
+    ///
+    /// [1]: https://www.rfc-editor.org/rfc/rfc8742.html
+    pub fn sequence<T>(&mut self) -> SequenceIter<'_, 'b, T>
+    where
+        T: Decode<'b, ()>
+    {
+        SequenceIter { decoder: self, done: false, _mark: marker::PhantomData }
+    }
+
+    /// Iterate over a sequence of top-level data items ([RFC 8742][1]).
+    ///
+    /// See [`Decoder::sequence`] for details.
+    ///
+    /// [1]: https://www.rfc-editor.org/rfc/rfc8742.html
+    pub fn sequence_with<'a, C, T>(&'a mut self, ctx: &'a mut C) -> SequenceIterWithCtx<'a, 'b, C, T>
+    where
+        T: Decode<'b, C>
+    {
+        SequenceIterWithCtx { decoder: self, ctx, done: false, _mark: marker::PhantomData }
     }
 
     /// Decode a CBOR tag.
     pub fn tag(&mut self) -> Result<Tag, Error> {
         let p = self.pos;
+        self.check_head()?;
         let b = self.read()?;
         if TAGGED != type_of(b) {
             return Err(Error::type_mismatch(self.type_of(b)?)
@@ -436,6 +843,7 @@ impl<'b> Decoder<'b> {
     /// Decode a CBOR null value.
     pub fn null(&mut self) -> Result<(), Error> {
         let p = self.pos;
+        self.check_head()?;
         match self.read()? {
             0xf6 => Ok(()),
             n    => Err(Error::type_mismatch(self.type_of(n)?)
@@ -447,6 +855,7 @@ impl<'b> Decoder<'b> {
     /// Decode a CBOR undefined value.
     pub fn undefined(&mut self) -> Result<(), Error> {
         let p = self.pos;
+        self.check_head()?;
         match self.read()? {
             0xf7 => Ok(()),
             n    => Err(Error::type_mismatch(self.type_of(n)?)
@@ -458,6 +867,7 @@ impl<'b> Decoder<'b> {
     /// Decode a CBOR simple value.
     pub fn simple(&mut self) -> Result<u8, Error> {
         let p = self.pos;
+        self.check_head()?;
         match self.read()? {
             n @ SIMPLE ..= 0xf3 => Ok(n - SIMPLE),
             0xf8 => self.read(),
@@ -472,6 +882,53 @@ impl<'b> Decoder<'b> {
         self.type_of(self.current()?)
     }
 
+    /// Find the item at the given path, starting from the current position.
+    ///
+    /// Returns a copy of this decoder positioned at the beginning of the item.
+    /// Array elements and map entries which are not on the path are skipped
+    /// over without being decoded and tags in front of the arrays and maps
+    /// along the path are ignored. The position of this decoder is left
+    /// unchanged.
+    ///
+    /// If an item on the path is not an array (for [`Segment::Index`]) or not
+    /// a map (for the other segments), a type mismatch error is returned. If
+    /// an array index is out of bounds or a map key is missing, the error
+    /// satisfies [`Error::is_not_found`]. Error positions refer to the array
+    /// or map in question.
+    ///
+    /// See [`Segment`] for an example.
+    pub fn query(&self, path: &[Segment<'_>]) -> Result<Decoder<'b>, Error> {
+        let mut d = self.clone();
+        crate::decode::path::select(&mut d, path)?;
+        Ok(d)
+    }
+
+    /// Find the byte range of the item at the given path, starting from the
+    /// current position.
+    ///
+    /// See [`Decoder::query`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use minicbor::Decoder;
+    /// use minicbor::decode::Segment;
+    ///
+    /// let input = [0x82, 0x01, 0x82, 0x02, 0x03]; // [1, [2, 3]]
+    /// assert_eq!(2 .. 5, Decoder::new(&input).query_span(&[Segment::Index(1)])?);
+    ///
+    /// let e = Decoder::new(&input).query_span(&[Segment::Index(2)]).unwrap_err();
+    /// assert!(e.is_not_found());
+    /// assert_eq!(Some(0), e.position());
+    /// # Ok::<_, Box<dyn core::error::Error>>(())
+    /// ```
+    pub fn query_span(&self, path: &[Segment<'_>]) -> Result<Range<usize>, Error> {
+        let mut d = self.query(path)?;
+        let start = d.position();
+        d.skip()?;
+        Ok(start .. d.position())
+    }
+
     /// Iterate over a series of CBOR tokens.
     #[cfg(feature = "half")]
     pub fn tokens<'a>(&'a mut self) -> crate::decode::Tokenizer<'a, 'b> {
@@ -481,6 +938,10 @@ impl<'b> Decoder<'b> {
     /// Skip over the current CBOR value.
     #[cfg(feature = "alloc")]
     pub fn skip(&mut self) -> Result<(), Error> {
+        if self.flags & LIMITS != 0 {
+            return self.skip_limited()
+        }
+
         // Unless we encounter indefinite-length arrays or maps inside of regular
         // maps or arrays we only need to count how many more CBOR items we need
         // to skip (initially starting with 1) or how many more break bytes we
@@ -552,11 +1013,16 @@ impl<'b> Decoder<'b> {
                     }
                 TAGGED ..= 0xdb => {
                     let p = self.pos;
-                    self.read().and_then(|n| self.unsigned(info_of(n), p))?;
-                    continue
+                    self.check_head()?;
+                    let t = self.read().and_then(|n| self.unsigned(info_of(n), p))?;
+                    if !self.is_string_ref_namespace(t) {
+                        continue
+                    }
+                    self.skip_string_refs()?
                 }
                 SIMPLE ..= 0xfb => {
                     let p = self.pos;
+                    self.check_head()?;
                     self.read().and_then(|n| self.unsigned(info_of(n), p))?;
                 }
                 BREAK => {
@@ -592,10 +1058,16 @@ impl<'b> Decoder<'b> {
 
     /// Skip over the current CBOR value.
     ///
-    /// Without feature `alloc`, skipping over maps or arrays that contain an
-    /// indefinite-length map or array will return an error.
+    /// Without feature `alloc` and unless a max. nesting depth is set (cf.
+    /// [`Limits`]), skipping over maps or arrays that contain an indefinite-length
+    /// map or array will return an error and the max. number of elements of
+    /// indefinite arrays and maps is not checked.
     #[cfg(not(feature = "alloc"))]
     pub fn skip(&mut self) -> Result<(), Error> {
+        if self.limits.depth != u32::MAX {
+            return self.skip_nested()
+        }
+
         let mut nrounds = 1u64; // number of iterations over array and map elements
         let mut irounds = 0u64; // number of indefinite iterations
 
@@ -627,11 +1099,13 @@ impl<'b> Decoder<'b> {
                     }
                 TAGGED ..= 0xdb => {
                     let p = self.pos;
+                    self.check_head()?;
                     self.read().and_then(|n| self.unsigned(info_of(n), p))?;
                     continue
                 }
                 SIMPLE ..= 0xfb => {
                     let p = self.pos;
+                    self.check_head()?;
                     self.read().and_then(|n| self.unsigned(info_of(n), p))?;
                 }
                 BREAK => {
@@ -648,6 +1122,484 @@ impl<'b> Decoder<'b> {
         Ok(())
     }
 
+    /// In strict mode, check the data item head at the current position.
+    ///
+    /// Incomplete heads are accepted as the subsequent decoding will fail
+    /// with a more appropriate error.
+    #[inline]
+    fn check_head(&self) -> Result<(), Error> {
+        if self.flags & STRICT == 0 {
+            return Ok(())
+        }
+        self.check_head_strict()
+    }
+
+    /// Check the data item head at the current position.
+    #[inline(never)]
+    fn check_head_strict(&self) -> Result<(), Error> {
+        let p = self.pos;
+        let Some(&b) = self.buf.get(p) else {
+            return Ok(())
+        };
+        let arg = |n: usize| self.buf.get(p + 1 .. p + 1 + n);
+        let ok = match (type_of(b), info_of(b)) {
+            (SIMPLE, n @ 0 ..= 19) => return Err(Error::unknown_simple(n).at(p)),
+            (SIMPLE, 24) => match arg(1) {
+                Some(&[n]) if n < 0x20 => {
+                    return Err(Error::not_well_formed().at(p).with_message("two-byte simple value below 32"))
+                }
+                Some(&[n]) => return Err(Error::unknown_simple(n).at(p)),
+                _ => true
+            }
+            (SIMPLE, 26) => {
+                if let Some(a) = arg(4).and_then(|a| <[u8; 4]>::try_from(a).ok()) {
+                    if crate::float::f16_bits(f32::from_be_bytes(a)).is_some() {
+                        return Err(Error::non_shortest_float().at(p))
+                    }
+                }
+                true
+            }
+            (SIMPLE, 27) => {
+                if let Some(a) = arg(8).and_then(|a| <[u8; 8]>::try_from(a).ok()) {
+                    let x = f64::from_be_bytes(a);
+                    if x.is_nan() || f64::from(x as f32) == x {
+                        return Err(Error::non_shortest_float().at(p))
+                    }
+                }
+                true
+            }
+            (SIMPLE, _) => true,
+            (BYTES | TEXT | ARRAY | MAP, 31) => {
+                return Err(Error::indefinite_length(self.type_of(b)?).at(p))
+            }
+            (_, 24) => arg(1).map(|a| a[0] >= 0x18).unwrap_or(true),
+            (_, 25) => arg(2).map(|a| a[0] != 0).unwrap_or(true),
+            (_, 26) => arg(4).map(|a| a[0 .. 2] != [0, 0]).unwrap_or(true),
+            (_, 27) => arg(8).map(|a| a[0 .. 4] != [0, 0, 0, 0]).unwrap_or(true),
+            _ => true
+        };
+        if ok {
+            Ok(())
+        } else {
+            Err(Error::non_minimal_int().at(p))
+        }
+    }
+
+    /// Check that the keys of the map at the current position, and of all
+    /// maps nested inside, are sorted and unique.
+    ///
+    /// The `len` entries of the map are scanned once with a non-strict
+    /// decoder and the encoded bytes of subsequent keys are compared. The
+    /// scanned range is remembered so that nested maps are not checked again
+    /// when they are decoded.
+    #[cfg(feature = "alloc")]
+    fn check_map_keys(&mut self, len: u64) -> Result<(), Error> {
+        struct Level<'b> {
+            /// The number of remaining items (two per map entry).
+            items: u64,
+            /// Is this a map?
+            map: bool,
+            /// The start position of the current map key.
+            key: usize,
+            /// The previous map key.
+            prev: Option<&'b [u8]>
+        }
+        let start = self.pos;
+        let mut d = Decoder::new(self.buf);
+        d.pos = start;
+        let mut stack = alloc::vec![Level { items: len.saturating_mul(2), map: true, key: start, prev: None }];
+        while let Some(level) = stack.last_mut() {
+            if level.items == 0 {
+                stack.pop();
+                continue
+            }
+            if level.map && level.items % 2 == 0 {
+                level.key = d.pos
+            } else if level.map {
+                let key = &self.buf[level.key .. d.pos];
+                if let Some(k) = level.prev {
+                    match k.cmp(key) {
+                        core::cmp::Ordering::Less    => {}
+                        core::cmp::Ordering::Equal   => return Err(self.duplicate_map_key(level.key)),
+                        core::cmp::Ordering::Greater => return Err(Error::unsorted_map_keys().at(level.key))
+                    }
+                }
+                level.prev = Some(key)
+            }
+            level.items -= 1;
+            while d.datatype()? == Type::Tag {
+                d.tag()?;
+            }
+            // Indefinite arrays and maps are skipped over as they are rejected
+            // by the strict decoder anyway.
+            match d.datatype()? {
+                Type::Array => {
+                    let n = d.array()?.unwrap_or(0);
+                    stack.push(Level { items: n, map: false, key: 0, prev: None })
+                }
+                Type::Map => {
+                    let n = d.map()?.unwrap_or(0);
+                    stack.push(Level { items: n.saturating_mul(2), map: true, key: 0, prev: None })
+                }
+                _ => d.skip()?
+            }
+        }
+        self.keys_checked = start .. d.pos;
+        Ok(())
+    }
+
+    /// Check that the keys of the map at the current position are sorted.
+    ///
+    /// The `len` entries of the map are scanned with a non-strict decoder
+    /// and the encoded bytes of subsequent keys are compared.
+    #[cfg(not(feature = "alloc"))]
+    fn check_map_keys(&mut self, len: u64) -> Result<(), Error> {
+        let mut d = Decoder::new(self.buf);
+        d.pos = self.pos;
+        let mut prev: Option<&'b [u8]> = None;
+        for _ in 0 .. len {
+            let p = d.pos;
+            d.skip()?;
+            let key = &self.buf[p .. d.pos];
+            if let Some(k) = prev {
+                match k.cmp(key) {
+                    core::cmp::Ordering::Less    => {}
+                    core::cmp::Ordering::Equal   => return Err(self.duplicate_map_key(p)),
+                    core::cmp::Ordering::Greater => return Err(Error::unsorted_map_keys().at(p))
+                }
+            }
+            prev = Some(key);
+            d.skip()?
+        }
+        Ok(())
+    }
+
+    /// Create a duplicate map key error for the key at position `p`.
+    ///
+    /// `Decode` impls which honour [`Decoder::rejects_duplicate_keys`] can use
+    /// this to report a key that occurred before. The error satisfies
+    /// [`Error::is_duplicate_map_key`]. With features `"alloc"` and `"half"`
+    /// its message shows the key in diagnostic notation.
+    pub fn duplicate_map_key(&self, p: usize) -> Error {
+        let e = Error::duplicate_map_key().at(p);
+        #[cfg(all(feature = "alloc", feature = "half"))]
+        {
+            let mut d = Decoder::new(self.buf);
+            d.pos = p;
+            if d.skip().is_ok() {
+                return e.with_message(crate::display(&self.buf[p .. d.pos]))
+            }
+        }
+        e
+    }
+
+    /// Skip over the current CBOR value while checking all limits.
+    ///
+    /// In contrast to the regular `skip` every array or map occupies a
+    /// stack entry to track the nesting depth.
+    #[cfg(feature = "alloc")]
+    fn skip_limited(&mut self) -> Result<(), Error> {
+        // Per open array or map the number of remaining items (for definite
+        // lengths), the number of items seen so far (for indefinite lengths)
+        // and the number of items per element (2 for maps, 1 for arrays).
+        let mut stack: alloc::vec::Vec<(Option<u64>, u64, u64)> = alloc::vec::Vec::new();
+        // Is the next item the content of a tag?
+        let mut tagged = false;
+        loop {
+            let p = self.pos;
+            let b = self.current()?;
+            if !core::mem::take(&mut tagged) && b != BREAK {
+                if let Some((None, n, k)) = stack.last_mut() {
+                    if *n % *k == 0 {
+                        let count = *n / *k + 1;
+                        self.check_next_item(count, p)?
+                    }
+                    *n += 1
+                }
+            }
+            match b {
+                UNSIGNED ..= 0x1b => { self.u64()?; }
+                SIGNED   ..= 0x3b => { self.int()?; }
+                BYTES    ..= 0x5f => { for v in self.bytes_iter()? { v?; } }
+                TEXT     ..= 0x7f => { for v in self.str_iter()? { v?; } }
+                b @ (ARRAY ..= 0xbf) => {
+                    let (len, k) = if type_of(b) == ARRAY {
+                        (self.array()?, 1)
+                    } else {
+                        (self.map()?.map(|n| n.saturating_mul(2)), 2)
+                    };
+                    self.enter().map_err(|e| e.at(p))?;
+                    if len != Some(0) {
+                        stack.push((len, 0, k));
+                        continue
+                    }
+                    self.leave()
+                }
+                TAGGED ..= 0xdb => {
+                    self.check_head()?;
+                    let t = self.read().and_then(|n| self.unsigned(info_of(n), p))?;
+                    if !self.is_string_ref_namespace(t) {
+                        tagged = true;
+                        continue
+                    }
+                    self.skip_string_refs()?
+                }
+                SIMPLE ..= 0xfb => {
+                    self.check_head()?;
+                    self.read().and_then(|n| self.unsigned(info_of(n), p))?;
+                }
+                BREAK => {
+                    self.read()?;
+                    if let Some((None, ..)) = stack.last() {
+                        stack.pop();
+                        self.leave()
+                    } else {
+                        return Err(Error::type_mismatch(Type::Break).at(p).with_message("unexpected break"))
+                    }
+                }
+                other => return Err(Error::type_mismatch(self.type_of(other)?)
+                    .at(p)
+                    .with_message("unknown type"))
+            }
+            // An item is complete, account for it in the enclosing array or map.
+            loop {
+                match stack.last_mut() {
+                    None => return Ok(()),
+                    Some((Some(n), ..)) => {
+                        *n -= 1;
+                        if *n > 0 {
+                            break
+                        }
+                        stack.pop();
+                        self.leave()
+                    }
+                    Some((None, ..)) => break
+                }
+            }
+        }
+    }
+
+    /// Skip over the current CBOR value while checking all limits.
+    ///
+    /// Without a stack of nested arrays and maps this recurses into their
+    /// elements and is only used if a max. nesting depth bounds the recursion.
+    #[cfg(not(feature = "alloc"))]
+    fn skip_nested(&mut self) -> Result<(), Error> {
+        let mut p = self.pos;
+        while TAGGED == type_of(self.current()?) {
+            self.check_head()?;
+            self.read().and_then(|n| self.unsigned(info_of(n), p))?;
+            p = self.pos
+        }
+        let (len, k) = match self.current()? {
+            UNSIGNED ..= 0x1b => { self.u64()?; return Ok(()) }
+            SIGNED   ..= 0x3b => { self.int()?; return Ok(()) }
+            BYTES    ..= 0x5f => { for v in self.bytes_iter()? { v?; } return Ok(()) }
+            TEXT     ..= 0x7f => { for v in self.str_iter()? { v?; } return Ok(()) }
+            ARRAY    ..= 0x9f => (self.array()?, 1),
+            MAP      ..= 0xbf => (self.map()?.map(|n| n.saturating_mul(2)), 2),
+            SIMPLE   ..= 0xfb => {
+                self.check_head()?;
+                self.read().and_then(|n| self.unsigned(info_of(n), p))?;
+                return Ok(())
+            }
+            other => return Err(Error::type_mismatch(self.type_of(other)?)
+                .at(p)
+                .with_message("unknown type"))
+        };
+        self.enter().map_err(|e| e.at(p))?;
+        let r = self.skip_elements(len, k);
+        self.leave();
+        r
+    }
+
+    /// Skip over the elements of an array (`k = 1`) or map (`k = 2`).
+    #[cfg(not(feature = "alloc"))]
+    fn skip_elements(&mut self, len: Option<u64>, k: u64) -> Result<(), Error> {
+        if let Some(n) = len {
+            for _ in 0 .. n {
+                self.skip_nested()?
+            }
+            return Ok(())
+        }
+        let mut n = 0;
+        loop {
+            let p = self.pos;
+            if self.current()? == BREAK {
+                self.read()?;
+                return Ok(())
+            }
+            if n % k == 0 {
+                self.check_next_item(n / k + 1, p)?
+            }
+            n += 1;
+            self.skip_nested()?
+        }
+    }
+
+    /// Begin a new string reference namespace.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn begin_string_refs(&mut self) {
+        Rc::make_mut(self.strings.get_or_insert_with(Rc::default)).push(alloc::vec::Vec::new())
+    }
+
+    /// End the current string reference namespace.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn end_string_refs(&mut self) {
+        if let Some(t) = self.strings.as_mut().map(Rc::make_mut) {
+            t.pop();
+            if t.is_empty() {
+                self.strings = None
+            }
+        }
+    }
+
+    /// Is `t` the tag of a nested string reference namespace?
+    #[cfg(feature = "alloc")]
+    fn is_string_ref_namespace(&self, t: u64) -> bool {
+        self.strings.is_some() && Tag::new(t) == IanaTag::StringRefNamespace.tag()
+    }
+
+    /// Skip over the content of a nested string reference namespace.
+    #[cfg(feature = "alloc")]
+    fn skip_string_refs(&mut self) -> Result<(), Error> {
+        self.enter()?;
+        self.begin_string_refs();
+        let r = self.skip();
+        self.end_string_refs();
+        self.leave();
+        r
+    }
+
+    /// Resolve a string reference (tag 25) if one is present.
+    ///
+    /// Returns `None` if no string reference namespace is active or the
+    /// current item is not a string reference.
+    #[cfg(feature = "alloc")]
+    #[inline(never)]
+    fn string_ref(&mut self, text: bool) -> Result<Option<&'b [u8]>, Error> {
+        if self.strings.is_none() || TAGGED != type_of(self.current()?) {
+            return Ok(None)
+        }
+        let p = self.pos;
+        if self.tag()? != IanaTag::StringRef.tag() {
+            self.pos = p;
+            return Ok(None)
+        }
+        let i = self.u64()?;
+        let s = usize::try_from(i).ok().and_then(|i| self.strings.as_ref()?.last()?.get(i).copied());
+        match s {
+            Some((t, b)) if t == text => Ok(Some(b)),
+            Some((true, _))  => Err(Error::type_mismatch(Type::String).with_message("expected bytes").at(p)),
+            Some((false, _)) => Err(Error::type_mismatch(Type::Bytes).with_message("expected text").at(p)),
+            None => Err(Error::message("unknown string reference").at(p))
+        }
+    }
+
+    /// Add a string to the current string reference namespace if it is
+    /// long enough.
+    #[cfg(feature = "alloc")]
+    #[inline(never)]
+    fn add_string(&mut self, text: bool, b: &'b [u8]) {
+        if let Some(t) = self.strings.as_mut().and_then(|t| Rc::make_mut(t).last_mut()) {
+            if b.len() >= crate::data::min_string_ref_len(t.len() as u64) {
+                t.push((text, b))
+            }
+        }
+    }
+
+    /// Check the length of an array or map against the limits.
+    #[inline]
+    fn check_items(&mut self, n: u64, p: usize) -> Result<(), Error> {
+        if self.flags & LIMITS == 0 {
+            return Ok(())
+        }
+        if n > self.limits.items {
+            return Err(Error::limit_exceeded("number of items", self.limits.items).at(p))
+        }
+        self.check_alloc(n, p)
+    }
+
+    /// Check the number of elements of an indefinite array or map.
+    ///
+    /// To be called with the running count for every additional element.
+    #[inline]
+    fn check_next_item(&mut self, count: u64, p: usize) -> Result<(), Error> {
+        if self.flags & LIMITS == 0 {
+            return Ok(())
+        }
+        if count > self.limits.items {
+            return Err(Error::limit_exceeded("number of items", self.limits.items).at(p))
+        }
+        self.check_alloc(1, p)
+    }
+
+    /// Check the length of a byte or text string against the limits.
+    #[inline]
+    fn check_len(&mut self, n: u64, p: usize) -> Result<(), Error> {
+        if self.flags & LIMITS == 0 {
+            return Ok(())
+        }
+        if n > self.limits.len {
+            return Err(Error::limit_exceeded("length", self.limits.len).at(p))
+        }
+        self.check_alloc(n, p)
+    }
+
+    /// Check the total length of indefinite string chunks.
+    #[inline]
+    fn check_chunks(&self, total: u64, p: usize) -> Result<(), Error> {
+        if self.flags & LIMITS == 0 {
+            return Ok(())
+        }
+        if total > self.limits.len {
+            return Err(Error::limit_exceeded("length", self.limits.len).at(p))
+        }
+        Ok(())
+    }
+
+    /// Apply `f` to a decoder for the given bytes.
+    ///
+    /// The nested decoder inherits the settings, limits and nesting depth of
+    /// this decoder. Afterwards its nesting depth and total length are taken
+    /// over, so that allocations of the nested decoder count towards the
+    /// limits of this one.
+    pub(crate) fn with_nested<'c, T, F>(&mut self, bytes: &'c [u8], f: F) -> Result<T, Error>
+    where
+        F: FnOnce(&mut Decoder<'c>) -> Result<T, Error>
+    {
+        let mut n = self.nested(bytes);
+        let r = f(&mut n);
+        self.depth = n.depth;
+        self.alloc = n.alloc;
+        r
+    }
+
+    /// Create a decoder for the given bytes which inherits the settings,
+    /// limits and nesting depth of this decoder.
+    fn nested<'c>(&self, bytes: &'c [u8]) -> Decoder<'c> {
+        Decoder {
+            buf: bytes,
+            pos: 0,
+            flags: self.flags,
+            limits: self.limits,
+            depth: self.depth,
+            alloc: self.alloc,
+            keys_checked: 0 .. 0,
+            #[cfg(feature = "alloc")]
+            strings: None
+        }
+    }
+
+    /// Add `n` to the sum of all lengths and check it against the limits.
+    fn check_alloc(&mut self, n: u64, p: usize) -> Result<(), Error> {
+        self.alloc = self.alloc.saturating_add(n);
+        if self.alloc > self.limits.alloc {
+            return Err(Error::limit_exceeded("total length", self.limits.alloc).at(p))
+        }
+        Ok(())
+    }
+
     /// Decode a `u64` value beginning with `b`.
     pub(crate) fn unsigned(&mut self, b: u8, p: usize) -> Result<u64, Error> {
         match b {
@@ -662,6 +1614,39 @@ impl<'b> Decoder<'b> {
         }
     }
 
+    /// Decode a positive (tag 2) or negative (tag 3) bignum of at most 16 bytes.
+    ///
+    /// Returns the sign and the magnitude, i.e. `n` for tag 2 and `-1 - n` for
+    /// tag 3.
+    fn bignum(&mut self, p: usize) -> Result<(bool, u128), Error> {
+        let (neg, b) = self.bignum_bytes(p)?;
+        if b.len() > 16 {
+            return Err(Error::bignum_overflow().with_message("when converting to u128").at(p))
+        }
+        let mut a = [0; 16];
+        a[16 - b.len() ..].copy_from_slice(b);
+        Ok((neg, u128::from_be_bytes(a)))
+    }
+
+    /// Decode a positive (tag 2) or negative (tag 3) bignum.
+    ///
+    /// Returns the sign and the big-endian bytes of the magnitude without
+    /// leading zeros. In strict mode, bignums must be in preferred serialization.
+    pub(crate) fn bignum_bytes(&mut self, p: usize) -> Result<(bool, &'b [u8]), Error> {
+        let t = self.tag()?;
+        let neg = match IanaTag::try_from(t) {
+            Ok(IanaTag::PosBignum) => false,
+            Ok(IanaTag::NegBignum) => true,
+            _ => return Err(Error::tag_mismatch(t).with_message("expected tag 2 or 3").at(p))
+        };
+        let b = self.bytes()?;
+        let i = b.iter().position(|x| *x != 0).unwrap_or(b.len());
+        if self.is_strict() && (i > 0 || b.len() <= 8) {
+            return Err(Error::non_minimal_int().with_message("bignum not in preferred serialization").at(p))
+        }
+        Ok((neg, &b[i ..]))
+    }
+
     /// Get the byte at the current position.
     fn current(&self) -> Result<u8, Error> {
         if let Some(b) = self.buf.get(self.pos) {
@@ -744,7 +1729,9 @@ impl<'b> Decoder<'b> {
 #[derive(Debug)]
 pub struct BytesIter<'a, 'b> {
     decoder: &'a mut Decoder<'b>,
-    len: Option<usize>
+    len: Option<usize>,
+    total: u64,
+    pos: usize
 }
 
 impl<'a, 'b> Iterator for BytesIter<'a, 'b> {
@@ -754,13 +1741,22 @@ impl<'a, 'b> Iterator for BytesIter<'a, 'b> {
         match self.len {
             None => match self.decoder.current() {
                 Ok(BREAK) => self.decoder.read().map(|_| None).transpose(),
-                Ok(_)     => Some(self.decoder.bytes()),
+                Ok(_)     => Some(self.decoder.definite_bytes().and_then(|b| {
+                    self.total = self.total.saturating_add(b.len() as u64);
+                    self.decoder.check_chunks(self.total, self.pos)?;
+                    Ok(b)
+                })),
                 Err(e)    => Some(Err(e))
             }
             Some(0) => None,
             Some(n) => {
                 self.len = Some(0);
-                Some(self.decoder.read_slice(n))
+                let b = self.decoder.read_slice(n);
+                #[cfg(feature = "alloc")]
+                if let Ok(b) = b {
+                    self.decoder.add_string(false, b)
+                }
+                Some(b)
             }
         }
     }
@@ -773,6 +1769,7 @@ impl<'a, 'b> Iterator for BytesIter<'a, 'b> {
 pub struct StrIter<'a, 'b> {
     decoder: &'a mut Decoder<'b>,
     len: Option<usize>,
+    total: u64,
     pos: usize
 }
 
@@ -783,13 +1780,22 @@ impl<'a, 'b> Iterator for StrIter<'a, 'b> {
         match self.len {
             None => match self.decoder.current() {
                 Ok(BREAK) => self.decoder.read().map(|_| None).transpose(),
-                Ok(_)     => Some(self.decoder.str()),
+                Ok(_)     => Some(self.decoder.definite_str().and_then(|s| {
+                    self.total = self.total.saturating_add(s.len() as u64);
+                    self.decoder.check_chunks(self.total, self.pos)?;
+                    Ok(s)
+                })),
                 Err(e)    => Some(Err(e))
             }
             Some(0) => None,
             Some(n) => {
                 self.len = Some(0);
-                Some(self.decoder.read_slice(n).and_then(|d| str::from_utf8(d).map_err(|e| Error::utf8(e).at(self.pos))))
+                let s = self.decoder.read_slice(n).and_then(|d| str::from_utf8(d).map_err(|e| Error::utf8(e).at(self.pos)));
+                #[cfg(feature = "alloc")]
+                if let Ok(s) = s {
+                    self.decoder.add_string(true, s.as_bytes())
+                }
+                Some(s)
             }
         }
     }
@@ -802,6 +1808,7 @@ impl<'a, 'b> Iterator for StrIter<'a, 'b> {
 pub struct ArrayIter<'a, 'b, T> {
     decoder: &'a mut Decoder<'b>,
     len: Option<u64>,
+    count: u64,
     _mark: marker::PhantomData<fn(T)>
 }
 
@@ -812,13 +1819,22 @@ impl<'a, 'b, T: Decode<'b, ()>> Iterator for ArrayIter<'a, 'b, T> {
         match self.len {
             None => match self.decoder.current() {
                 Ok(BREAK) => self.decoder.read().map(|_| None).transpose(),
-                Ok(_)     => Some(T::decode(self.decoder, &mut ())),
+                Ok(_)     => {
+                    self.count += 1;
+                    let i = self.count - 1;
+                    let p = self.decoder.pos;
+                    Some(self.decoder.check_next_item(self.count, p).and_then(|()| {
+                        in_index(T::decode(self.decoder, &mut ()), i)
+                    }))
+                }
                 Err(e)    => Some(Err(e))
             }
             Some(0) => None,
             Some(n) => {
                 self.len = Some(n - 1);
-                Some(T::decode(self.decoder, &mut ()))
+                self.count += 1;
+                let i = self.count - 1;
+                Some(in_index(T::decode(self.decoder, &mut ()), i))
             }
         }
     }
@@ -832,6 +1848,7 @@ pub struct ArrayIterWithCtx<'a, 'b, C, T> {
     decoder: &'a mut Decoder<'b>,
     ctx: &'a mut C,
     len: Option<u64>,
+    count: u64,
     _mark: marker::PhantomData<fn(T)>
 }
 
@@ -842,13 +1859,22 @@ impl<'a, 'b, C, T: Decode<'b, C>> Iterator for ArrayIterWithCtx<'a, 'b, C, T> {
         match self.len {
             None => match self.decoder.current() {
                 Ok(BREAK) => self.decoder.read().map(|_| None).transpose(),
-                Ok(_)     => Some(T::decode(self.decoder, self.ctx)),
+                Ok(_)     => {
+                    self.count += 1;
+                    let i = self.count - 1;
+                    let p = self.decoder.pos;
+                    Some(self.decoder.check_next_item(self.count, p).and_then(|()| {
+                        in_index(T::decode(self.decoder, self.ctx), i)
+                    }))
+                }
                 Err(e)    => Some(Err(e))
             }
             Some(0) => None,
             Some(n) => {
                 self.len = Some(n - 1);
-                Some(T::decode(self.decoder, self.ctx))
+                self.count += 1;
+                let i = self.count - 1;
+                Some(in_index(T::decode(self.decoder, self.ctx), i))
             }
         }
     }
@@ -861,6 +1887,7 @@ impl<'a, 'b, C, T: Decode<'b, C>> Iterator for ArrayIterWithCtx<'a, 'b, C, T> {
 pub struct MapIter<'a, 'b, K, V> {
     decoder: &'a mut Decoder<'b>,
     len: Option<u64>,
+    count: u64,
     _mark: marker::PhantomData<fn(K, V)>
 }
 
@@ -877,12 +1904,20 @@ where
             K: Decode<'b, ()>,
             V: Decode<'b, ()>
         {
-            Ok((K::decode(d, &mut ())?, V::decode(d, &mut ())?))
+            let p = d.pos;
+            let k = K::decode(d, &mut ())?;
+            let key = d.buf.get(p .. d.pos).unwrap_or_default();
+            let v = V::decode(d, &mut ()).map_err(|e| e.in_key(key))?;
+            Ok((k, v))
         }
         match self.len {
             None => match self.decoder.current() {
                 Ok(BREAK) => self.decoder.read().map(|_| None).transpose(),
-                Ok(_)  => Some(pair(self.decoder)),
+                Ok(_)  => {
+                    self.count += 1;
+                    let p = self.decoder.pos;
+                    Some(self.decoder.check_next_item(self.count, p).and_then(|()| pair(self.decoder)))
+                }
                 Err(e) => Some(Err(e))
             }
             Some(0) => None,
@@ -902,9 +1937,17 @@ pub struct MapIterWithCtx<'a, 'b, C, K, V> {
     decoder: &'a mut Decoder<'b>,
     ctx: &'a mut C,
     len: Option<u64>,
+    count: u64,
     _mark: marker::PhantomData<fn(K, V)>
 }
 
+#[cfg(feature = "alloc")]
+impl<'b, C, K, V> MapIterWithCtx<'_, 'b, C, K, V> {
+    pub(crate) fn decoder(&self) -> &Decoder<'b> {
+        self.decoder
+    }
+}
+
 impl<'a, 'b, C, K, V> Iterator for MapIterWithCtx<'a, 'b, C, K, V>
 where
     K: Decode<'b, C>,
@@ -918,12 +1961,20 @@ where
             K: Decode<'b, C>,
             V: Decode<'b, C>
         {
-            Ok((K::decode(d, ctx)?, V::decode(d, ctx)?))
+            let p = d.pos;
+            let k = K::decode(d, ctx)?;
+            let key = d.buf.get(p .. d.pos).unwrap_or_default();
+            let v = V::decode(d, ctx).map_err(|e| e.in_key(key))?;
+            Ok((k, v))
         }
         match self.len {
             None => match self.decoder.current() {
                 Ok(BREAK) => self.decoder.read().map(|_| None).transpose(),
-                Ok(_)  => Some(pair(self.decoder, self.ctx)),
+                Ok(_)  => {
+                    self.count += 1;
+                    let p = self.decoder.pos;
+                    Some(self.decoder.check_next_item(self.count, p).and_then(|()| pair(self.decoder, self.ctx)))
+                }
                 Err(e) => Some(Err(e))
             }
             Some(0) => None,
@@ -935,13 +1986,61 @@ where
     }
 }
 
+/// An iterator over a sequence of top-level data items.
+///
+/// Returned from [`Decoder::sequence`].
+#[derive(Debug)]
+pub struct SequenceIter<'a, 'b, T> {
+    decoder: &'a mut Decoder<'b>,
+    done: bool,
+    _mark: marker::PhantomData<fn(T)>
+}
+
+impl<'a, 'b, T: Decode<'b, ()>> Iterator for SequenceIter<'a, 'b, T> {
+    type Item = Result<T, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done || self.decoder.pos >= self.decoder.buf.len() {
+            return None
+        }
+        let x = T::decode(self.decoder, &mut ());
+        self.done = x.is_err();
+        Some(x)
+    }
+}
+
+/// An iterator over a sequence of top-level data items.
+///
+/// Returned from [`Decoder::sequence_with`].
+#[derive(Debug)]
+pub struct SequenceIterWithCtx<'a, 'b, C, T> {
+    decoder: &'a mut Decoder<'b>,
+    ctx: &'a mut C,
+    done: bool,
+    _mark: marker::PhantomData<fn(T)>
+}
+
+impl<'a, 'b, C, T: Decode<'b, C>> Iterator for SequenceIterWithCtx<'a, 'b, C, T> {
+    type Item = Result<T, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done || self.decoder.pos >= self.decoder.buf.len() {
+            return None
+        }
+        let x = T::decode(self.decoder, self.ctx);
+        self.done = x.is_err();
+        Some(x)
+    }
+}
+
 /// A decoding probe to to look ahead what comes next.
 ///
 /// A `Probe` derefs to [`Decoder`] and thus can be used like one without
 /// affecting the decoder from which it was created.
 //
-// The current implementation just clones `Decoder` as it is very cheap
-// to do so. `Probe` is nevertheless introduced to discourage use of
+// The current implementation just clones `Decoder` as it is cheap to do
+// so: apart from plain values, it only holds reference counted string
+// tables which are copied only if the probe adds strings to them. `Probe` is nevertheless introduced to discourage use of
 // `Decoder::clone` in client code for this purpose so that it stays
 // independent of the current implementation.
 // With a more heavyweight `Decoder`, `Probe` could only store a reference
@@ -986,3 +2085,15 @@ where
 {
     val.try_into().map_err(|_| Error::overflow(val.into()).at(pos).with_message(msg))
 }
+
+/// Add the index of an array element to the decoding context of its error.
+///
+/// The error is updated in place, so that an `Ok` value is passed through
+/// without being moved.
+#[inline]
+pub(crate) fn in_index<T>(mut r: Result<T, Error>, i: u64) -> Result<T, Error> {
+    if let Err(e) = &mut r {
+        e.add_index(i)
+    }
+    r
+}
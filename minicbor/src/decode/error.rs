@@ -2,11 +2,14 @@ use core::{fmt, str};
 use crate::data::{Tag, Type};
 
 #[cfg(feature = "alloc")]
-use alloc::string::ToString;
+use alloc::string::{String, ToString};
 
 #[cfg(feature = "alloc")]
 use alloc::boxed::Box;
 
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
 /// Decoding error.
 #[derive(Debug)]
 pub struct Error {
@@ -14,8 +17,17 @@ pub struct Error {
     pos: Option<usize>,
     #[cfg(not(feature = "alloc"))]
     msg: &'static str,
+    /// The message and decoding context, boxed to keep `Error` small.
     #[cfg(feature = "alloc")]
-    msg: alloc::string::String
+    msg: Option<Box<Message>>
+}
+
+/// The message and decoding context of an [`Error`].
+#[cfg(feature = "alloc")]
+#[derive(Debug, Default)]
+struct Message {
+    text: String,
+    path: Vec<Frame>
 }
 
 impl Error {
@@ -66,11 +78,12 @@ impl Error {
     /// formatted strings. Otherwise only a `&'static str` can be used as a
     /// message.
     #[cfg(feature = "alloc")]
+    #[cold]
     pub fn message<T: fmt::Display>(msg: T) -> Self {
         Error {
             err: ErrorImpl::Message,
             pos: None,
-            msg: msg.to_string()
+            msg: Some(Box::new(Message { text: msg.to_string(), path: Vec::new() }))
         }
     }
 
@@ -124,7 +137,95 @@ impl Error {
 
     pub(crate) fn overflow(item: u64) -> Self {
         Error {
-            err: ErrorImpl::Overflow(item),
+            err: ErrorImpl::Overflow(Some(item)),
+            pos: None,
+            msg: Default::default()
+        }
+    }
+
+    pub(crate) fn bignum_overflow() -> Self {
+        Error {
+            err: ErrorImpl::Overflow(None),
+            pos: None,
+            msg: Default::default()
+        }
+    }
+
+    pub(crate) fn non_minimal_int() -> Self {
+        Error {
+            err: ErrorImpl::NonMinimalInt,
+            pos: None,
+            msg: Default::default()
+        }
+    }
+
+    pub(crate) fn non_shortest_float() -> Self {
+        Error {
+            err: ErrorImpl::NonShortestFloat,
+            pos: None,
+            msg: Default::default()
+        }
+    }
+
+    pub(crate) fn indefinite_length(ty: Type) -> Self {
+        Error {
+            err: ErrorImpl::IndefiniteLength(ty),
+            pos: None,
+            msg: Default::default()
+        }
+    }
+
+    pub(crate) fn unsorted_map_keys() -> Self {
+        Error {
+            err: ErrorImpl::UnsortedMapKeys,
+            pos: None,
+            msg: Default::default()
+        }
+    }
+
+    pub(crate) fn duplicate_map_key() -> Self {
+        Error {
+            err: ErrorImpl::DuplicateMapKey,
+            pos: None,
+            msg: Default::default()
+        }
+    }
+
+    pub(crate) fn unknown_simple(n: u8) -> Self {
+        Error {
+            err: ErrorImpl::UnknownSimple(n),
+            pos: None,
+            msg: Default::default()
+        }
+    }
+
+    pub(crate) fn not_well_formed() -> Self {
+        Error {
+            err: ErrorImpl::NotWellFormed,
+            pos: None,
+            msg: Default::default()
+        }
+    }
+
+    pub(crate) fn limit_exceeded(what: &'static str, max: u64) -> Self {
+        Error {
+            err: ErrorImpl::LimitExceeded(what, max),
+            pos: None,
+            msg: Default::default()
+        }
+    }
+
+    pub(crate) fn not_found() -> Self {
+        Error {
+            err: ErrorImpl::NotFound,
+            pos: None,
+            msg: Default::default()
+        }
+    }
+
+    pub(crate) fn trailing_data() -> Self {
+        Error {
+            err: ErrorImpl::TrailingData,
             pos: None,
             msg: Default::default()
         }
@@ -153,11 +254,100 @@ impl Error {
     /// formatted strings. Otherwise only a `&'static str` can be used as a
     /// message.
     #[cfg(feature = "alloc")]
+    #[cold]
     pub fn with_message<T: fmt::Display>(mut self, msg: T) -> Self {
-        self.msg = msg.to_string();
+        self.msg.get_or_insert_with(Box::default).text = msg.to_string();
+        self
+    }
+
+    /// Add a frame to the decoding context of this error.
+    ///
+    /// As an error propagates outwards, every enclosing frame is added after
+    /// the existing ones (cf. [`Error::path`]).
+    ///
+    /// *Requires feature* `"alloc"`.
+    #[cfg(feature = "alloc")]
+    #[cold]
+    pub fn with_frame(mut self, frame: Frame) -> Self {
+        self.msg.get_or_insert_with(Box::default).path.push(frame);
+        self
+    }
+
+    /// The decoding context of this error, innermost frame first.
+    ///
+    /// Derived `Decode` impls add the type and field and the collection
+    /// impls add array indices and map keys when an error passes through
+    /// them. The path is shown in front of the error message, outermost frame
+    /// first, e.g. `Config.servers[2].port (index 1): unexpected type ...`.
+    ///
+    /// *Requires feature* `"alloc"`.
+    #[cfg(feature = "alloc")]
+    pub fn path(&self) -> &[Frame] {
+        self.msg.as_ref().map(|m| m.path.as_slice()).unwrap_or_default()
+    }
+
+    /// Add the type with the given name to the decoding context.
+    #[doc(hidden)]
+    #[cfg(not(feature = "alloc"))]
+    pub fn in_type(self, _: &'static str) -> Self {
+        self
+    }
+
+    /// Add the type with the given name to the decoding context.
+    #[doc(hidden)]
+    #[cfg(feature = "alloc")]
+    pub fn in_type(self, name: &'static str) -> Self {
+        self.with_frame(Frame::Type(name))
+    }
+
+    /// Add the field with the given name and index to the decoding context.
+    #[doc(hidden)]
+    #[cfg(not(feature = "alloc"))]
+    pub fn in_field(self, _: &'static str, _: u32) -> Self {
         self
     }
 
+    /// Add the field with the given name and index to the decoding context.
+    #[doc(hidden)]
+    #[cfg(feature = "alloc")]
+    #[cold]
+    pub fn in_field(self, name: &'static str, idx: u32) -> Self {
+        self.with_frame(Frame::Field(name, idx))
+    }
+
+    /// Add the array element index to the decoding context.
+    #[cfg(not(feature = "alloc"))]
+    pub(crate) fn add_index(&mut self, _: u64) {}
+
+    /// Add the array element index to the decoding context.
+    #[cfg(feature = "alloc")]
+    #[cold]
+    pub(crate) fn add_index(&mut self, i: u64) {
+        self.msg.get_or_insert_with(Box::default).path.push(Frame::Index(i))
+    }
+
+    /// Add the map key, given as encoded bytes, to the decoding context.
+    #[cfg(not(feature = "alloc"))]
+    pub(crate) fn in_key(self, _: &[u8]) -> Self {
+        self
+    }
+
+    /// Add the map key, given as encoded bytes, to the decoding context.
+    #[cfg(feature = "alloc")]
+    #[cold]
+    pub(crate) fn in_key(self, key: &[u8]) -> Self {
+        use crate::decode::{Decoder, Segment};
+        let mut d = Decoder::new(key);
+        let k = match d.datatype() {
+            Ok(Type::String) => d.str().map(|s| Segment::Str(s).to_string()),
+            Ok(Type::Bytes)  => d.bytes().map(|b| Segment::Bytes(b).to_string()),
+            Ok(Type::U8 | Type::U16 | Type::U32 | Type::U64 | Type::I8 | Type::I16 | Type::I32 | Type::I64 | Type::Int) =>
+                d.int().map(|i| i.to_string()),
+            _ => Ok(String::from(".."))
+        };
+        self.with_frame(Frame::Key(k.unwrap_or_else(|_| String::from(".."))))
+    }
+
     pub fn is_end_of_input(&self) -> bool {
         matches!(self.err, ErrorImpl::EndOfInput)
     }
@@ -170,10 +360,88 @@ impl Error {
         matches!(self.err, ErrorImpl::TagMismatch(_))
     }
 
+    pub fn is_overflow(&self) -> bool {
+        matches!(self.err, ErrorImpl::Overflow(_))
+    }
+
     pub fn is_message(&self) -> bool {
         matches!(self.err, ErrorImpl::Message)
     }
 
+    /// An integer, length or tag was not encoded in its shortest form.
+    ///
+    /// Only produced by a strict [`Decoder`](crate::Decoder).
+    pub fn is_non_minimal_int(&self) -> bool {
+        matches!(self.err, ErrorImpl::NonMinimalInt)
+    }
+
+    /// A floating point value was not encoded in its shortest form.
+    ///
+    /// Only produced by a strict [`Decoder`](crate::Decoder).
+    pub fn is_non_shortest_float(&self) -> bool {
+        matches!(self.err, ErrorImpl::NonShortestFloat)
+    }
+
+    /// An indefinite-length item was encountered.
+    ///
+    /// Only produced by a strict [`Decoder`](crate::Decoder).
+    pub fn is_indefinite_length(&self) -> bool {
+        matches!(self.err, ErrorImpl::IndefiniteLength(_))
+    }
+
+    /// The keys of a map are not sorted.
+    ///
+    /// Only produced by a strict [`Decoder`](crate::Decoder).
+    pub fn is_unsorted_map_keys(&self) -> bool {
+        matches!(self.err, ErrorImpl::UnsortedMapKeys)
+    }
+
+    /// A map contains the same key more than once.
+    ///
+    /// Only produced by a strict [`Decoder`](crate::Decoder) or one which
+    /// [rejects duplicate keys](crate::Decoder::set_reject_duplicate_keys).
+    pub fn is_duplicate_map_key(&self) -> bool {
+        matches!(self.err, ErrorImpl::DuplicateMapKey)
+    }
+
+    /// An unassigned simple value was encountered.
+    ///
+    /// Only produced by a strict [`Decoder`](crate::Decoder).
+    pub fn is_unknown_simple(&self) -> bool {
+        matches!(self.err, ErrorImpl::UnknownSimple(_))
+    }
+
+    /// The input is not well-formed CBOR.
+    ///
+    /// Only produced by a strict [`Decoder`](crate::Decoder).
+    pub fn is_not_well_formed(&self) -> bool {
+        matches!(self.err, ErrorImpl::NotWellFormed)
+    }
+
+    /// A [`Limits`](crate::decode::Limits) value has been exceeded.
+    pub fn is_limit_exceeded(&self) -> bool {
+        matches!(self.err, ErrorImpl::LimitExceeded(..))
+    }
+
+    /// A path segment did not match any array element or map entry.
+    ///
+    /// Only produced by [`Decoder::query`](crate::Decoder::query) and
+    /// [`Decoder::query_span`](crate::Decoder::query_span).
+    pub fn is_not_found(&self) -> bool {
+        matches!(self.err, ErrorImpl::NotFound)
+    }
+
+    /// Bytes remained after the end of a data item.
+    ///
+    /// Produced by [`decode_exact`](crate::decode_exact), [`Raw::new`] and
+    /// when decoding an [`Embedded`] value.
+    ///
+    /// [`Raw::new`]: crate::data::Raw::new
+    /// [`Embedded`]: crate::data::Embedded
+    pub fn is_trailing_data(&self) -> bool {
+        matches!(self.err, ErrorImpl::TrailingData)
+    }
+
     /// Byte index of the input at which the error occurred.
     pub fn position(&self) -> Option<usize> {
         self.pos
@@ -195,6 +463,50 @@ impl Error {
     }
 }
 
+/// A frame of the decoding context of an [`Error`].
+///
+/// *Requires feature* `"alloc"`.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Frame {
+    /// A type (or enum variant) with the given name.
+    Type(&'static str),
+    /// A field with the given name and CBOR index.
+    Field(&'static str, u32),
+    /// An array element with the given index.
+    Index(u64),
+    /// A map value with the given key in diagnostic notation.
+    Key(String)
+}
+
+/// Displays a decoding context, e.g. `Config.servers[2].port (index 1)`.
+///
+/// The frames are given innermost first and shown outermost first. Only the
+/// outermost type name is shown.
+#[cfg(feature = "alloc")]
+struct Path<'a>(&'a [Frame]);
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for Path<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, frame) in self.0.iter().rev().enumerate() {
+            match frame {
+                Frame::Type(t) if i == 0 => f.write_str(t)?,
+                Frame::Type(_)           => {}
+                Frame::Field(n, _) if i == 0 => f.write_str(n)?,
+                Frame::Field(n, _)       => write!(f, ".{n}")?,
+                Frame::Index(n)          => write!(f, "[{n}]")?,
+                Frame::Key(k)            => write!(f, "[{k}]")?
+            }
+        }
+        if let Some(Frame::Field(_, i)) = self.0.first() {
+            write!(f, " (index {i})")?
+        }
+        Ok(())
+    }
+}
+
 /// Internal error representation.
 #[derive(Debug)]
 enum ErrorImpl {
@@ -204,8 +516,8 @@ enum ErrorImpl {
     InvalidChar(u32),
     /// Decoding a string failed because it is invalid UTF-8.
     Utf8(str::Utf8Error),
-    /// A numeric value exceeds its value range.
-    Overflow(u64),
+    /// A numeric value exceeds its value range (`None` if it is a bignum).
+    Overflow(Option<u64>),
     /// An unexpected type was encountered.
     TypeMismatch(Type),
     /// An unexpected tag was encountered.
@@ -214,6 +526,26 @@ enum ErrorImpl {
     UnknownVariant(u32),
     /// A value was missing at the specified index.
     MissingValue(u32),
+    /// An integer, length or tag is not encoded in its shortest form.
+    NonMinimalInt,
+    /// A floating point value is not encoded in its shortest form.
+    NonShortestFloat,
+    /// An indefinite-length item was encountered.
+    IndefiniteLength(Type),
+    /// The keys of a map are not sorted.
+    UnsortedMapKeys,
+    /// A map key occurs more than once.
+    DuplicateMapKey,
+    /// An unassigned simple value was encountered.
+    UnknownSimple(u8),
+    /// The input is not well-formed.
+    NotWellFormed,
+    /// A decoding limit has been exceeded.
+    LimitExceeded(&'static str, u64),
+    /// A path segment was not found.
+    NotFound,
+    /// Unexpected bytes after the end of a data item.
+    TrailingData,
     /// Generic error message.
     Message,
     /// Custom error.
@@ -223,16 +555,24 @@ enum ErrorImpl {
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        #[cfg(feature = "alloc")]
+        if !self.path().is_empty() {
+            write!(f, "{}: ", Path(self.path()))?
+        }
+        #[cfg(feature = "alloc")]
+        let msg: &str = self.msg.as_ref().map(|m| m.text.as_str()).unwrap_or_default();
+        #[cfg(not(feature = "alloc"))]
+        let msg: &str = self.msg;
         match &self.err {
             ErrorImpl::EndOfInput =>
-                match (self.msg.as_ref(), self.pos) {
+                match (msg, self.pos) {
                     ("", None)    => write!(f, "end of input bytes"),
                     ("", Some(p)) => write!(f, "end of input bytes at position {p}"),
                     (m, None)     => write!(f, "end of input bytes: {m}"),
                     (m, Some(p))  => write!(f, "end of input bytes at position {p}: {m}")
                 }
             ErrorImpl::InvalidChar(n) =>
-                match (self.msg.as_ref(), self.pos) {
+                match (msg, self.pos) {
                     ("", None)    => write!(f, "invalid char {n:#x?}"),
                     ("", Some(p)) => write!(f, "invalid char {n:#x?} at position {p}"),
                     (m, None)     => write!(f, "invalid char {n:#x?}: {m}"),
@@ -240,7 +580,7 @@ impl fmt::Display for Error {
                 }
             #[cfg(not(feature = "std"))]
             ErrorImpl::Utf8(e) =>
-                match (self.msg.as_ref(), self.pos) {
+                match (msg, self.pos) {
                     ("", None)    => write!(f, "invalid utf-8: {e}"),
                     ("", Some(p)) => write!(f, "invalid utf-8 at position {p}: {e}"),
                     (m, None)     => write!(f, "invalid utf-8: {e}, {m}"),
@@ -248,56 +588,133 @@ impl fmt::Display for Error {
                 }
             #[cfg(feature = "std")]
             ErrorImpl::Utf8(_) =>
-                match (self.msg.as_ref(), self.pos) {
+                match (msg, self.pos) {
                     ("", None)    => write!(f, "invalid utf-8"),
                     ("", Some(p)) => write!(f, "invalid utf-8 at position {p}"),
                     (m, None)     => write!(f, "invalid utf-8: {m}"),
                     (m, Some(p))  => write!(f, "invalid utf-8 at position {p}: {m}")
                 }
-            ErrorImpl::Overflow(n) =>
-                match (self.msg.as_ref(), self.pos) {
+            ErrorImpl::Overflow(None) =>
+                match (msg, self.pos) {
+                    ("", None)    => write!(f, "bignum overflows target type"),
+                    ("", Some(p)) => write!(f, "bignum overflows target type at position {p}"),
+                    (m, None)     => write!(f, "bignum overflows target type: {m}"),
+                    (m, Some(p))  => write!(f, "bignum overflows target type at position {p}: {m}")
+                }
+            ErrorImpl::Overflow(Some(n)) =>
+                match (msg, self.pos) {
                     ("", None)    => write!(f, "{n} overflows target type"),
                     ("", Some(p)) => write!(f, "{n} overflows target type at position {p}"),
                     (m, None)     => write!(f, "{n} overflows target type: {m}"),
                     (m, Some(p))  => write!(f, "{n} overflows target type at position {p}: {m}")
                 }
             ErrorImpl::TypeMismatch(t) =>
-                match (self.msg.as_ref(), self.pos) {
+                match (msg, self.pos) {
                     ("", None)    => write!(f, "unexpected type {t}"),
                     ("", Some(p)) => write!(f, "unexpected type {t} at position {p}"),
                     (m, None)     => write!(f, "unexpected type {t}: {m}"),
                     (m, Some(p))  => write!(f, "unexpected type {t} at position {p}: {m}")
                 }
             ErrorImpl::TagMismatch(t) =>
-                match (self.msg.as_ref(), self.pos) {
+                match (msg, self.pos) {
                     ("", None)    => write!(f, "unexpected tag {t}"),
                     ("", Some(p)) => write!(f, "unexpected tag {t} at position {p}"),
                     (m, None)     => write!(f, "unexpected tag {t}: {m}"),
                     (m, Some(p))  => write!(f, "unexpected tag {t} at position {p}: {m}")
                 }
             ErrorImpl::UnknownVariant(n) =>
-                match (self.msg.as_ref(), self.pos) {
+                match (msg, self.pos) {
                     ("", None)    => write!(f, "unknown enum variant {n}"),
                     ("", Some(p)) => write!(f, "unknown enum variant {n} at position {p}"),
                     (m, None)     => write!(f, "unknown enum variant {n}: {m}"),
                     (m, Some(p))  => write!(f, "unknown enum variant {n} at position {p}: {m}")
                 }
             ErrorImpl::MissingValue(n) =>
-                match (self.msg.as_ref(), self.pos) {
+                match (msg, self.pos) {
                     ("", None)    => write!(f, "missing value at index {n}"),
                     ("", Some(p)) => write!(f, "missing value at index {n} in map or array starting at position {p}"),
                     (m, None)     => write!(f, "missing value at index {n} ({m})"),
                     (m, Some(p))  => write!(f, "missing value at index {n} ({m}) in map or array starting at position {p}")
                 }
+            ErrorImpl::NonMinimalInt =>
+                match (msg, self.pos) {
+                    ("", None)    => write!(f, "non-minimal integer encoding"),
+                    ("", Some(p)) => write!(f, "non-minimal integer encoding at position {p}"),
+                    (m, None)     => write!(f, "non-minimal integer encoding: {m}"),
+                    (m, Some(p))  => write!(f, "non-minimal integer encoding at position {p}: {m}")
+                }
+            ErrorImpl::NonShortestFloat =>
+                match (msg, self.pos) {
+                    ("", None)    => write!(f, "non-shortest float encoding"),
+                    ("", Some(p)) => write!(f, "non-shortest float encoding at position {p}"),
+                    (m, None)     => write!(f, "non-shortest float encoding: {m}"),
+                    (m, Some(p))  => write!(f, "non-shortest float encoding at position {p}: {m}")
+                }
+            ErrorImpl::IndefiniteLength(t) =>
+                match (msg, self.pos) {
+                    ("", None)    => write!(f, "indefinite-length item {t}"),
+                    ("", Some(p)) => write!(f, "indefinite-length item {t} at position {p}"),
+                    (m, None)     => write!(f, "indefinite-length item {t}: {m}"),
+                    (m, Some(p))  => write!(f, "indefinite-length item {t} at position {p}: {m}")
+                }
+            ErrorImpl::UnsortedMapKeys =>
+                match (msg, self.pos) {
+                    ("", None)    => write!(f, "unsorted map keys"),
+                    ("", Some(p)) => write!(f, "unsorted map key at position {p}"),
+                    (m, None)     => write!(f, "unsorted map keys: {m}"),
+                    (m, Some(p))  => write!(f, "unsorted map key at position {p}: {m}")
+                }
+            ErrorImpl::DuplicateMapKey =>
+                match (msg, self.pos) {
+                    ("", None)    => write!(f, "duplicate map key"),
+                    ("", Some(p)) => write!(f, "duplicate map key at position {p}"),
+                    (m, None)     => write!(f, "duplicate map key: {m}"),
+                    (m, Some(p))  => write!(f, "duplicate map key at position {p}: {m}")
+                }
+            ErrorImpl::UnknownSimple(n) =>
+                match (msg, self.pos) {
+                    ("", None)    => write!(f, "unknown simple value {n}"),
+                    ("", Some(p)) => write!(f, "unknown simple value {n} at position {p}"),
+                    (m, None)     => write!(f, "unknown simple value {n}: {m}"),
+                    (m, Some(p))  => write!(f, "unknown simple value {n} at position {p}: {m}")
+                }
+            ErrorImpl::NotWellFormed =>
+                match (msg, self.pos) {
+                    ("", None)    => write!(f, "not well-formed"),
+                    ("", Some(p)) => write!(f, "not well-formed at position {p}"),
+                    (m, None)     => write!(f, "not well-formed: {m}"),
+                    (m, Some(p))  => write!(f, "not well-formed at position {p}: {m}")
+                }
+            ErrorImpl::LimitExceeded(w, n) =>
+                match (msg, self.pos) {
+                    ("", None)    => write!(f, "{w} exceeds limit {n}"),
+                    ("", Some(p)) => write!(f, "{w} exceeds limit {n} at position {p}"),
+                    (m, None)     => write!(f, "{w} exceeds limit {n}: {m}"),
+                    (m, Some(p))  => write!(f, "{w} exceeds limit {n} at position {p}: {m}")
+                }
+            ErrorImpl::NotFound =>
+                match (msg, self.pos) {
+                    ("", None)    => write!(f, "path segment not found"),
+                    ("", Some(p)) => write!(f, "path segment not found in item at position {p}"),
+                    (m, None)     => write!(f, "path segment not found: {m}"),
+                    (m, Some(p))  => write!(f, "path segment not found in item at position {p}: {m}")
+                }
+            ErrorImpl::TrailingData =>
+                match (msg, self.pos) {
+                    ("", None)    => write!(f, "trailing data"),
+                    ("", Some(p)) => write!(f, "trailing data at position {p}"),
+                    (m, None)     => write!(f, "trailing data: {m}"),
+                    (m, Some(p))  => write!(f, "trailing data at position {p}: {m}")
+                }
             ErrorImpl::Message =>
                 if let Some(p) = self.pos {
-                    write!(f, "decode error at position {p}: {}", self.msg)
+                    write!(f, "decode error at position {p}: {msg}")
                 } else {
-                    write!(f, "decode error: {}", self.msg)
+                    write!(f, "decode error: {msg}")
                 }
             #[cfg(feature = "alloc")]
             ErrorImpl::Custom(_) =>
-                match (self.msg.as_ref(), self.pos) {
+                match (msg, self.pos) {
                     ("", None)    => write!(f, "decode error"),
                     ("", Some(p)) => write!(f, "decode error at position {p}"),
                     (m, None)     => write!(f, "decode error: {m}"),
@@ -317,6 +734,16 @@ impl core::error::Error for Error {
             | ErrorImpl::TagMismatch(_)
             | ErrorImpl::UnknownVariant(_)
             | ErrorImpl::MissingValue(_)
+            | ErrorImpl::NonMinimalInt
+            | ErrorImpl::NonShortestFloat
+            | ErrorImpl::IndefiniteLength(_)
+            | ErrorImpl::UnsortedMapKeys
+            | ErrorImpl::DuplicateMapKey
+            | ErrorImpl::UnknownSimple(_)
+            | ErrorImpl::NotWellFormed
+            | ErrorImpl::LimitExceeded(..)
+            | ErrorImpl::NotFound
+            | ErrorImpl::TrailingData
             | ErrorImpl::Message
             => None,
             ErrorImpl::Utf8(e)   => Some(e),
@@ -1,6 +1,6 @@
 //! Introspection utilities.
 
-use crate::{BYTES, TEXT, ARRAY, MAP, SIMPLE, TAGGED, SIGNED, UNSIGNED, Decoder, data::Type};
+use crate::{BYTES, TEXT, ARRAY, MAP, SIMPLE, TAGGED, SIGNED, UNSIGNED, BREAK, Decoder, data::Type};
 use super::{decoder::{info_of, type_of}, Error};
 
 /// Information about a CBOR item size.
@@ -76,3 +76,185 @@ impl Size {
         }
     }
 }
+
+/// The result of [`Scanner::scan`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Progress {
+    /// The item is complete and has the given length in bytes.
+    Complete(usize),
+    /// The item is incomplete and at least the given number of bytes
+    /// need to be added to the input before scanning can make progress.
+    Incomplete(usize)
+}
+
+/// The kind of the innermost open item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Frame {
+    Top,
+    Array,
+    Map,
+    Bytes,
+    Text
+}
+
+/// Determine the length of a CBOR item from partial input.
+///
+/// When CBOR items arrive in chunks, e.g. from a socket, a `Scanner` can be
+/// used to find out if the bytes received so far contain a complete item,
+/// without decoding it or allocating memory. Given a prefix of the input,
+/// [`Scanner::scan`] reports either the length of the complete item, or a
+/// lower bound on the number of bytes missing. Scanning is resumable, i.e.
+/// work done by previous calls is not repeated. This makes it possible to
+/// frame a sequence of CBOR items without any length prefix.
+///
+/// Only the structure of the item is checked, not its content. For example,
+/// text strings may contain invalid UTF-8, which only a subsequent decoding
+/// would detect.
+///
+/// Nested definite-length arrays and maps require no extra space. The const
+/// parameter `N` denotes the max. number of nested indefinite-length items
+/// and scanning input exceeding this limit results in an error for which
+/// [`Error::is_limit_exceeded`] returns `true`.
+///
+/// # Example
+///
+/// ```
+/// use minicbor::decode::info::{Progress, Scanner};
+///
+/// let input = [0x9f, 0x01, 0x82, 0x02, 0x03, 0xff, 0x00]; // [_ 1, [2, 3]], 0
+///
+/// let mut s: Scanner = Scanner::new();
+/// assert_eq!(Progress::Incomplete(1), s.scan(&input[.. 3])?);
+/// assert_eq!(Progress::Incomplete(1), s.scan(&input[.. 5])?);
+/// assert_eq!(Progress::Complete(6), s.scan(&input)?);
+///
+/// // The next item begins after the previous one.
+/// s.reset();
+/// assert_eq!(Progress::Complete(1), s.scan(&input[6 ..])?);
+/// # Ok::<_, Box<dyn core::error::Error>>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct Scanner<const N: usize = 16> {
+    /// The number of bytes scanned so far.
+    pos: usize,
+    /// The number of items remaining in the current frame.
+    items: u64,
+    /// The current frame.
+    frame: Frame,
+    /// The enclosing frames of open indefinite-length items.
+    stack: [(Frame, u64); N],
+    /// The number of frames on the stack.
+    depth: usize
+}
+
+impl<const N: usize> Scanner<N> {
+    /// Create a new scanner for a single CBOR item.
+    pub const fn new() -> Self {
+        Scanner {
+            pos: 0,
+            items: 1,
+            frame: Frame::Top,
+            stack: [(Frame::Top, 0); N],
+            depth: 0
+        }
+    }
+
+    /// Reset this scanner to scan a new item.
+    pub fn reset(&mut self) {
+        *self = Self::new()
+    }
+
+    /// Scan the given input.
+    ///
+    /// The input always needs to begin with the first byte of the item.
+    /// In between calls to `scan` more bytes may be appended to the input,
+    /// but the bytes already present must not be changed.
+    ///
+    /// Once `Progress::Complete` has been returned, further calls will keep
+    /// returning the same result until the scanner is [reset](Scanner::reset).
+    pub fn scan(&mut self, buf: &[u8]) -> Result<Progress, Error> {
+        loop {
+            if self.items == 0 {
+                if self.frame == Frame::Top {
+                    return Ok(Progress::Complete(self.pos))
+                }
+                let Some(&b) = buf.get(self.pos) else {
+                    return Ok(Progress::Incomplete(1))
+                };
+                if b == BREAK {
+                    self.pos += 1;
+                    self.depth -= 1;
+                    (self.frame, self.items) = self.stack[self.depth];
+                    continue
+                }
+                // A map entry consists of a key and a value, so a break after
+                // the key finds an item remaining and is rejected below.
+                self.items = if self.frame == Frame::Map { 2 } else { 1 }
+            }
+            let p = self.pos;
+            let Some(&b) = buf.get(p) else {
+                return Ok(Progress::Incomplete(1))
+            };
+            if b == BREAK {
+                return Err(Error::type_mismatch(Type::Break).at(p).with_message("unexpected break"))
+            }
+            let chunk = match self.frame {
+                Frame::Bytes => Some((BYTES, "expected bytes (definite length)")),
+                Frame::Text  => Some((TEXT, "expected text (definite length)")),
+                _            => None
+            };
+            if let Some((t, msg)) = chunk {
+                if type_of(b) != t || info_of(b) == 0x1f {
+                    let t = Decoder::new(&buf[p ..]).datatype().unwrap_or(Type::Unknown(b));
+                    return Err(Error::type_mismatch(t).at(p).with_message(msg))
+                }
+            }
+            let n = Size::head(b).map_err(|e| e.at(p))?;
+            let Some(head) = buf.get(p .. p + n) else {
+                return Ok(Progress::Incomplete(p + n - buf.len()))
+            };
+            let mut items = self.items - 1;
+            let mut len = n;
+            match Size::tail(head).map_err(|e| e.at(p))? {
+                Size::Head => if type_of(b) == TAGGED {
+                    items += 1
+                }
+                Size::Bytes(k) => {
+                    len = usize::try_from(k).ok()
+                        .and_then(|k| k.checked_add(n))
+                        .ok_or_else(|| Error::overflow(k).at(p).with_message("item length"))?;
+                    if buf.len() - p < len {
+                        return Ok(Progress::Incomplete(len - (buf.len() - p)))
+                    }
+                }
+                Size::Items(k) => {
+                    items = if type_of(b) == MAP { k.checked_mul(2) } else { Some(k) }
+                        .and_then(|k| items.checked_add(k))
+                        .ok_or_else(|| Error::overflow(k).at(p).with_message("number of items"))?
+                }
+                Size::Indef => {
+                    if self.depth == N {
+                        return Err(Error::limit_exceeded("nesting depth", N as u64).at(p))
+                    }
+                    self.stack[self.depth] = (self.frame, items);
+                    self.depth += 1;
+                    self.frame = match type_of(b) {
+                        BYTES => Frame::Bytes,
+                        TEXT  => Frame::Text,
+                        ARRAY => Frame::Array,
+                        _     => Frame::Map
+                    };
+                    items = 0
+                }
+            }
+            self.items = items;
+            self.pos = p + len
+        }
+    }
+}
+
+impl<const N: usize> Default for Scanner<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
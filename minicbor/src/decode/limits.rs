@@ -0,0 +1,105 @@
+/// Resource limits of a [`Decoder`](crate::Decoder).
+///
+/// By default no limits apply. Decoding untrusted input should set
+/// appropriate limits as otherwise the nesting depth and the lengths of
+/// items are entirely under the control of the input.
+///
+/// When exceeded, all limits cause a decoding error for which
+/// [`Error::is_limit_exceeded`](crate::decode::Error::is_limit_exceeded)
+/// returns `true`.
+///
+/// # Example
+///
+/// ```
+/// use minicbor::Decoder;
+/// use minicbor::decode::Limits;
+///
+/// let input = [0x81, 0x81, 0x81, 0x80]; // [[[[]]]]
+///
+/// let mut d = Decoder::new(&input);
+/// d.set_limits(Limits::new().with_max_depth(3));
+/// let e = d.decode::<Vec<Vec<Vec<Vec<u8>>>>>().unwrap_err();
+/// assert!(e.is_limit_exceeded());
+/// assert_eq!(Some(3), e.position());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Limits {
+    pub(crate) depth: u32,
+    pub(crate) items: u64,
+    pub(crate) len: u64,
+    pub(crate) alloc: u64
+}
+
+impl Limits {
+    /// Create limits which do not limit anything.
+    pub const fn new() -> Self {
+        Limits {
+            depth: u32::MAX,
+            items: u64::MAX,
+            len: u64::MAX,
+            alloc: u64::MAX
+        }
+    }
+
+    /// Set the max. nesting depth.
+    ///
+    /// The nesting depth is increased by the `Decode` impls of arrays and
+    /// collections, by [`Decoder::skip`](crate::Decoder::skip), by types
+    /// deriving `Decode` and by any other `Decode` impl calling
+    /// [`Decoder::enter`](crate::Decoder::enter).
+    pub const fn with_max_depth(mut self, n: u32) -> Self {
+        self.depth = n;
+        self
+    }
+
+    /// Set the max. number of array elements or map entries.
+    pub const fn with_max_items(mut self, n: u64) -> Self {
+        self.items = n;
+        self
+    }
+
+    /// Set the max. length in bytes of byte and text strings.
+    ///
+    /// For indefinite strings the sum of all chunk lengths is limited.
+    pub const fn with_max_len(mut self, n: u64) -> Self {
+        self.len = n;
+        self
+    }
+
+    /// Set the max. sum of all lengths the decoder encounters.
+    ///
+    /// The lengths of all byte and text strings and the number of all array
+    /// elements and map entries are added up and can not exceed this limit.
+    /// This gives an upper bound on the memory required to allocate decoded
+    /// values.
+    pub const fn with_max_alloc(mut self, n: u64) -> Self {
+        self.alloc = n;
+        self
+    }
+
+    /// Get the max. nesting depth.
+    pub fn max_depth(&self) -> u32 {
+        self.depth
+    }
+
+    /// Get the max. number of array elements or map entries.
+    pub fn max_items(&self) -> u64 {
+        self.items
+    }
+
+    /// Get the max. length in bytes of byte and text strings.
+    pub fn max_len(&self) -> u64 {
+        self.len
+    }
+
+    /// Get the max. sum of all lengths the decoder encounters.
+    pub fn max_alloc(&self) -> u64 {
+        self.alloc
+    }
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Limits::new()
+    }
+}
@@ -0,0 +1,145 @@
+use core::fmt;
+use crate::data::{Int, Type};
+use crate::decode::{Decoder, Error};
+
+/// A segment of a path to a nested CBOR item.
+///
+/// A path is a slice of segments, each of which selects an element of the
+/// array or a value of the map at the current position. Paths are resolved
+/// by [`Decoder::query`] and [`Decoder::query_span`].
+///
+/// # Example
+///
+/// ```
+/// use minicbor::Decoder;
+/// use minicbor::decode::Segment;
+///
+/// // {3: [true, false, {"id": 42}]}
+/// let input = [0xa1, 0x03, 0x83, 0xf5, 0xf4, 0xa1, 0x62, 0x69, 0x64, 0x18, 0x2a];
+///
+/// let path = [Segment::Int(3.into()), Segment::Index(2), Segment::Str("id")];
+/// assert_eq!(42, Decoder::new(&input).query(&path)?.u8()?);
+/// # Ok::<_, Box<dyn core::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Segment<'a> {
+    /// The array element at the given index.
+    Index(u64),
+    /// The map value whose key is the given integer.
+    Int(Int),
+    /// The map value whose key is the given text string.
+    Str(&'a str),
+    /// The map value whose key is the given byte string.
+    Bytes(&'a [u8])
+}
+
+impl fmt::Display for Segment<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Segment::Index(i) => write!(f, "[{i}]"),
+            Segment::Int(i)   => write!(f, "{i}"),
+            Segment::Str(s)   => write!(f, "{s:?}"),
+            Segment::Bytes(b) => {
+                f.write_str("h'")?;
+                for x in *b {
+                    write!(f, "{x:02x}")?
+                }
+                f.write_str("'")
+            }
+        }
+    }
+}
+
+/// Move the decoder to the start of the item at the given path.
+pub(crate) fn select(d: &mut Decoder<'_>, path: &[Segment<'_>]) -> Result<(), Error> {
+    for (i, s) in path.iter().enumerate() {
+        while d.datatype()? == Type::Tag {
+            d.tag()?;
+        }
+        let p = d.position();
+        if let Segment::Index(n) = s {
+            let len = d.array().map_err(|e| context(e, "expected array", i, s))?;
+            let missing = || context(Error::not_found().at(p), "array index out of bounds", i, s);
+            if len.is_some_and(|k| *n >= k) {
+                return Err(missing())
+            }
+            for _ in 0 .. *n {
+                if len.is_none() && is_break(d)? {
+                    return Err(missing())
+                }
+                d.skip()?
+            }
+            if len.is_none() && is_break(d)? {
+                return Err(missing())
+            }
+        } else {
+            let mut len = d.map().map_err(|e| context(e, "expected map", i, s))?;
+            let missing = || context(Error::not_found().at(p), "map key not found", i, s);
+            loop {
+                match len {
+                    Some(0) => return Err(missing()),
+                    Some(k) => len = Some(k - 1),
+                    None    => if is_break(d)? { return Err(missing()) }
+                }
+                if is_key(d, s)? {
+                    break
+                }
+                d.skip()?
+            }
+        }
+    }
+    Ok(())
+}
+
+fn is_break(d: &Decoder<'_>) -> Result<bool, Error> {
+    Ok(d.datatype()? == Type::Break)
+}
+
+/// Decode or skip over the next map key and compare it with the given segment.
+fn is_key(d: &mut Decoder<'_>, s: &Segment<'_>) -> Result<bool, Error> {
+    match (s, d.datatype()?) {
+        (Segment::Int(i), Type::U8 | Type::U16 | Type::U32 | Type::U64
+            | Type::I8 | Type::I16 | Type::I32 | Type::I64 | Type::Int) =>
+        {
+            Ok(d.int()? == *i)
+        }
+        (Segment::Str(s), Type::String | Type::StringIndef) => {
+            let mut rest = Some(s.as_bytes());
+            for chunk in d.str_iter()? {
+                let chunk = chunk?;
+                rest = rest.and_then(|r| r.strip_prefix(chunk.as_bytes()))
+            }
+            Ok(rest == Some(&[]))
+        }
+        (Segment::Bytes(b), Type::Bytes | Type::BytesIndef) => {
+            let mut rest = Some(*b);
+            for chunk in d.bytes_iter()? {
+                let chunk = chunk?;
+                rest = rest.and_then(|r| r.strip_prefix(chunk))
+            }
+            Ok(rest == Some(&[]))
+        }
+        _ => {
+            d.skip()?;
+            Ok(false)
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+fn context(e: Error, msg: &'static str, i: usize, s: &Segment<'_>) -> Error {
+    if e.is_type_mismatch() || e.is_not_found() {
+        e.with_message(format_args!("{msg} for segment {i} ({s})"))
+    } else {
+        e
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+fn context(e: Error, msg: &'static str, _: usize, _: &Segment<'_>) -> Error {
+    if e.is_type_mismatch() || e.is_not_found() {
+        e.with_message(msg)
+    } else {
+        e
+    }
+}
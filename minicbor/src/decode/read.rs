@@ -0,0 +1,388 @@
+use crate::data::Type;
+use crate::decode::{Decode, Decoder, Error, Limits};
+use crate::decode::decoder::in_index;
+use crate::decode::info::{Progress, Scanner, Size};
+use std::{io, marker};
+
+/// The number of bytes to read at once.
+const CHUNK_SIZE: usize = 8 * 1024;
+
+/// Decode CBOR items from a [`std::io::Read`] impl.
+///
+/// Bytes are pulled from the reader on demand. Every item is read into an
+/// internal buffer in its entirety before it is decoded, but only one item
+/// at a time is kept in memory, i.e. a sequence of CBOR items of arbitrary
+/// total length can be processed. Large arrays and maps can be decoded
+/// element by element with [`ReadDecoder::array_iter`] and
+/// [`ReadDecoder::map_iter`], which only read the head of the array or map
+/// up front and then one element at a time. Memory use is thus bounded by
+/// the size of the largest element, not by the size of the whole input.
+///
+/// When reading untrusted input, [`ReadDecoder::set_max_len`] should be used
+/// to bound the buffer size. To find the end of an item, its structure is
+/// scanned incrementally (cf. [`info::Scanner`](crate::decode::info::Scanner))
+/// which supports up to 128 nested indefinite-length items.
+///
+/// I/O errors are returned as [custom](Error::custom) errors. If the reader
+/// reaches its end in the middle of an item, an error for which
+/// [`Error::is_end_of_input`] returns `true` is produced.
+///
+/// *Requires feature* `"std"`.
+///
+/// # Example
+///
+/// ```
+/// use minicbor::decode::ReadDecoder;
+///
+/// let input: &[u8] = &[0x83, 0x01, 0x02, 0x03, 0x63, 0x61, 0x62, 0x63]; // [1, 2, 3], "abc"
+///
+/// let mut d = ReadDecoder::new(input);
+/// assert_eq!(Some(vec![1, 2, 3]), d.decode::<Vec<u8>>()?);
+/// assert_eq!(Some("abc"), d.decode::<&str>()?);
+/// assert_eq!(None, d.decode::<u8>()?);
+///
+/// // An array is decoded one element at a time.
+/// let input: &[u8] = &[0x82, 0x61, 0x61, 0x61, 0x62]; // ["a", "b"]
+///
+/// let mut d = ReadDecoder::new(input);
+/// let mut it = d.array_iter::<String>()?;
+/// assert_eq!(Some("a".to_string()), it.next().transpose()?);
+/// assert_eq!(Some("b".to_string()), it.next().transpose()?);
+/// assert_eq!(None, it.next().transpose()?);
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug)]
+pub struct ReadDecoder<R> {
+    reader: R,
+    buffer: Vec<u8>,
+    /// The offset of the first byte in the buffer which has not been consumed.
+    start: usize,
+    /// The number of bytes in the buffer which have been read.
+    end: usize,
+    /// The length of the item at the front of the buffer.
+    item: usize,
+    scanner: Scanner<128>,
+    limits: Limits,
+    max_len: usize,
+    strict: bool,
+    unique_keys: bool
+}
+
+impl<R> ReadDecoder<R> {
+    /// Create a new decoder reading from the given reader.
+    pub fn new(reader: R) -> Self {
+        ReadDecoder {
+            reader,
+            buffer: Vec::new(),
+            start: 0,
+            end: 0,
+            item: 0,
+            scanner: Scanner::new(),
+            limits: Limits::new(),
+            max_len: usize::MAX,
+            strict: false,
+            unique_keys: false
+        }
+    }
+
+    /// Set the max. length of a CBOR item in bytes (unlimited by default).
+    ///
+    /// Items exceeding this length are not read into memory and produce an
+    /// error for which [`Error::is_limit_exceeded`] returns `true`.
+    pub fn set_max_len(&mut self, val: usize) {
+        self.max_len = val
+    }
+
+    /// Set the resource limits of the decoders used for each item.
+    ///
+    /// See [`Decoder::set_limits`] for details.
+    pub fn set_limits(&mut self, limits: Limits) {
+        self.limits = limits
+    }
+
+    /// Enable or disable strict decoding of every item (disabled by default).
+    ///
+    /// See [`Decoder::set_strict`] for details.
+    pub fn set_strict(&mut self, val: bool) {
+        self.strict = val
+    }
+
+    /// Reject maps with duplicate keys in every item (disabled by default).
+    ///
+    /// See [`Decoder::set_reject_duplicate_keys`] for details.
+    pub fn set_reject_duplicate_keys(&mut self, val: bool) {
+        self.unique_keys = val
+    }
+
+    /// Get a reference to the inner reader.
+    pub fn reader(&self) -> &R {
+        &self.reader
+    }
+
+    /// Get a mutable reference to the inner reader.
+    pub fn reader_mut(&mut self) -> &mut R {
+        &mut self.reader
+    }
+
+    /// Deconstruct this decoder into the inner reader and the bytes which
+    /// have been read from it but not been consumed yet.
+    pub fn into_parts(mut self) -> (R, Vec<u8>) {
+        self.buffer.truncate(self.end);
+        self.buffer.drain(.. self.start + self.item);
+        (self.reader, self.buffer)
+    }
+}
+
+impl<R: io::Read> ReadDecoder<R> {
+    /// Read the next CBOR item.
+    ///
+    /// Returns `Ok(None)` if the reader is at its end before the first byte
+    /// of a new item.
+    pub fn read_item(&mut self) -> Result<Option<&[u8]>, Error> {
+        self.consume();
+        loop {
+            let len = self.end - self.start;
+            match self.scanner.scan(&self.buffer[self.start .. self.end])? {
+                Progress::Complete(n) => {
+                    self.scanner.reset();
+                    self.item = n;
+                    return Ok(Some(&self.buffer[self.start .. self.start + n]))
+                }
+                Progress::Incomplete(n) => {
+                    if n > self.max_len.saturating_sub(len) {
+                        return Err(Error::limit_exceeded("item length", self.max_len as u64).at(len))
+                    }
+                    if !self.fill()? {
+                        if self.start == self.end {
+                            return Ok(None)
+                        }
+                        return Err(Error::end_of_input())
+                    }
+                }
+            }
+        }
+    }
+
+    /// Read the next CBOR item and decode it.
+    ///
+    /// Returns `Ok(None)` if the reader is at its end before the first byte
+    /// of a new item.
+    pub fn decode<'a, T: Decode<'a, ()>>(&'a mut self) -> Result<Option<T>, Error> {
+        self.decode_with(&mut ())
+    }
+
+    /// Like [`ReadDecoder::decode`] but accepting a user provided decoding context.
+    pub fn decode_with<'a, C, T: Decode<'a, C>>(&'a mut self, ctx: &mut C) -> Result<Option<T>, Error> {
+        self.decode_at(0, ctx)
+    }
+
+    /// Read the head of an array and iterate over its elements.
+    ///
+    /// Definite and indefinite length arrays are supported. Only the head
+    /// is read by this method. Every call to `next` on the returned iterator
+    /// reads and decodes the next element, so that at most one element is
+    /// held in memory. The decoding limits (cf. [`ReadDecoder::set_limits`])
+    /// apply to the array as a whole, i.e. its elements are decoded one
+    /// nesting level deep and the number of elements is checked against
+    /// the max. number of items.
+    pub fn array_iter<T>(&mut self) -> Result<ReadArrayIter<'_, R, T>, Error>
+    where
+        T: for<'a> Decode<'a, ()>
+    {
+        let len = self.read_head(|d| d.array())?;
+        Ok(ReadArrayIter { decoder: self, len, count: 0, _mark: marker::PhantomData })
+    }
+
+    /// Read the head of a map and iterate over its entries.
+    ///
+    /// Like [`ReadDecoder::array_iter`], only the head is read up front and
+    /// then one key or value at a time.
+    pub fn map_iter<K, V>(&mut self) -> Result<ReadMapIter<'_, R, K, V>, Error>
+    where
+        K: for<'a> Decode<'a, ()>,
+        V: for<'a> Decode<'a, ()>
+    {
+        let len = self.read_head(|d| d.map())?;
+        Ok(ReadMapIter { decoder: self, len, count: 0, _mark: marker::PhantomData })
+    }
+
+    /// Read the next CBOR item and decode it at the given nesting depth.
+    fn decode_at<'a, C, T: Decode<'a, C>>(&'a mut self, depth: u32, ctx: &mut C) -> Result<Option<T>, Error> {
+        let limits = self.limits;
+        let strict = self.strict;
+        let unique_keys = self.unique_keys;
+        let Some(item) = self.read_item()? else {
+            return Ok(None)
+        };
+        let mut d = Decoder::new(item);
+        d.set_limits(limits);
+        d.set_strict(strict);
+        d.set_reject_duplicate_keys(unique_keys);
+        for _ in 0 .. depth {
+            d.enter()?
+        }
+        d.decode_with(ctx).map(Some)
+    }
+
+    /// Decode the next element of an array or map read with
+    /// [`ReadDecoder::array_iter`] or [`ReadDecoder::map_iter`].
+    ///
+    /// `count` is the number of elements so far, including this one.
+    fn element<T>(&mut self, len: Option<u64>, count: u64) -> Result<T, Error>
+    where
+        T: for<'a> Decode<'a, ()>
+    {
+        if len.is_none() && count > self.limits.max_items() {
+            return Err(Error::limit_exceeded("number of items", self.limits.max_items()))
+        }
+        self.decode_at(1, &mut ())?.ok_or_else(Error::end_of_input)
+    }
+
+    /// Consume a break if it is next in the input.
+    fn read_break(&mut self) -> Result<bool, Error> {
+        self.read_head(|d| {
+            if d.datatype()? != Type::Break {
+                return Ok(false)
+            }
+            d.set_position(1);
+            Ok(true)
+        })
+    }
+
+    /// Read the head of the next item and apply `f` to it.
+    ///
+    /// Only the bytes `f` consumes are removed from the input.
+    fn read_head<T, F>(&mut self, f: F) -> Result<T, Error>
+    where
+        F: FnOnce(&mut Decoder<'_>) -> Result<T, Error>
+    {
+        self.consume();
+        loop {
+            if let Some(&b) = self.buffer[self.start .. self.end].first() {
+                let n = Size::head(b)?;
+                if n <= self.end - self.start {
+                    let mut d = Decoder::new(&self.buffer[self.start .. self.start + n]);
+                    d.set_limits(self.limits);
+                    d.set_strict(self.strict);
+                    let x = f(&mut d)?;
+                    self.item = d.position();
+                    return Ok(x)
+                }
+            }
+            if !self.fill()? {
+                return Err(Error::end_of_input())
+            }
+        }
+    }
+
+    /// Remove the item at the front of the buffer.
+    fn consume(&mut self) {
+        self.start += self.item;
+        self.item = 0;
+        if self.start == self.end {
+            self.start = 0;
+            self.end = 0
+        }
+    }
+
+    /// Read more bytes into the buffer.
+    ///
+    /// The buffer grows by at most `CHUNK_SIZE` bytes at once, so that its
+    /// size is bounded by the number of bytes actually received, regardless
+    /// of the lengths announced in the input.
+    ///
+    /// Returns `false` if the reader is at its end.
+    fn fill(&mut self) -> Result<bool, Error> {
+        if self.end == self.buffer.len() && self.start > 0 {
+            self.buffer.copy_within(self.start .. self.end, 0);
+            self.end -= self.start;
+            self.start = 0
+        }
+        if self.end == self.buffer.len() {
+            let n = self.max_len.saturating_sub(self.end).min(CHUNK_SIZE);
+            self.buffer.resize(self.end + n, 0)
+        }
+        loop {
+            match self.reader.read(&mut self.buffer[self.end ..]) {
+                Ok(n) => {
+                    self.end += n;
+                    return Ok(n > 0)
+                }
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(Error::custom(e))
+            }
+        }
+    }
+}
+
+/// An iterator over array elements read from a [`ReadDecoder`].
+///
+/// Returned from [`ReadDecoder::array_iter`].
+#[derive(Debug)]
+pub struct ReadArrayIter<'a, R, T> {
+    decoder: &'a mut ReadDecoder<R>,
+    len: Option<u64>,
+    count: u64,
+    _mark: marker::PhantomData<fn(T)>
+}
+
+impl<R: io::Read, T: for<'a> Decode<'a, ()>> Iterator for ReadArrayIter<'_, R, T> {
+    type Item = Result<T, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.len {
+            None => match self.decoder.read_break() {
+                Ok(true)  => {
+                    self.len = Some(0);
+                    return None
+                }
+                Ok(false) => {}
+                Err(e)    => return Some(Err(e))
+            }
+            Some(0) => return None,
+            Some(n) => self.len = Some(n - 1)
+        }
+        self.count += 1;
+        let i = self.count - 1;
+        Some(in_index(self.decoder.element(self.len, self.count), i))
+    }
+}
+
+/// An iterator over map entries read from a [`ReadDecoder`].
+///
+/// Returned from [`ReadDecoder::map_iter`].
+#[derive(Debug)]
+pub struct ReadMapIter<'a, R, K, V> {
+    decoder: &'a mut ReadDecoder<R>,
+    len: Option<u64>,
+    count: u64,
+    _mark: marker::PhantomData<fn(K, V)>
+}
+
+impl<R, K, V> Iterator for ReadMapIter<'_, R, K, V>
+where
+    R: io::Read,
+    K: for<'a> Decode<'a, ()>,
+    V: for<'a> Decode<'a, ()>
+{
+    type Item = Result<(K, V), Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.len {
+            None => match self.decoder.read_break() {
+                Ok(true)  => {
+                    self.len = Some(0);
+                    return None
+                }
+                Ok(false) => {}
+                Err(e)    => return Some(Err(e))
+            }
+            Some(0) => return None,
+            Some(n) => self.len = Some(n - 1)
+        }
+        self.count += 1;
+        let entry = self.decoder.element(self.len, self.count)
+            .and_then(|k| Ok((k, self.decoder.element(self.len, self.count)?)));
+        Some(entry)
+    }
+}
@@ -2,6 +2,9 @@
 
 use core::ops::{Deref, DerefMut};
 
+#[cfg(feature = "alloc")]
+use core::ops::Range;
+
 use crate::data::Token;
 use crate::decode::Error;
 
@@ -59,6 +62,235 @@ impl<'a, 'b> Tokenizer<'a, 'b> {
             }
         }
     }
+
+    /// Turn this tokenizer into an iterator over [`SpannedToken`]s.
+    ///
+    /// In addition to every token, the byte range it occupies in the input
+    /// and its nesting depth are produced.
+    ///
+    /// *Requires feature* `"alloc"`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use minicbor::data::Token;
+    /// use minicbor::decode::{SpannedToken, Tokenizer};
+    ///
+    /// let input = [0x82, 0x18, 0x64, 0x9f, 0xf5, 0xff]; // [100, [_ true]]
+    /// let tokens = Tokenizer::new(&input).spanned().collect::<Result<Vec<_>, _>>()?;
+    ///
+    /// assert_eq!(tokens, [
+    ///     SpannedToken { span: 0 .. 1, depth: 0, token: Token::Array(2) },
+    ///     SpannedToken { span: 1 .. 3, depth: 1, token: Token::U8(100) },
+    ///     SpannedToken { span: 3 .. 4, depth: 1, token: Token::BeginArray },
+    ///     SpannedToken { span: 4 .. 5, depth: 2, token: Token::Bool(true) },
+    ///     SpannedToken { span: 5 .. 6, depth: 1, token: Token::Break }
+    /// ]);
+    /// # Ok::<_, Box<dyn core::error::Error>>(())
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn spanned(self) -> Spanned<'a, 'b> {
+        Spanned { tokenizer: self, stack: alloc::vec::Vec::new() }
+    }
+
+    /// Turn this tokenizer into an annotated hex dump of its tokens.
+    ///
+    /// Every token is displayed on a separate line which shows its bytes
+    /// in hexadecimal, indented by nesting depth (cf. [`SpannedToken`]),
+    /// followed by a comment with the decoded token. The payload of byte
+    /// and text strings is shown on separate lines of at most 16 bytes each,
+    /// with text being escaped as in Rust's `Debug` output.
+    /// Should decoding fail, the error message becomes the last line.
+    ///
+    /// *Requires feature* `"alloc"`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use minicbor::decode::Tokenizer;
+    ///
+    /// let input = [0x82, 0x18, 0x64, 0x9f, 0x62, 0x68, 0x69, 0xff]; // [100, [_ "hi"]]
+    ///
+    /// let expected = "\
+    /// 82             # A[2]
+    ///    18 64       #    100
+    ///    9f          #    ?A[
+    ///       62       #       text(2)
+    ///          68 69 #          \"hi\"
+    ///    ff          #    ]";
+    ///
+    /// assert_eq!(expected, Tokenizer::new(&input).annotated().to_string());
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn annotated(self) -> Annotated<'a, 'b> {
+        Annotated { tokenizer: self }
+    }
+}
+
+/// A token, the byte range it occupies in the input and its nesting depth.
+///
+/// Tokens at the top level have depth 0. Elements of arrays and maps, chunks
+/// of indefinite-length byte and text strings and tagged values have the depth
+/// of the enclosing array, map, string or tag plus 1. The `Break` token which
+/// ends an indefinite-length item has the same depth as its beginning.
+///
+/// *Requires feature* `"alloc"`.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpannedToken<'b> {
+    /// The byte range of the token in the input.
+    pub span: Range<usize>,
+    /// The nesting depth of the token.
+    pub depth: usize,
+    /// The token.
+    pub token: Token<'b>
+}
+
+/// An [`Iterator`] over [`SpannedToken`]s.
+///
+/// Returned from [`Tokenizer::spanned`].
+///
+/// *Requires feature* `"alloc"`.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+pub struct Spanned<'a, 'b> {
+    tokenizer: Tokenizer<'a, 'b>,
+    /// Per open item the number of remaining elements (`None` if indefinite).
+    stack: alloc::vec::Vec<Option<u64>>
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, 'b> Spanned<'a, 'b> {
+    /// Account for a complete item in the enclosing items.
+    fn complete(&mut self) {
+        while let Some(Some(n)) = self.stack.last_mut() {
+            *n -= 1;
+            if *n > 0 {
+                break
+            }
+            self.stack.pop();
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, 'b> Iterator for Spanned<'a, 'b> {
+    type Item = Result<SpannedToken<'b>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = self.tokenizer.decoder.position();
+        let token = match self.tokenizer.next()? {
+            Ok(t)  => t,
+            Err(e) => return Some(Err(e))
+        };
+        let end = self.tokenizer.decoder.position();
+        let mut depth = self.stack.len();
+        match token {
+            Token::Array(n) if n > 0 => self.stack.push(Some(n)),
+            Token::Map(n) if n > 0   => self.stack.push(Some(n.saturating_mul(2))),
+            Token::Tag(_)            => self.stack.push(Some(1)),
+            Token::BeginBytes
+            | Token::BeginString
+            | Token::BeginArray
+            | Token::BeginMap        => self.stack.push(None),
+            Token::Break             => if let Some(None) = self.stack.last() {
+                self.stack.pop();
+                depth -= 1;
+                self.complete()
+            }
+            _ => self.complete()
+        }
+        Some(Ok(SpannedToken { span: start .. end, depth, token }))
+    }
+}
+
+/// An annotated hex dump of CBOR tokens.
+///
+/// Created by [`Tokenizer::annotated`].
+///
+/// *Requires feature* `"alloc"`.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+pub struct Annotated<'a, 'b> {
+    tokenizer: Tokenizer<'a, 'b>
+}
+
+#[cfg(feature = "alloc")]
+impl core::fmt::Display for Annotated<'_, '_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        /// Line comment.
+        enum C<'b> {
+            T(Token<'b>),           // display token
+            H(&'static str, usize), // string head with payload length
+            S(&'b str),             // text string (escaped)
+            N                       // no comment
+        }
+
+        let input = self.tokenizer.decoder.input();
+        let mut lines = alloc::vec::Vec::new();
+        let mut error = None;
+
+        for t in self.tokenizer.clone().spanned() {
+            let SpannedToken { span, depth, token } = match t {
+                Ok(t)  => t,
+                Err(e) => {
+                    error = Some(e);
+                    break
+                }
+            };
+            let (name, payload, text) = match token {
+                Token::Bytes(b)  => ("bytes", b, None),
+                Token::String(s) => ("text", s.as_bytes(), Some(s)),
+                _ => {
+                    lines.push((depth, &input[span], C::T(token)));
+                    continue
+                }
+            };
+            let head = &input[span.start .. span.end - payload.len()];
+            lines.push((depth, head, C::H(name, payload.len())));
+            for (i, chunk) in payload.chunks(16).enumerate() {
+                match text {
+                    Some(s) if i == 0 => lines.push((depth + 1, chunk, C::S(s))),
+                    _                 => lines.push((depth + 1, chunk, C::N))
+                }
+            }
+        }
+
+        let width = lines.iter()
+            .map(|(depth, bytes, _)| 3 * depth + 3 * bytes.len() - 1)
+            .max()
+            .unwrap_or(0);
+
+        for (i, (depth, bytes, comment)) in lines.iter().enumerate() {
+            if i > 0 {
+                f.write_str("\n")?
+            }
+            let indent = 3 * depth;
+            write!(f, "{:indent$}", "")?;
+            for (j, b) in bytes.iter().enumerate() {
+                if j > 0 {
+                    f.write_str(" ")?
+                }
+                write!(f, "{b:02x}")?
+            }
+            let pad = width - (indent + 3 * bytes.len() - 1);
+            match comment {
+                C::T(t)    => write!(f, "{:pad$} # {:indent$}{t}", "", "")?,
+                C::H(n, k) => write!(f, "{:pad$} # {:indent$}{n}({k})", "", "")?,
+                C::S(s)    => write!(f, "{:pad$} # {:indent$}{s:?}", "", "")?,
+                C::N       => {}
+            }
+        }
+
+        if let Some(e) = error {
+            if !lines.is_empty() {
+                f.write_str("\n")?
+            }
+            write!(f, "!!! decoding error: {e}")?
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(feature = "alloc")]
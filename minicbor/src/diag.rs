@@ -0,0 +1,391 @@
+//! Parse CBOR diagnostic notation.
+//!
+//! The function [`parse`] is the inverse of [`display`](crate::display), i.e.
+//! it accepts the format produced by `display` and `Tokenizer`'s `Display`
+//! impl and turns it into CBOR bytes:
+//!
+//! - Unsigned and negative integers, e.g. `0`, `1000` or `-1`, which are
+//!   encoded in their shortest form.
+//! - Floating point values, e.g. `1.5e0`, `-4.1e0`, `inf`, `-inf` or `NaN`.
+//!   Values are encoded as the narrowest of `f16`, `f32` and `f64` which
+//!   displays as the given text. Other formats, e.g. `1.5` or `0.1`, are
+//!   accepted as well and encoded in their shortest exact form.
+//! - `false`, `true`, `null`, `undefined` and `simple(n)`.
+//! - Byte strings `h'01 02'`, text strings `"abc"` and indefinite-length
+//!   strings `(_ h'01', h'02')`, `(_ "a", "b")`, `''_` and `""_`.
+//! - Arrays `[1, 2]`, maps `{1: 2}` and their indefinite-length versions
+//!   `[_ 1, 2]` and `{_ 1: 2}`.
+//! - Tagged values `t(x)`, e.g. `24(h'01')`.
+//!
+//! Several top-level items separated by commas yield a CBOR sequence.
+//!
+//! Since `display` does not escape quotes in text strings, a `"` ends a text
+//! string only if followed by `,`, `:`, `]`, `}`, `)` or the end of the input
+//! (whitespace in between is allowed). This means that text strings which
+//! contain a `"` followed by one of these characters can not be parsed.
+//!
+//! *Requires features* `"alloc"` and `"half"`.
+//!
+//! # Example
+//!
+//! ```
+//! let s = r#"{_ "a": [1, -2.5e0], "b": 24(h'f6')}"#;
+//! let b = minicbor::diag::parse(s)?;
+//! assert_eq!(s, minicbor::display(&b).to_string());
+//! # Ok::<_, Box<dyn core::error::Error>>(())
+//! ```
+
+use alloc::{format, vec::Vec};
+use core::convert::Infallible;
+use core::fmt;
+
+use crate::data::{Int, Tag};
+use crate::encode::{self, Encoder};
+
+/// Max. nesting depth of arrays, maps and tags.
+const MAX_DEPTH: usize = 512;
+
+/// Parse diagnostic notation and return the CBOR encoding.
+pub fn parse(s: &str) -> Result<Vec<u8>, Error> {
+    let mut p = Parser { input: s, pos: 0, depth: 0 };
+    let mut e = Encoder::new(Vec::new());
+    p.ws();
+    p.item(&mut e)?;
+    p.ws();
+    while p.peek().is_some() {
+        p.expect(b',', "expected `,` or end of input")?;
+        p.ws();
+        p.item(&mut e)?;
+        p.ws()
+    }
+    Ok(e.into_writer())
+}
+
+/// Diagnostic notation parse error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error {
+    pos: usize,
+    msg: &'static str
+}
+
+impl Error {
+    /// Byte index of the input at which the error occurred.
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} at position {}", self.msg, self.pos)
+    }
+}
+
+impl core::error::Error for Error {}
+
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+    depth: usize
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self, msg: &'static str) -> Error {
+        Error { pos: self.pos, msg }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.input.as_bytes().get(self.pos).copied()
+    }
+
+    /// Skip over whitespace.
+    fn ws(&mut self) {
+        while self.peek().is_some_and(|c| c.is_ascii_whitespace()) {
+            self.pos += 1
+        }
+    }
+
+    /// Consume the given character.
+    fn expect(&mut self, c: u8, msg: &'static str) -> Result<(), Error> {
+        if self.peek() != Some(c) {
+            return Err(self.error(msg))
+        }
+        self.pos += 1;
+        Ok(())
+    }
+
+    /// Consume the given string if present.
+    fn accept(&mut self, s: &str) -> bool {
+        if self.input[self.pos ..].starts_with(s) {
+            self.pos += s.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Check the result of an encoding operation (writing to a `Vec` can not fail).
+    fn enc<T>(&self, r: Result<T, encode::Error<Infallible>>) -> Result<(), Error> {
+        r.map(|_| ()).map_err(|_| self.error("encoding error"))
+    }
+
+    /// Parse a data item.
+    fn item(&mut self, e: &mut Encoder<Vec<u8>>) -> Result<(), Error> {
+        match self.peek() {
+            Some(b'[') => self.nested(|p| p.array(e)),
+            Some(b'{') => self.nested(|p| p.map(e)),
+            Some(b'(') => self.chunks(e),
+            Some(b'"') => self.text(e, true),
+            Some(b'\'') => self.bytes(e, true),
+            Some(b'h') if self.input[self.pos ..].starts_with("h'") => self.bytes(e, false),
+            Some(_)    => self.word(e),
+            None       => Err(self.error("unexpected end of input"))
+        }
+    }
+
+    /// Run the given parse function one nesting level deeper.
+    fn nested<F>(&mut self, f: F) -> Result<(), Error>
+    where
+        F: FnOnce(&mut Self) -> Result<(), Error>
+    {
+        if self.depth == MAX_DEPTH {
+            return Err(self.error("nesting depth exceeds limit"))
+        }
+        self.depth += 1;
+        let r = f(self);
+        self.depth -= 1;
+        r
+    }
+
+    /// Parse an array.
+    fn array(&mut self, e: &mut Encoder<Vec<u8>>) -> Result<(), Error> {
+        self.pos += 1; // '['
+        if self.accept("_") {
+            self.enc(e.begin_array())?;
+            self.elements(b']', "expected `,` or `]`", |p| p.item(e))?;
+            return self.enc(e.end())
+        }
+        let mut b = Encoder::new(Vec::new());
+        let n = self.elements(b']', "expected `,` or `]`", |p| p.item(&mut b))?;
+        self.enc(e.array(n))?;
+        e.writer_mut().extend_from_slice(b.writer());
+        Ok(())
+    }
+
+    /// Parse a map.
+    fn map(&mut self, e: &mut Encoder<Vec<u8>>) -> Result<(), Error> {
+        self.pos += 1; // '{'
+        if self.accept("_") {
+            self.enc(e.begin_map())?;
+            self.elements(b'}', "expected `,` or `}`", |p| p.entry(e))?;
+            return self.enc(e.end())
+        }
+        let mut b = Encoder::new(Vec::new());
+        let n = self.elements(b'}', "expected `,` or `}`", |p| p.entry(&mut b))?;
+        self.enc(e.map(n))?;
+        e.writer_mut().extend_from_slice(b.writer());
+        Ok(())
+    }
+
+    /// Parse a map entry.
+    fn entry(&mut self, e: &mut Encoder<Vec<u8>>) -> Result<(), Error> {
+        self.item(e)?;
+        self.ws();
+        self.expect(b':', "expected `:`")?;
+        self.ws();
+        self.item(e)
+    }
+
+    /// Parse comma-separated elements until the given end character.
+    ///
+    /// Returns the number of elements.
+    fn elements<F>(&mut self, end: u8, msg: &'static str, mut f: F) -> Result<u64, Error>
+    where
+        F: FnMut(&mut Self) -> Result<(), Error>
+    {
+        let mut n = 0;
+        self.ws();
+        if self.peek() == Some(end) {
+            self.pos += 1;
+            return Ok(n)
+        }
+        loop {
+            f(self)?;
+            n += 1;
+            self.ws();
+            match self.peek() {
+                Some(b',') => {
+                    self.pos += 1;
+                    self.ws()
+                }
+                Some(c) if c == end => {
+                    self.pos += 1;
+                    return Ok(n)
+                }
+                _ => return Err(self.error(msg))
+            }
+        }
+    }
+
+    /// Parse an indefinite-length byte or text string.
+    fn chunks(&mut self, e: &mut Encoder<Vec<u8>>) -> Result<(), Error> {
+        if !self.accept("(_") {
+            return Err(self.error("expected `(_`"))
+        }
+        self.ws();
+        let is_text = match self.peek() {
+            Some(b'"') => true,
+            Some(b'h') => false,
+            _ => return Err(self.error("expected byte or text string"))
+        };
+        if is_text {
+            self.enc(e.begin_str())?
+        } else {
+            self.enc(e.begin_bytes())?
+        }
+        self.elements(b')', "expected `,` or `)`", |p| match (is_text, p.peek()) {
+            (true, Some(b'"'))  => p.text(e, false),
+            (false, Some(b'h')) => p.bytes(e, false),
+            _ => Err(p.error("chunks of indefinite-length strings must be of the same type"))
+        })?;
+        self.enc(e.end())
+    }
+
+    /// Parse a text string (or `""_` if `indef` is true).
+    fn text(&mut self, e: &mut Encoder<Vec<u8>>, indef: bool) -> Result<(), Error> {
+        let start = self.pos + 1;
+        if indef && self.input[start ..].starts_with("\"_") {
+            self.pos += 3;
+            return self.enc(e.begin_str().and_then(|e| e.end()))
+        }
+        let mut end = start;
+        loop {
+            let Some(i) = self.input[end ..].find('"') else {
+                self.pos = self.input.len();
+                return Err(self.error("unterminated text string"))
+            };
+            end += i;
+            let rest = self.input[end + 1 ..].trim_start();
+            if rest.is_empty() || rest.starts_with([',', ':', ']', '}', ')']) {
+                break
+            }
+            end += 1
+        }
+        self.pos = end + 1;
+        self.enc(e.str(&self.input[start .. end]))
+    }
+
+    /// Parse a byte string (or `''_` if `indef` is true).
+    fn bytes(&mut self, e: &mut Encoder<Vec<u8>>, indef: bool) -> Result<(), Error> {
+        if indef && self.accept("''_") {
+            return self.enc(e.begin_bytes().and_then(|e| e.end()))
+        }
+        if !self.accept("h'") {
+            return Err(self.error("expected `h'`"))
+        }
+        let mut b = Vec::new();
+        let mut hi: Option<u8> = None;
+        loop {
+            let Some(c) = self.peek() else {
+                return Err(self.error("unterminated byte string"))
+            };
+            if c == b'\'' {
+                break
+            }
+            if !c.is_ascii_whitespace() {
+                let Some(x) = (c as char).to_digit(16) else {
+                    return Err(self.error("invalid hex digit"))
+                };
+                match hi.take() {
+                    Some(h) => b.push(h << 4 | x as u8),
+                    None    => hi = Some(x as u8)
+                }
+            }
+            self.pos += 1
+        }
+        if hi.is_some() {
+            return Err(self.error("odd number of hex digits"))
+        }
+        self.pos += 1;
+        self.enc(e.bytes(&b))
+    }
+
+    /// Parse a number, tag, keyword or simple value.
+    fn word(&mut self, e: &mut Encoder<Vec<u8>>) -> Result<(), Error> {
+        let start = self.pos;
+        let len = self.input[start ..]
+            .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '+' | '.')))
+            .unwrap_or(self.input.len() - start);
+        if len == 0 {
+            return Err(self.error("unexpected character"))
+        }
+        let w = &self.input[start .. start + len];
+        self.pos += len;
+        match w {
+            "false"     => return self.enc(e.bool(false)),
+            "true"      => return self.enc(e.bool(true)),
+            "null"      => return self.enc(e.null()),
+            "undefined" => return self.enc(e.undefined()),
+            "simple"    => return self.simple(e),
+            _           => {}
+        }
+        if self.peek() == Some(b'(') {
+            let t = w.parse::<u64>().map_err(|_| Error { pos: start, msg: "invalid tag" })?;
+            self.pos += 1;
+            self.enc(e.tag(Tag::new(t)))?;
+            self.ws();
+            self.nested(|p| p.item(e))?;
+            self.ws();
+            return self.expect(b')', "expected `)`")
+        }
+        let digits = w.strip_prefix('-').unwrap_or(w);
+        if !digits.is_empty() && digits.bytes().all(|c| c.is_ascii_digit()) {
+            let i = w.parse::<i128>()
+                .ok()
+                .and_then(|i| Int::try_from(i).ok())
+                .ok_or(Error { pos: start, msg: "integer out of range" })?;
+            return self.enc(e.int(i))
+        }
+        self.float(e, w).map_err(|mut err| {
+            err.pos = start;
+            err
+        })
+    }
+
+    /// Parse the argument of `simple`.
+    fn simple(&mut self, e: &mut Encoder<Vec<u8>>) -> Result<(), Error> {
+        self.expect(b'(', "expected `(`")?;
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.pos += 1
+        }
+        match self.input[start .. self.pos].parse::<u8>() {
+            Ok(n) if !(20 ..= 31).contains(&n) => {
+                self.expect(b')', "expected `)`")?;
+                self.enc(e.simple(n))
+            }
+            _ => Err(Error { pos: start, msg: "invalid simple value" })
+        }
+    }
+
+    /// Encode a floating point value in the narrowest type which displays as `w`.
+    fn float(&mut self, e: &mut Encoder<Vec<u8>>, w: &str) -> Result<(), Error> {
+        if let Ok(x) = w.parse::<f32>() {
+            if format!("{x:e}") == w {
+                if half::f16::from_f32(x).to_f32() == x || x.is_nan() {
+                    return self.enc(e.f16(x))
+                }
+                return self.enc(e.f32(x))
+            }
+        }
+        let x = match w {
+            "Infinity"  => f64::INFINITY,
+            "-Infinity" => f64::NEG_INFINITY,
+            _ => w.parse::<f64>().map_err(|_| self.error("invalid value"))?
+        };
+        if format!("{x:e}") == w {
+            return self.enc(e.f64(x))
+        }
+        self.enc(e.float(x))
+    }
+}
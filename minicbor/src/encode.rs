@@ -5,6 +5,8 @@
 
 mod encoder;
 mod error;
+#[cfg(feature = "alloc")]
+pub(crate) mod deterministic;
 pub mod write;
 
 use crate::data::{IanaTag, Int, Tag, Tagged};
@@ -92,6 +94,34 @@ impl<C, T: CborLen<C> + ?Sized> CborLen<C> for alloc::boxed::Box<T> {
     }
 }
 
+#[cfg(feature = "alloc")]
+impl<C, T: Encode<C> + ?Sized> Encode<C> for alloc::rc::Rc<T> {
+    fn encode<W: Write>(&self, e: &mut Encoder<W>, ctx: &mut C) -> Result<(), Error<W::Error>> {
+        (**self).encode(e, ctx)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<C, T: CborLen<C> + ?Sized> CborLen<C> for alloc::rc::Rc<T> {
+    fn cbor_len(&self, ctx: &mut C) -> usize {
+        (**self).cbor_len(ctx)
+    }
+}
+
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+impl<C, T: Encode<C> + ?Sized> Encode<C> for alloc::sync::Arc<T> {
+    fn encode<W: Write>(&self, e: &mut Encoder<W>, ctx: &mut C) -> Result<(), Error<W::Error>> {
+        (**self).encode(e, ctx)
+    }
+}
+
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+impl<C, T: CborLen<C> + ?Sized> CborLen<C> for alloc::sync::Arc<T> {
+    fn cbor_len(&self, ctx: &mut C) -> usize {
+        (**self).cbor_len(ctx)
+    }
+}
+
 impl<C> Encode<C> for str {
     fn encode<W: Write>(&self, e: &mut Encoder<W>, _: &mut C) -> Result<(), Error<W::Error>> {
         e.str(self)?.ok()
@@ -450,7 +480,7 @@ macro_rules! encode_basic {
     }
 }
 
-encode_basic!(u8 i8 u16 i16 u32 i32 u64 i64 bool f32 f64 char);
+encode_basic!(u8 i8 u16 i16 u32 i32 u64 i64 u128 i128 bool f32 f64 char);
 
 impl<C> CborLen<C> for bool {
     fn cbor_len(&self, _: &mut C) -> usize {
@@ -503,6 +533,16 @@ impl<C> CborLen<C> for u64 {
     }
 }
 
+impl<C> CborLen<C> for u128 {
+    fn cbor_len(&self, ctx: &mut C) -> usize {
+        if let Ok(n) = u64::try_from(*self) {
+            return n.cbor_len(ctx)
+        }
+        // tag 2, byte string head and at most 16 bytes
+        2 + 16 - self.leading_zeros() as usize / 8
+    }
+}
+
 impl<C> CborLen<C> for i8 {
     fn cbor_len(&self, ctx: &mut C) -> usize {
         let x = if *self >= 0 { *self as u8 } else { (-1 - self) as u8 };
@@ -531,6 +571,13 @@ impl<C> CborLen<C> for i64 {
     }
 }
 
+impl<C> CborLen<C> for i128 {
+    fn cbor_len(&self, ctx: &mut C) -> usize {
+        let x = if *self >= 0 { *self as u128 } else { (-1 - self) as u128 };
+        x.cbor_len(ctx)
+    }
+}
+
 impl<C> CborLen<C> for f32 {
     fn cbor_len(&self, _: &mut C) -> usize {
         5
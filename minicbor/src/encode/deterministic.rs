@@ -0,0 +1,156 @@
+//! Deterministic encoding as specified in [RFC 8949, section 4.2][1].
+//!
+//! [1]: https://www.rfc-editor.org/rfc/rfc8949.html#section-4.2
+
+use alloc::vec::Vec;
+use core::convert::Infallible;
+use core::mem;
+
+use crate::data::Type;
+use crate::decode::Decoder;
+use crate::encode::{Encoder, Error};
+
+/// Re-encode the given CBOR item in core deterministic encoding.
+///
+/// - Integers, lengths and tags use their shortest form.
+/// - Floating point values use the shortest exact form.
+/// - Map entries are sorted by the bytewise lexicographic order of
+///   their deterministically encoded keys.
+///
+/// Indefinite-length items and maps with duplicate keys are rejected.
+///
+/// The traversal is iterative, with an explicit stack of open arrays,
+/// maps and tags, so deeply nested input can not overflow the call stack.
+pub(crate) fn canonicalize(input: &[u8]) -> Result<Vec<u8>, Error<Infallible>> {
+    let mut d = Decoder::new(input);
+    let mut e = Encoder::new(Vec::with_capacity(input.len()));
+    let mut stack: Vec<Level> = Vec::new();
+    'items: loop {
+        if let Some(level) = head(&mut d, &mut e)? {
+            if level.remaining > 0 {
+                stack.push(level);
+                continue
+            }
+            level.close(&mut e)?
+        }
+        // The item just completed belongs to the innermost open level.
+        while let Some(top) = stack.last_mut() {
+            if let Kind::Map { key, entries, .. } = &mut top.kind {
+                let b = mem::take(e.writer_mut());
+                match key.take() {
+                    None    => { *key = Some(b); continue 'items }
+                    Some(k) => entries.push((k, b))
+                }
+            }
+            top.remaining -= 1;
+            if top.remaining > 0 {
+                continue 'items
+            }
+            if let Some(level) = stack.pop() {
+                level.close(&mut e)?
+            }
+        }
+        break
+    }
+    if d.position() != input.len() {
+        return Err(Error::message("deterministic encoding requires exactly one data item"))
+    }
+    Ok(e.into_writer())
+}
+
+/// An array, map or tag whose elements are being canonicalized.
+struct Level {
+    /// The number of elements (or map entries) still to come.
+    remaining: u64,
+    kind: Kind
+}
+
+enum Kind {
+    Array,
+    Tag,
+    Map {
+        /// The output written so far, outside of this map.
+        outer: Vec<u8>,
+        /// The encoded key of the current entry, if any.
+        key: Option<Vec<u8>>,
+        /// The encoded entries of this map.
+        entries: Vec<(Vec<u8>, Vec<u8>)>
+    }
+}
+
+impl Level {
+    /// Finish this level after all its elements have been canonicalized.
+    fn close(self, e: &mut Encoder<Vec<u8>>) -> Result<(), Error<Infallible>> {
+        if let Kind::Map { outer, mut entries, .. } = self.kind {
+            *e.writer_mut() = outer;
+            entries.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+            if entries.windows(2).any(|w| w[0].0 == w[1].0) {
+                return Err(Error::message("duplicate map key in deterministic encoding"))
+            }
+            e.map(entries.len() as u64)?;
+            for (k, v) in &entries {
+                e.put(k)?.put(v)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Canonicalize a single CBOR data item, or the head of an array, map or tag.
+///
+/// For arrays, maps and tags, the level of their elements is returned.
+/// Map entries are written to separate buffers, so they can be sorted.
+fn head(d: &mut Decoder<'_>, e: &mut Encoder<Vec<u8>>) -> Result<Option<Level>, Error<Infallible>> {
+    match d.datatype().map_err(Error::custom)? {
+        Type::Bool      => { e.bool(d.bool().map_err(Error::custom)?)?; }
+        Type::Null      => { e.null()?; }
+        Type::Undefined => { e.undefined()?; }
+        Type::Simple    => { e.simple(d.simple().map_err(Error::custom)?)?; }
+        Type::U8
+        | Type::U16
+        | Type::U32
+        | Type::U64
+        | Type::I8
+        | Type::I16
+        | Type::I32
+        | Type::I64
+        | Type::Int     => { e.int(d.int().map_err(Error::custom)?)?; }
+        Type::F16 => {
+            // An `f16` is already as short as it gets, except for NaN payloads.
+            let p = d.position();
+            d.skip().map_err(Error::custom)?;
+            let b = &d.input()[p .. d.position()];
+            if b[1] & 0x7c == 0x7c && (b[1] & 0x03 != 0 || b[2] != 0) {
+                e.float(f64::NAN)?;
+            } else {
+                e.put(b)?;
+            }
+        }
+        Type::F32    => { e.float(f64::from(d.f32().map_err(Error::custom)?))?; }
+        Type::F64    => { e.float(d.f64().map_err(Error::custom)?)?; }
+        Type::Bytes  => { e.bytes(d.bytes().map_err(Error::custom)?)?; }
+        Type::String => { e.str(d.str().map_err(Error::custom)?)?; }
+        Type::Array  => {
+            let n = d.array().map_err(Error::custom)?.unwrap_or_default();
+            e.array(n)?;
+            return Ok(Some(Level { remaining: n, kind: Kind::Array }))
+        }
+        Type::Map => {
+            let n = d.map().map_err(Error::custom)?.unwrap_or_default();
+            let outer = mem::take(e.writer_mut());
+            let kind = Kind::Map { outer, key: None, entries: Vec::new() };
+            return Ok(Some(Level { remaining: n, kind }))
+        }
+        Type::Tag => {
+            e.tag(d.tag().map_err(Error::custom)?)?;
+            return Ok(Some(Level { remaining: 1, kind: Kind::Tag }))
+        }
+        Type::BytesIndef | Type::StringIndef | Type::ArrayIndef | Type::MapIndef => {
+            return Err(Error::message("indefinite-length items are not allowed in deterministic encoding"))
+        }
+        t @ (Type::Break | Type::Unknown(_)) => {
+            return Err(Error::message(format_args!("unexpected type {t} in deterministic encoding")))
+        }
+    }
+    Ok(None)
+}
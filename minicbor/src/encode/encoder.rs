@@ -1,15 +1,31 @@
 use crate::{SIGNED, BYTES, TEXT, ARRAY, MAP, TAGGED, SIMPLE};
-use crate::data::{Int, Tag};
+
+#[cfg(feature = "alloc")]
+use crate::BREAK;
+use crate::data::{IanaTag, Int, Tag};
 use crate::encode::{Encode, Error, Write};
 
 /// A non-allocating CBOR encoder writing encoded bytes to the given [`Write`] sink.
+///
+/// The only exception are string references (cf. [`StringRefs`]) which
+/// require the encoder to remember the strings encoded so far.
+///
+/// [`StringRefs`]: crate::data::StringRefs
 #[derive(Debug, Clone)]
-pub struct Encoder<W> { writer: W }
+pub struct Encoder<W> {
+    writer: W,
+    #[cfg(feature = "alloc")]
+    strings: Option<alloc::boxed::Box<StringTables>>
+}
 
 impl<W: Write> Encoder<W> {
     /// Construct an `Encoder` that writes to the given [`Write`] sink.
     pub fn new(writer: W) -> Encoder<W> {
-        Encoder { writer }
+        Encoder {
+            writer,
+            #[cfg(feature = "alloc")]
+            strings: None
+        }
     }
 
     /// Access the inner writer.
@@ -144,6 +160,40 @@ impl<W: Write> Encoder<W> {
         }
     }
 
+    /// Encode a `u128` value.
+    ///
+    /// Values up to `u64::MAX` are encoded as regular integers, larger ones
+    /// as positive bignums (tag 2) without leading zero bytes.
+    pub fn u128(&mut self, x: u128) -> Result<&mut Self, Error<W::Error>> {
+        if let Ok(n) = u64::try_from(x) {
+            return self.u64(n)
+        }
+        self.bignum(IanaTag::PosBignum, x)
+    }
+
+    /// Encode an `i128` value.
+    ///
+    /// Values within the range of [`Int`] are encoded as regular integers,
+    /// others as positive (tag 2) or negative (tag 3) bignums without leading
+    /// zero bytes.
+    pub fn i128(&mut self, x: i128) -> Result<&mut Self, Error<W::Error>> {
+        if x >= 0 {
+            return self.u128(x as u128)
+        }
+        let n = (-1 - x) as u128;
+        if let Ok(n) = u64::try_from(n) {
+            return self.int(Int::neg(n))
+        }
+        self.bignum(IanaTag::NegBignum, n)
+    }
+
+    /// Encode a bignum with the given tag.
+    fn bignum(&mut self, t: IanaTag, x: u128) -> Result<&mut Self, Error<W::Error>> {
+        let b = x.to_be_bytes();
+        let i = x.leading_zeros() as usize / 8;
+        self.tag(t)?.bytes(&b[i ..])
+    }
+
     /// Encode a CBOR `null` value.
     pub fn null(&mut self) -> Result<&mut Self, Error<W::Error>> {
         self.put(&[SIMPLE | 22])
@@ -196,6 +246,37 @@ impl<W: Write> Encoder<W> {
         self.put(&[SIMPLE | 27])?.put(&x.to_be_bytes()[..])
     }
 
+    /// Encode an `f64` value using the shortest exact floating point encoding.
+    ///
+    /// The value is encoded as `f16` or `f32` if this is possible without
+    /// loss of precision and as `f64` otherwise (aka "preferred serialization").
+    /// Infinities are encoded as `f16`. All NaN values are encoded as the
+    /// canonical `f16` NaN `0xf97e00`, i.e. NaN payloads are not preserved.
+    ///
+    /// See also [`crate::float`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut e = minicbor::Encoder::new(Vec::new());
+    /// e.float(1.5)?.float(100000.0)?.float(0.1)?;
+    /// assert_eq!(e.writer().len(), 3 + 5 + 9);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn float(&mut self, x: f64) -> Result<&mut Self, Error<W::Error>> {
+        if x.is_nan() {
+            return self.put(&[SIMPLE | 25, 0x7e, 0])
+        }
+        let y = x as f32;
+        if f64::from(y) != x {
+            return self.f64(x)
+        }
+        if let Some(n) = crate::float::f16_bits(y) {
+            return self.put(&[SIMPLE | 25])?.put(&n.to_be_bytes()[..])
+        }
+        self.f32(y)
+    }
+
     /// Encode a `bool` value.
     pub fn bool(&mut self, x: bool) -> Result<&mut Self, Error<W::Error>> {
         self.put(&[SIMPLE | if x { 0x15 } else { 0x14 }])
@@ -212,12 +293,34 @@ impl<W: Write> Encoder<W> {
     }
 
     /// Encode a byte slice.
+    ///
+    /// Within a string reference namespace (cf. [`StringRefs`]), a byte
+    /// slice that has been encoded before is replaced by a reference (tag 25).
+    ///
+    /// [`StringRefs`]: crate::data::StringRefs
     pub fn bytes(&mut self, x: &[u8]) -> Result<&mut Self, Error<W::Error>> {
+        #[cfg(feature = "alloc")]
+        if self.strings.is_some() {
+            if let Some(i) = self.string_ref(false, x) {
+                return self.tag(IanaTag::StringRef)?.u64(i)
+            }
+        }
         self.type_len(BYTES, x.len() as u64)?.put(x)
     }
 
     /// Encode a string slice.
+    ///
+    /// Within a string reference namespace (cf. [`StringRefs`]), a string
+    /// slice that has been encoded before is replaced by a reference (tag 25).
+    ///
+    /// [`StringRefs`]: crate::data::StringRefs
     pub fn str(&mut self, x: &str) -> Result<&mut Self, Error<W::Error>> {
+        #[cfg(feature = "alloc")]
+        if self.strings.is_some() {
+            if let Some(i) = self.string_ref(true, x.as_bytes()) {
+                return self.tag(IanaTag::StringRef)?.u64(i)
+            }
+        }
         self.type_len(TEXT, x.len() as u64)?.put(x.as_bytes())
     }
 
@@ -242,6 +345,10 @@ impl<W: Write> Encoder<W> {
     ///
     /// Use [`Encoder::end`] to terminate.
     pub fn begin_bytes(&mut self) -> Result<&mut Self, Error<W::Error>> {
+        #[cfg(feature = "alloc")]
+        if let Some(s) = &mut self.strings {
+            s.chunks = true
+        }
         self.put(&[0x5f])
     }
 
@@ -256,11 +363,19 @@ impl<W: Write> Encoder<W> {
     ///
     /// Use [`Encoder::end`] to terminate.
     pub fn begin_str(&mut self) -> Result<&mut Self, Error<W::Error>> {
+        #[cfg(feature = "alloc")]
+        if let Some(s) = &mut self.strings {
+            s.chunks = true
+        }
         self.put(&[0x7f])
     }
 
     /// Terminate an indefinite collection.
     pub fn end(&mut self) -> Result<&mut Self, Error<W::Error>> {
+        #[cfg(feature = "alloc")]
+        if let Some(s) = &mut self.strings {
+            s.chunks = false
+        }
         self.put(&[0xff])
     }
 
@@ -287,8 +402,105 @@ impl<W: Write> Encoder<W> {
         Ok(self)
     }
 
+    /// Begin a new string reference namespace.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn begin_string_refs(&mut self) {
+        self.strings.get_or_insert_with(Default::default).tables.push(StringTable::default())
+    }
+
+    /// End the current string reference namespace.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn end_string_refs(&mut self) {
+        if let Some(s) = &mut self.strings {
+            s.tables.pop();
+            if s.tables.is_empty() {
+                self.strings = None
+            }
+        }
+    }
+
+    /// Is a string reference namespace active?
+    #[cfg(feature = "alloc")]
+    pub(crate) fn in_string_refs(&self) -> bool {
+        self.strings.is_some()
+    }
+
+    /// Look up the index of a string in the current string reference namespace.
+    ///
+    /// If the string is not found, it is added if it is long enough and
+    /// `None` is returned. Chunks of indefinite strings are never considered.
+    #[cfg(feature = "alloc")]
+    #[inline(never)]
+    fn string_ref(&mut self, text: bool, x: &[u8]) -> Option<u64> {
+        let s = self.strings.as_mut()?;
+        if s.chunks {
+            return None
+        }
+        let t = s.tables.last_mut()?;
+        let m = if text { &t.text } else { &t.bytes };
+        if let Some(i) = m.get(x) {
+            return Some(*i)
+        }
+        self.add_string(text, x);
+        None
+    }
+
+    /// Add a string to the current string reference namespace if it is long
+    /// enough.
+    ///
+    /// Like the decoder, every string is counted, even if it occurred before,
+    /// in which case references continue to use the first index.
+    #[cfg(feature = "alloc")]
+    fn add_string(&mut self, text: bool, x: &[u8]) {
+        if let Some(t) = self.strings.as_mut().and_then(|s| s.tables.last_mut()) {
+            if x.len() >= crate::data::min_string_ref_len(t.len) {
+                let m = if text { &mut t.text } else { &mut t.bytes };
+                m.entry(x.to_vec()).or_insert(t.len);
+                t.len += 1
+            }
+        }
+    }
+
+    /// Add the strings of an encoded data item to the current string
+    /// reference namespace, just as a decoder does when reading the item.
+    ///
+    /// To be called before writing pre-encoded bytes (cf. [`Raw`]).
+    /// Nested namespaces and string references are skipped over.
+    ///
+    /// [`Raw`]: crate::data::Raw
+    #[cfg(feature = "alloc")]
+    pub(crate) fn add_strings(&mut self, item: &[u8]) -> Result<(), Error<W::Error>> {
+        if self.strings.is_none() {
+            return Ok(())
+        }
+        let mut d = crate::Decoder::new(item);
+        while let Some(&b) = item.get(d.position()) {
+            match b {
+                BYTES ..= 0x5b => {
+                    let x = d.bytes().map_err(Error::custom)?;
+                    self.add_string(false, x)
+                }
+                TEXT ..= 0x7b => {
+                    let x = d.str().map_err(Error::custom)?;
+                    self.add_string(true, x.as_bytes())
+                }
+                ARRAY ..= 0x9f => { d.array().map_err(Error::custom)?; }
+                MAP   ..= 0xbf => { d.map().map_err(Error::custom)?; }
+                TAGGED ..= 0xdb => {
+                    let t = d.tag().map_err(Error::custom)?;
+                    if t == IanaTag::StringRef.tag() || t == IanaTag::StringRefNamespace.tag() {
+                        d.skip().map_err(Error::custom)?
+                    }
+                }
+                BREAK => d.set_position(d.position() + 1),
+                _ => d.skip().map_err(Error::custom)?
+            }
+        }
+        Ok(())
+    }
+
     /// Write type and length information.
-    fn type_len(&mut self, t: u8, x: u64) -> Result<&mut Self, Error<W::Error>> {
+    pub(crate) fn type_len(&mut self, t: u8, x: u64) -> Result<&mut Self, Error<W::Error>> {
         match x {
             0        ..= 0x17        => self.put(&[t | x as u8]),
             0x18     ..= 0xff        => self.put(&[t | 24, x as u8]),
@@ -299,3 +511,21 @@ impl<W: Write> Encoder<W> {
     }
 }
 
+/// The string tables of all active string reference namespaces.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Default)]
+struct StringTables {
+    /// One table per namespace, the innermost last.
+    tables: alloc::vec::Vec<StringTable>,
+    /// Are we encoding the chunks of an indefinite string?
+    chunks: bool
+}
+
+/// The strings of a string reference namespace and their indices.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Default)]
+struct StringTable {
+    text: alloc::collections::BTreeMap<alloc::vec::Vec<u8>, u64>,
+    bytes: alloc::collections::BTreeMap<alloc::vec::Vec<u8>, u64>,
+    len: u64
+}
@@ -0,0 +1,267 @@
+//! Preferred serialization of floating point values.
+//!
+//! [`Encoder::f32`] and [`Encoder::f64`] always use the full width of the
+//! respective type. RFC 8949 ([section 4.1][1]) recommends to use the shortest
+//! floating point encoding that preserves the value instead. This is what
+//! [`Encoder::float`] does.
+//!
+//! The [`Encode`] impls of `f32` and `f64` always use the full width and their
+//! [`CborLen`] impls report 5 and 9 bytes respectively. For fields of types
+//! deriving `Encode`, `Decode` and `CborLen`, preferred serialization can be
+//! selected individually: fields of type `f32`, `f64` or `Option`s thereof can
+//! be annotated with `#[cbor(with = "minicbor::float")]`. The length computed
+//! by [`cbor_len`] always matches the encoding produced by [`encode`](fn@encode)
+//! and [`decode`](fn@decode) accepts `f16` values even without feature `"half"`.
+//!
+//! # Example
+//!
+//! ```
+//! use minicbor::{float, Decoder, Encoder};
+//!
+//! let mut e = Encoder::new(Vec::new());
+//! float::encode(&Some(1.5f64), &mut e, &mut ())?;
+//! float::encode(&0.1f64, &mut e, &mut ())?;
+//!
+//! assert_eq!(3, float::cbor_len(&Some(1.5f64), &mut ()));
+//! assert_eq!(9, float::cbor_len(&0.1f64, &mut ()));
+//! assert_eq!(3 + 9, e.writer().len());
+//!
+//! let mut d = Decoder::new(e.writer());
+//! assert_eq!(Some(1.5), float::decode::<_, Option<f64>>(&mut d, &mut ())?);
+//! assert_eq!(0.1, float::decode::<_, f64>(&mut d, &mut ())?);
+//! # Ok::<_, Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! [1]: https://www.rfc-editor.org/rfc/rfc8949.html#section-4.1
+
+use crate::decode::{self, Decoder};
+use crate::encode::{self, Encoder, Write};
+
+use crate::data::Type;
+
+#[cfg(doc)]
+use crate::encode::{Encode, CborLen};
+
+/// Floating point values which are encoded in their shortest form.
+///
+/// Implemented for `f32`, `f64` and `Option`s of those.
+pub trait Float: Sized {
+    /// Encode this value in its shortest form.
+    fn encode_float<W: Write>(&self, e: &mut Encoder<W>) -> Result<(), encode::Error<W::Error>>;
+
+    /// Decode a value of this type, accepting any floating point width.
+    fn decode_float(d: &mut Decoder<'_>) -> Result<Self, decode::Error>;
+
+    /// The length in bytes of the encoding produced by [`Float::encode_float`].
+    fn float_len(&self) -> usize;
+
+    /// Like [`Encode::is_nil`].
+    fn is_nil(&self) -> bool {
+        false
+    }
+
+    /// Like [`Decode::nil`](crate::Decode::nil).
+    fn nil() -> Option<Self> {
+        None
+    }
+}
+
+impl Float for f64 {
+    fn encode_float<W: Write>(&self, e: &mut Encoder<W>) -> Result<(), encode::Error<W::Error>> {
+        e.float(*self)?.ok()
+    }
+
+    fn decode_float(d: &mut Decoder<'_>) -> Result<Self, decode::Error> {
+        if let Some(x) = decode_f16(d)? {
+            return Ok(f64::from(x))
+        }
+        d.f64()
+    }
+
+    fn float_len(&self) -> usize {
+        len(*self)
+    }
+}
+
+impl Float for f32 {
+    fn encode_float<W: Write>(&self, e: &mut Encoder<W>) -> Result<(), encode::Error<W::Error>> {
+        e.float(f64::from(*self))?.ok()
+    }
+
+    fn decode_float(d: &mut Decoder<'_>) -> Result<Self, decode::Error> {
+        if let Some(x) = decode_f16(d)? {
+            return Ok(x)
+        }
+        d.f32()
+    }
+
+    fn float_len(&self) -> usize {
+        len(f64::from(*self))
+    }
+}
+
+impl<T: Float> Float for Option<T> {
+    fn encode_float<W: Write>(&self, e: &mut Encoder<W>) -> Result<(), encode::Error<W::Error>> {
+        if let Some(x) = self {
+            x.encode_float(e)
+        } else {
+            e.null()?.ok()
+        }
+    }
+
+    fn decode_float(d: &mut Decoder<'_>) -> Result<Self, decode::Error> {
+        if Type::Null == d.datatype()? {
+            d.skip()?;
+            return Ok(None)
+        }
+        T::decode_float(d).map(Some)
+    }
+
+    fn float_len(&self) -> usize {
+        if let Some(x) = self {
+            x.float_len()
+        } else {
+            1
+        }
+    }
+
+    fn is_nil(&self) -> bool {
+        self.is_none()
+    }
+
+    fn nil() -> Option<Self> {
+        Some(None)
+    }
+}
+
+/// Freestanding function calling `Float::decode_float`.
+///
+/// For use in `#[cbor(with = "minicbor::float")]` or `#[cbor(decode_with =
+/// "minicbor::float::decode")]`.
+pub fn decode<'b, C, T>(d: &mut Decoder<'b>, _: &mut C) -> Result<T, decode::Error>
+where
+    T: Float
+{
+    T::decode_float(d)
+}
+
+/// Freestanding function calling `Float::nil`.
+///
+/// For use in `#[cbor(with = "minicbor::float")]` or `#[cbor(nil =
+/// "minicbor::float::nil")]`.
+pub fn nil<T>() -> Option<T>
+where
+    T: Float
+{
+    T::nil()
+}
+
+/// Freestanding function calling `Float::encode_float`.
+///
+/// For use in `#[cbor(with = "minicbor::float")]` or `#[cbor(encode_with =
+/// "minicbor::float::encode")]`.
+pub fn encode<C, T, W>(x: &T, e: &mut Encoder<W>, _: &mut C) -> Result<(), encode::Error<W::Error>>
+where
+    T: Float,
+    W: Write
+{
+    x.encode_float(e)
+}
+
+/// Freestanding function calling `Float::is_nil`.
+///
+/// For use in `#[cbor(with = "minicbor::float")]` or `#[cbor(is_nil =
+/// "minicbor::float::is_nil")]`.
+pub fn is_nil<T>(x: &T) -> bool
+where
+    T: Float
+{
+    x.is_nil()
+}
+
+/// Freestanding function calling `Float::float_len`.
+///
+/// For use in `#[cbor(with = "minicbor::float")]` or `#[cbor(cbor_len =
+/// "minicbor::float::cbor_len")]`.
+pub fn cbor_len<C, T>(x: &T, _: &mut C) -> usize
+where
+    T: Float
+{
+    x.float_len()
+}
+
+/// Decode an `f16` value if the next item is one.
+fn decode_f16(d: &mut Decoder<'_>) -> Result<Option<f32>, decode::Error> {
+    if Type::F16 != d.datatype()? {
+        return Ok(None)
+    }
+    #[cfg(feature = "half")]
+    let x = d.f16()?;
+    #[cfg(not(feature = "half"))]
+    let x = f16_to_f32(d.f16_bits()?);
+    Ok(Some(x))
+}
+
+/// The length in bytes of the shortest encoding of the given value.
+pub(crate) fn len(x: f64) -> usize {
+    if x.is_nan() {
+        return 3
+    }
+    let y = x as f32;
+    if f64::from(y) != x {
+        return 9
+    }
+    if f16_bits(y).is_some() { 3 } else { 5 }
+}
+
+/// Get the bits of the `f16` value which is equal to the given `f32`.
+///
+/// Returns `None` if `x` can not be represented exactly as `f16`.
+/// NaN payloads are not preserved.
+pub(crate) fn f16_bits(x: f32) -> Option<u16> {
+    let b = x.to_bits();
+    let s = ((b >> 16) & 0x8000) as u16;
+    let e = ((b >> 23) & 0xff) as i32;
+    let m = b & 0x7f_ffff;
+    match e {
+        0xff if m == 0 => Some(s | 0x7c00),
+        0xff           => Some(0x7e00),
+        0    if m == 0 => Some(s),
+        0              => None,
+        _ => {
+            let e = e - 127;
+            if !(-24 ..= 15).contains(&e) {
+                return None
+            }
+            if e >= -14 {
+                // normal `f16`, the 13 lower mantissa bits are lost
+                (m & 0x1fff == 0).then(|| s | ((e + 15) as u16) << 10 | (m >> 13) as u16)
+            } else {
+                // subnormal `f16` with an implicit exponent of -24
+                let m = m | 0x80_0000;
+                let k = (-1 - e) as u32;
+                (m & ((1 << k) - 1) == 0).then(|| s | (m >> k) as u16)
+            }
+        }
+    }
+}
+
+/// Convert the bits of an `f16` value to an `f32`.
+///
+/// Used for decoding `f16` values without feature `"half"`.
+#[cfg(not(feature = "half"))]
+fn f16_to_f32(b: u16) -> f32 {
+    let s = u32::from(b & 0x8000) << 16;
+    let e = u32::from((b >> 10) & 0x1f);
+    let m = u32::from(b & 0x3ff);
+    match e {
+        0x1f => f32::from_bits(s | 0x7f80_0000 | m << 13),
+        0 if m == 0 => f32::from_bits(s),
+        0 => {
+            // subnormal `f16`, always a normal `f32`
+            let x = m as f32 * (1.0 / (1 << 24) as f32);
+            if s == 0 { x } else { -x }
+        }
+        _ => f32::from_bits(s | (e + 127 - 15) << 23 | m << 13)
+    }
+}
@@ -17,9 +17,10 @@
 //! [`data::Type`] that can represent every possible CBOR type and decoding
 //! can thus proceed based on this information. It is also possible to just
 //! tokenize the input bytes using a [`Tokenizer`](decode::Tokenizer), i.e.
-//! an `Iterator` over CBOR [`Token`](data::Token)s. Finally, the length
-//! in bytes of a value's CBOR representation can be calculated if the
-//! value's type implements the [`CborLen`] trait.
+//! an `Iterator` over CBOR [`Token`](data::Token)s, or to decode them into
+//! a dynamically typed [`Value`](data::Value) (*requires feature* `"alloc"`).
+//! Finally, the length in bytes of a value's CBOR representation can be
+//! calculated if the value's type implements the [`CborLen`] trait.
 //!
 //! Optionally, `Encode` and `Decode` can be derived for structs and enums
 //! using the respective derive macros (*requires feature* `"derive"`).
@@ -32,6 +33,11 @@
 //! [1]: https://docs.rs/minicbor_io/
 //! [2]: https://crates.io/crates/serde
 //! [3]: https://crates.io/crates/minicbor-serde
+//! [4]: https://crates.io/crates/num-bigint
+//! [5]: https://crates.io/crates/rust_decimal
+//! [6]: https://crates.io/crates/chrono
+//! [7]: https://crates.io/crates/time
+//! [8]: https://crates.io/crates/uuid
 //!
 //! # Feature flags
 //!
@@ -44,6 +50,25 @@
 //!
 //! - `"derive"`: Allows deriving [`Encode`] and [`Decode`] traits.
 //!
+//! - `"num-bigint"`: Implies `"alloc"` and implements [`Encode`], [`Decode`]
+//!   and [`CborLen`] for `BigInt` and `BigUint` of the [`num-bigint`][4]
+//!   crate using bignums (tags 2 and 3).
+//!
+//! - `"rust_decimal"`: Implements [`Encode`], [`Decode`] and [`CborLen`]
+//!   for `Decimal` of the [`rust_decimal`][5] crate using decimal fractions
+//!   (tag 4, cf. [`data::Decimal`]).
+//!
+//! - `"chrono"`: Implements [`Encode`], [`Decode`] and [`CborLen`] for
+//!   `DateTime` and `NaiveDate` of the [`chrono`][6] crate using tag 0 and
+//!   tag 1004 (cf. [`data::DateTime`] and [`data::Date`]).
+//!
+//! - `"time"`: Implements [`Encode`], [`Decode`] and [`CborLen`] for
+//!   `OffsetDateTime` and `Date` of the [`time`][7] crate using tag 0 and
+//!   tag 1004 (cf. [`data::DateTime`] and [`data::Date`]).
+//!
+//! - `"uuid"`: Implements [`Encode`], [`Decode`] and [`CborLen`] for `Uuid`
+//!   of the [`uuid`][8] crate using tag 37 (cf. [`uuid`]).
+//!
 //! # Example: generic encoding and decoding
 //!
 //! ```
@@ -144,6 +169,16 @@ pub mod bytes;
 pub mod data;
 pub mod decode;
 pub mod encode;
+pub mod float;
+
+#[cfg(all(feature = "alloc", feature = "half"))]
+pub mod diag;
+
+#[cfg(feature = "uuid")]
+pub mod uuid;
+
+#[cfg(feature = "num-bigint")]
+mod bigint;
 
 const UNSIGNED: u8 = 0x00;
 const SIGNED: u8   = 0x20;
@@ -183,6 +218,73 @@ where
     Decoder::new(b).decode_with(ctx)
 }
 
+/// Decode a type implementing [`Decode`] from the given byte slice and
+/// check that no bytes remain.
+///
+/// Unlike [`decode`](fn@decode), which ignores any bytes after the decoded value, this
+/// function returns an error for which [`decode::Error::is_trailing_data`]
+/// returns `true` if the input is not consumed completely.
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(1, minicbor::decode_exact::<u8>(&[0x01])?);
+/// assert_eq!(1, minicbor::decode::<u8>(&[0x01, 0x02])?);
+///
+/// let e = minicbor::decode_exact::<u8>(&[0x01, 0x02]).unwrap_err();
+/// assert!(e.is_trailing_data());
+/// assert_eq!(Some(1), e.position());
+/// # Ok::<_, Box<dyn core::error::Error>>(())
+/// ```
+pub fn decode_exact<'b, T>(b: &'b [u8]) -> Result<T, decode::Error>
+where
+    T: Decode<'b, ()>
+{
+    decode_exact_with(b, &mut ())
+}
+
+/// Decode a type implementing [`Decode`] from the given byte slice and
+/// check that no bytes remain.
+///
+/// See [`decode_exact`] for details.
+pub fn decode_exact_with<'b, C, T>(b: &'b [u8], ctx: &mut C) -> Result<T, decode::Error>
+where
+    T: Decode<'b, C>
+{
+    let mut d = Decoder::new(b);
+    let x = d.decode_with(ctx)?;
+    if d.position() != b.len() {
+        return Err(decode::Error::trailing_data().at(d.position()))
+    }
+    Ok(x)
+}
+
+/// Decode a type implementing [`Decode`] from the given reader.
+///
+/// The item is read into memory in its entirety before it is decoded and
+/// the reader may be advanced beyond the end of the decoded item. Use a
+/// [`decode::ReadDecoder`] to decode more than one item, or to decode large
+/// arrays and maps one element at a time in bounded memory.
+///
+/// *Requires feature* `"std"`.
+///
+/// # Example
+///
+/// ```
+/// let file = std::io::Cursor::new([0x82, 0x61, 0x61, 0x61, 0x62]); // ["a", "b"]
+/// let v: Vec<String> = minicbor::decode_from_reader(file)?;
+/// assert_eq!(v, ["a", "b"]);
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+#[cfg(feature = "std")]
+pub fn decode_from_reader<T, R>(reader: R) -> Result<T, decode::Error>
+where
+    T: for<'a> Decode<'a, ()>,
+    R: std::io::Read
+{
+    decode::ReadDecoder::new(reader).decode()?.ok_or_else(decode::Error::end_of_input)
+}
+
 /// Encode a type implementing [`Encode`] to the given [`encode::Write`] impl.
 pub fn encode<T, W>(x: T, w: W) -> Result<(), encode::Error<W::Error>>
 where
@@ -201,6 +303,34 @@ where
     Encoder::new(w).encode_with(x, ctx)?.ok()
 }
 
+/// Encode a sequence of values as consecutive top-level data items
+/// ([RFC 8742][1]) to the given [`encode::Write`] impl.
+///
+/// Use [`Decoder::sequence`] to decode the items again.
+///
+/// # Example
+///
+/// ```
+/// let mut buf = [0; 4];
+/// minicbor::encode_sequence(["a", "b"], buf.as_mut())?;
+/// assert_eq!(buf, [0x61, 0x61, 0x61, 0x62]);
+/// # Ok::<_, Box<dyn core::error::Error>>(())
+/// ```
+///
+/// [1]: https://www.rfc-editor.org/rfc/rfc8742.html
+pub fn encode_sequence<I, W>(items: I, w: W) -> Result<(), encode::Error<W::Error>>
+where
+    I: IntoIterator,
+    I::Item: Encode<()>,
+    W: encode::Write
+{
+    let mut e = Encoder::new(w);
+    for x in items {
+        e.encode(x)?;
+    }
+    Ok(())
+}
+
 /// Encode a type implementing [`Encode`] and return the encoded byte vector.
 ///
 /// *Requires feature* `"alloc"`.
@@ -227,6 +357,63 @@ where
     Ok(e.into_writer())
 }
 
+/// Encode a type implementing [`Encode`] deterministically and return the
+/// encoded byte vector.
+///
+/// The result follows the core deterministic encoding requirements of
+/// [RFC 8949, section 4.2.1][1]:
+///
+/// - Integers, lengths and tags are encoded in their shortest form.
+/// - Floating point values are encoded in the shortest form that preserves
+///   their value, i.e. `f64`s may become `f32`s or `f16`s. All NaNs are
+///   encoded as `0xf97e00`.
+/// - The entries of every map are sorted by the bytewise lexicographic
+///   order of their encoded keys.
+///
+/// Any existing [`Encode`] impl, including derived ones, can be used. It is
+/// an error if the value contains indefinite-length items or maps with
+/// duplicate keys. Please note that [`len`] does not account for shorter
+/// floating point values.
+///
+/// # Example
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// let m = HashMap::from([(100, 1.5), (-1, 0.1), (10, 65504.0)]);
+/// let v = minicbor::to_vec_deterministic(&m)?;
+///
+/// assert_eq!(v, [0xa3, 0x0a, 0xf9, 0x7b, 0xff, 0x18, 0x64, 0xf9, 0x3e, 0x00,
+///                0x20, 0xfb, 0x3f, 0xb9, 0x99, 0x99, 0x99, 0x99, 0x99, 0x9a]);
+///
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+///
+/// *Requires feature* `"alloc"`.
+///
+/// [1]: https://www.rfc-editor.org/rfc/rfc8949.html#section-4.2.1
+#[cfg(feature = "alloc")]
+pub fn to_vec_deterministic<T>(x: T) -> Result<Vec<u8>, encode::Error<Infallible>>
+where
+    T: Encode<()>
+{
+    encode::deterministic::canonicalize(&to_vec(x)?)
+}
+
+/// Encode a type implementing [`Encode`] deterministically and return the
+/// encoded byte vector.
+///
+/// See [`to_vec_deterministic`] for details.
+///
+/// *Requires feature* `"alloc"`.
+#[cfg(feature = "alloc")]
+pub fn to_vec_deterministic_with<C, T>(x: T, ctx: &mut C) -> Result<Vec<u8>, encode::Error<Infallible>>
+where
+    T: Encode<C>
+{
+    encode::deterministic::canonicalize(&to_vec_with(x, ctx)?)
+}
+
 /// Display the given CBOR bytes in [diagnostic notation][1].
 ///
 /// *Requires features* `"alloc"` *and* `"half"`.
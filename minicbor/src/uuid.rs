@@ -0,0 +1,74 @@
+//! Support for [`uuid::Uuid`].
+//!
+//! A `Uuid` is encoded as tag 37 ([`IanaTag::Uuid`]) followed by a byte
+//! string of length 16. Its `Decode` impl requires the tag. Data which
+//! contains untagged UUIDs (e.g. encoded as [`ByteArray<16>`]) can be
+//! decoded with [`decode_lenient`], which accepts both forms, e.g. by using
+//! `#[cbor(decode_with = "minicbor::uuid::decode_lenient")]` in a derived
+//! `Decode` impl.
+//!
+//! [`ByteArray<16>`]: crate::bytes::ByteArray
+//!
+//! # Example
+//!
+//! ```
+//! use minicbor::Decoder;
+//! use uuid::Uuid;
+//!
+//! let id = Uuid::from_u128(0x8f5a_c6b0_61a3_4a6f_9d6e_3d44_0b7e_1c2a);
+//!
+//! let input = minicbor::to_vec(id)?;
+//! assert_eq!([0xd8, 0x25, 0x50], input[.. 3]);
+//! assert_eq!(id, minicbor::decode::<Uuid>(&input)?);
+//!
+//! // Without tag 37.
+//! let input = minicbor::to_vec(minicbor::bytes::ByteArray::from(*id.as_bytes()))?;
+//! assert!(minicbor::decode::<Uuid>(&input).is_err());
+//! assert_eq!(id, minicbor::uuid::decode_lenient(&mut Decoder::new(&input), &mut ())?);
+//! # Ok::<_, Box<dyn core::error::Error>>(())
+//! ```
+
+use ::uuid::Uuid;
+
+use crate::data::{IanaTag, Type};
+use crate::decode::{self, Decode, Decoder};
+use crate::encode::{self, CborLen, Encode, Encoder, Write};
+
+impl<C> Encode<C> for Uuid {
+    fn encode<W: Write>(&self, e: &mut Encoder<W>, _: &mut C) -> Result<(), encode::Error<W::Error>> {
+        e.tag(IanaTag::Uuid)?.bytes(self.as_bytes())?.ok()
+    }
+}
+
+impl<C> CborLen<C> for Uuid {
+    fn cbor_len(&self, ctx: &mut C) -> usize {
+        IanaTag::Uuid.cbor_len(ctx) + 17
+    }
+}
+
+impl<'b, C> Decode<'b, C> for Uuid {
+    fn decode(d: &mut Decoder<'b>, _: &mut C) -> Result<Self, decode::Error> {
+        let p = d.position();
+        let t = d.tag()?;
+        if t != IanaTag::Uuid.tag() {
+            return Err(decode::Error::tag_mismatch(t).with_message("expected tag 37").at(p))
+        }
+        uuid_bytes(d)
+    }
+}
+
+/// Decode a `Uuid` with or without tag 37.
+///
+/// For use in `#[cbor(decode_with = "minicbor::uuid::decode_lenient")]`.
+pub fn decode_lenient<'b, C>(d: &mut Decoder<'b>, ctx: &mut C) -> Result<Uuid, decode::Error> {
+    if d.datatype()? == Type::Bytes {
+        return uuid_bytes(d)
+    }
+    Uuid::decode(d, ctx)
+}
+
+fn uuid_bytes(d: &mut Decoder<'_>) -> Result<Uuid, decode::Error> {
+    let p = d.position();
+    let b = d.bytes()?;
+    Uuid::from_slice(b).map_err(|_| decode::Error::message("expected 16 bytes").at(p))
+}